@@ -0,0 +1,30 @@
+use dotenv::dotenv;
+use game_night_web::db;
+use std::env;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv().ok();
+    env_logger::init();
+
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 3 {
+        println!("Usage: cargo run --example ensure_admin <username> <password>");
+        return Ok(());
+    }
+
+    let username = &args[1];
+    let password = &args[2];
+
+    // Initialize database pool and bring the schema up to date
+    let pool = db::init_pool().await;
+    db::MIGRATOR.run(&pool).await?;
+
+    if db::ensure_admin(&pool, username, password).await? {
+        println!("✅ Created admin user '{}'", username);
+    } else {
+        println!("An admin user already exists. Nothing to do.");
+    }
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use bcrypt;
+use game_night_web::models::User;
 use std::env;
 
 fn main() {
@@ -11,8 +11,9 @@ fn main() {
     
     let password = &args[1];
     
-    match bcrypt::hash(password, 12) {
-        Ok(hash) => println!("Bcrypt hash for '{}': {}", password, hash),
+    // Uses PASSWORD_HASH_ALGO, like the server
+    match User::hash_password(password) {
+        Ok(hash) => println!("Hash for '{}': {}", password, hash),
         Err(e) => println!("Error generating hash: {}", e),
     }
 }
\ No newline at end of file
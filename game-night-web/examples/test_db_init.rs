@@ -32,7 +32,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // Verify admin user was created
     let admin_users: Vec<User> = sqlx::query_as::<_, User>(
-        "SELECT id, username, password_hash, is_admin, created_at FROM users WHERE is_admin = 1"
+        "SELECT id, username, password_hash, is_admin, created_at, session_epoch, last_login_at, vote_weight, disabled, has_onboarded FROM users WHERE is_admin = 1"
     )
     .fetch_all(&pool)
     .await?;
@@ -1,8 +1,6 @@
 use dotenv::dotenv;
 use game_night_web::db;
 use game_night_web::models::User;
-use std::time::Duration;
-use tokio::time::sleep;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -34,7 +32,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // Verify the admin user was created correctly
     let admin_users: Vec<User> = sqlx::query_as::<_, User>(
-        "SELECT id, username, password_hash, is_admin, created_at FROM users WHERE is_admin = 1"
+        "SELECT id, username, password_hash, is_admin, created_at, session_epoch, last_login_at, vote_weight, disabled, has_onboarded FROM users WHERE is_admin = 1"
     )
     .fetch_all(&pool)
     .await?;
@@ -0,0 +1,2 @@
+-- Allow polls to lock votes in ahead of their expiration time
+ALTER TABLE polls ADD COLUMN votes_lock_at TIMESTAMP;
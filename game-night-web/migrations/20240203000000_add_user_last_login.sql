@@ -0,0 +1,2 @@
+-- Record when each user last logged in successfully
+ALTER TABLE users ADD COLUMN last_login_at TIMESTAMP;
@@ -0,0 +1,2 @@
+-- Optional number of unique voters needed before a poll is considered decided
+ALTER TABLE polls ADD COLUMN quorum INTEGER;
@@ -0,0 +1,2 @@
+-- Allow anyone who has voted on a poll to see who else voted
+ALTER TABLE polls ADD COLUMN open_voters BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- Optional image (e.g. game cover art) shown alongside a poll option
+ALTER TABLE options ADD COLUMN image_url TEXT;
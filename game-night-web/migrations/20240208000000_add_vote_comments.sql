@@ -0,0 +1,3 @@
+-- Allow polls to require a comment explaining each vote
+ALTER TABLE polls ADD COLUMN require_vote_comment BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE votes ADD COLUMN vote_comment TEXT;
@@ -0,0 +1,2 @@
+-- Allow polls to close automatically once their quorum is reached
+ALTER TABLE polls ADD COLUMN auto_close_at_quorum BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- Optional cap on the number of votes an option can receive
+ALTER TABLE options ADD COLUMN max_votes INTEGER;
@@ -0,0 +1,2 @@
+-- Which kinds of options a poll accepts: 'mixed', 'text', or 'dates'
+ALTER TABLE polls ADD COLUMN poll_kind TEXT NOT NULL DEFAULT 'mixed';
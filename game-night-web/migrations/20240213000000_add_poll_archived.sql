@@ -0,0 +1,2 @@
+-- Archived polls are hidden from the active and expired listings
+ALTER TABLE polls ADD COLUMN archived BOOLEAN NOT NULL DEFAULT 0;
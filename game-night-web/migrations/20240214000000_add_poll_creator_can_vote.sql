@@ -0,0 +1,2 @@
+-- Whether the poll's creator may vote on it
+ALTER TABLE polls ADD COLUMN creator_can_vote BOOLEAN NOT NULL DEFAULT 1;
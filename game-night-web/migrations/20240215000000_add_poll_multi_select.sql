@@ -0,0 +1,2 @@
+-- Whether voters may pick several options (1) or only one (0)
+ALTER TABLE polls ADD COLUMN multi_select BOOLEAN NOT NULL DEFAULT 1;
@@ -0,0 +1,2 @@
+-- How much each of the user's votes counts toward poll results
+ALTER TABLE users ADD COLUMN vote_weight INTEGER NOT NULL DEFAULT 1;
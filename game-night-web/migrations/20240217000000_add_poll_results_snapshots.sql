@@ -0,0 +1,12 @@
+-- Finalized polls accept no more votes
+ALTER TABLE polls ADD COLUMN finalized BOOLEAN NOT NULL DEFAULT 0;
+
+-- Results recorded when a poll is finalized, kept as they were at that moment
+CREATE TABLE IF NOT EXISTS poll_results_snapshots (
+    poll_id INTEGER PRIMARY KEY,
+    results TEXT NOT NULL,
+    finalized_by INTEGER,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (poll_id) REFERENCES polls(id) ON DELETE CASCADE,
+    FOREIGN KEY (finalized_by) REFERENCES users(id) ON DELETE SET NULL
+);
@@ -0,0 +1,5 @@
+-- Polls that let voters suggest and vote for their own option
+ALTER TABLE polls ADD COLUMN allow_write_in BOOLEAN NOT NULL DEFAULT 0;
+
+-- Who suggested an option as a write-in; NULL for options added by the creator
+ALTER TABLE options ADD COLUMN suggested_by INTEGER REFERENCES users(id) ON DELETE SET NULL;
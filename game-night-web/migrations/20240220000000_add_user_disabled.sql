@@ -0,0 +1,2 @@
+-- Accounts disabled for inactivity (or by an admin) can't log in until re-enabled
+ALTER TABLE users ADD COLUMN disabled BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,3 @@
+-- Whether the user has seen (or skipped) the welcome page. Existing accounts
+-- count as onboarded; new non-admin accounts are created with 0.
+ALTER TABLE users ADD COLUMN has_onboarded BOOLEAN NOT NULL DEFAULT 1;
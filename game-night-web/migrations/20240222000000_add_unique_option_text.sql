@@ -0,0 +1,37 @@
+-- Option text is unique within a poll, ignoring case. Existing duplicates
+-- are merged into the oldest option with the same text: their votes and
+-- comments move over (votes the user already has there are dropped) and the
+-- duplicates are removed.
+UPDATE OR IGNORE votes
+SET option_id = (
+    SELECT MIN(keeper.id)
+    FROM options dup
+    JOIN options keeper ON keeper.poll_id = dup.poll_id AND LOWER(keeper.text) = LOWER(dup.text)
+    WHERE dup.id = votes.option_id
+);
+
+UPDATE comments
+SET option_id = (
+    SELECT MIN(keeper.id)
+    FROM options dup
+    JOIN options keeper ON keeper.poll_id = dup.poll_id AND LOWER(keeper.text) = LOWER(dup.text)
+    WHERE dup.id = comments.option_id
+)
+WHERE option_id IS NOT NULL;
+
+DELETE FROM votes
+WHERE option_id IN (
+    SELECT dup.id FROM options dup
+    WHERE EXISTS (
+        SELECT 1 FROM options keeper
+        WHERE keeper.poll_id = dup.poll_id AND LOWER(keeper.text) = LOWER(dup.text) AND keeper.id < dup.id
+    )
+);
+
+DELETE FROM options
+WHERE EXISTS (
+    SELECT 1 FROM options keeper
+    WHERE keeper.poll_id = options.poll_id AND LOWER(keeper.text) = LOWER(options.text) AND keeper.id < options.id
+);
+
+CREATE UNIQUE INDEX idx_options_poll_text ON options(poll_id, LOWER(text));
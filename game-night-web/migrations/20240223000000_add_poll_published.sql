@@ -0,0 +1,3 @@
+-- Whether a poll has been approved and appears in listings. Existing polls
+-- are published; new polls start unpublished when REQUIRE_POLL_APPROVAL is set.
+ALTER TABLE polls ADD COLUMN published BOOLEAN NOT NULL DEFAULT 1;
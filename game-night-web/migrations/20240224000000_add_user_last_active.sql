@@ -0,0 +1,4 @@
+-- When an account last became active: its last login, or when an admin re-enabled it.
+-- Inactivity is measured from this, so re-enabling doesn't overwrite last_login_at.
+ALTER TABLE users ADD COLUMN last_active_at TIMESTAMP;
+UPDATE users SET last_active_at = last_login_at;
@@ -16,22 +16,60 @@
 //! 3. Upon success, encrypted session cookie is set
 //! 4. Subsequent requests use cookie for authentication
 //! 5. Request guards automatically validate sessions
+//!
+//! ## Idle Timeout
+//! When `IDLE_TIMEOUT_MINUTES` is set, the session cookie records when it was
+//! issued and carries a matching max-age. Authenticated requests re-issue it
+//! once it is a tenth of the way to expiring, so activity keeps the session
+//! alive while an idle one lapses. The timestamp is also checked server-side,
+//! so a cookie kept past its max-age is still rejected.
+//!
+//! ## Re-authentication
+//! Sensitive admin actions (role changes, user deletion, and account merges) also need a
+//! recent password confirmation. Confirming the password at `/reauth` sets
+//! a second private cookie recording when it happened, which is honored for
+//! `REAUTH_WINDOW_MINUTES` (default 5). After that the password is asked for
+//! again. The marker is tied to the session epoch, so revoking sessions
+//! also ends it. Changing a password always asks for the current one, and
+//! doing so refreshes the marker.
 
+use chrono::Utc;
+use log::error;
+use rocket::config::SecretKey;
+use rocket::figment::Figment;
 use rocket::http::{Cookie, CookieJar, Status};
 use rocket::request::{FromRequest, Outcome, Request};
 use rocket::serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 use std::ops::Deref;
 
+use crate::config;
 use crate::models::User;
 
+/// Message shown when a user's stored password hash is malformed. No
+/// password can match it, so the account can't be used until its password
+/// is reset.
+pub const PASSWORD_RESET_NEEDED: &str =
+    "This account's password needs to be reset; contact an admin";
+
+/// How long a session may go unused before it ends, or `None` when
+/// sessions never time out.
+fn idle_timeout_seconds() -> Option<i64> {
+    config::get().idle_timeout_minutes.map(|minutes| minutes * 60)
+}
+
+/// How long a password confirmation allows sensitive actions.
+fn reauth_window_seconds() -> i64 {
+    config::get().reauth_window_minutes * 60
+}
+
 /// Request guard that represents an authenticated user.
 /// 
 /// This struct wraps a User and is used as a request guard to ensure
 /// that only authenticated users can access certain endpoints.
 /// 
 /// # Usage
-/// ```rust
+/// ```rust,ignore
 /// #[get("/protected")]
 /// fn protected_route(user: AuthenticatedUser) -> String {
 ///     format!("Hello, {}!", user.username)
@@ -59,7 +97,7 @@ impl Deref for AuthenticatedUser {
 /// that only authenticated admin users can access certain endpoints.
 /// 
 /// # Usage
-/// ```rust
+/// ```rust,ignore
 /// #[get("/admin")]
 /// fn admin_route(admin: AdminUser) -> String {
 ///     format!("Admin panel for {}", admin.username)
@@ -81,6 +119,25 @@ impl Deref for AdminUser {
     }
 }
 
+/// Request-local marker recording that a session cookie was rejected
+/// because the session had ended, rather than because none was sent.
+#[derive(Debug, Clone, Copy)]
+pub struct StaleSession(pub bool);
+
+/// Request-local cache of the session lookup, so guards that need the
+/// current user don't repeat it within a request. `Err` means the lookup
+/// itself failed.
+struct CachedUser(Result<Option<User>, ()>);
+
+/// Returns whether the request carried a session cookie for a session that
+/// has since ended (for example, a deleted user or revoked sessions).
+///
+/// # Arguments
+/// * `request` - The current request
+pub fn is_stale_session(request: &Request<'_>) -> bool {
+    request.local_cache(|| StaleSession(false)).0
+}
+
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for AuthenticatedUser {
     type Error = ();
@@ -91,46 +148,121 @@ impl<'r> FromRequest<'r> for AuthenticatedUser {
     /// the database, and returns an AuthenticatedUser if successful.
     /// 
     /// # Authentication Process
-    /// 1. Extract user_id from encrypted session cookie
+    /// 1. Extract user_id, session epoch, and issue time from encrypted session cookie
     /// 2. Query database for user with that ID
-    /// 3. Return Success if user found, Error otherwise
-    /// 4. Clean up invalid cookies if user lookup fails
+    /// 3. Return Success if user found, the epoch is current, and the session
+    ///    hasn't been idle past the timeout, Error otherwise
+    /// 4. Clean up invalid cookies if the session has ended, marking the
+    ///    request as a [`StaleSession`] so the user can be told why
+    /// 5. Re-issue the cookie when the idle timeout is on and it is due
+    ///
+    /// A database error leaves the cookie alone, since the session may
+    /// still be valid. The result is cached for the rest of the request.
     /// 
     /// # Returns
     /// - `Outcome::Success(AuthenticatedUser)` if authentication succeeds
     /// - `Outcome::Error(Unauthorized)` if authentication fails
+    /// - `Outcome::Error(ServiceUnavailable)` if the user couldn't be loaded
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        // Get the user_id from the cookies
-        let cookies = request.cookies();
-        let user_id = cookies
-            .get_private("user_id")
-            .and_then(|cookie| cookie.value().parse::<i64>().ok());
-
-        if let Some(user_id) = user_id {
-            // Get the database connection
-            let pool = request.rocket().state::<SqlitePool>().unwrap();
-
-            // Fetch the user from the database
-            let user_result = sqlx::query_as::<_, User>(
-                "SELECT id, username, password_hash, is_admin, created_at FROM users WHERE id = ?",
-            )
-            .bind(user_id)
-            .fetch_one(pool)
+        let cached = request
+            .local_cache_async(async { CachedUser(authenticate(request).await) })
             .await;
 
-            match user_result {
-                Ok(user) => Outcome::Success(AuthenticatedUser { user }),
-                Err(_) => {
-                    cookies.remove_private(Cookie::from("user_id"));
-                    Outcome::Error((Status::Unauthorized, ()))
-                }
+        match &cached.0 {
+            Ok(Some(user)) => Outcome::Success(AuthenticatedUser { user: user.clone() }),
+            Ok(None) => Outcome::Error((Status::Unauthorized, ())),
+            Err(()) => Outcome::Error((Status::ServiceUnavailable, ())),
+        }
+    }
+}
+
+/// Looks up the user for the request's session cookie, as described on
+/// [`AuthenticatedUser`]'s request guard.
+///
+/// # Arguments
+/// * `request` - The current request
+///
+/// # Returns
+/// * `Ok(Some(User))` - The session is valid
+/// * `Ok(None)` - No session cookie was sent, or the session has ended
+/// * `Err(())` - The user couldn't be loaded from the database
+async fn authenticate(request: &Request<'_>) -> Result<Option<User>, ()> {
+    // Get the user_id and session epoch from the cookies
+    let cookies = request.cookies();
+    let Some((user_id, session_epoch, issued_at)) = cookies
+        .get_private("user_id")
+        .and_then(|cookie| parse_session_cookie(cookie.value()))
+    else {
+        return Ok(None);
+    };
+
+    // Get the database connection
+    let pool = request.rocket().state::<SqlitePool>().unwrap();
+
+    // Fetch the user from the database
+    let user_result = sqlx::query_as::<_, User>(
+        "SELECT id, username, password_hash, is_admin, created_at, session_epoch, last_login_at, vote_weight, disabled, has_onboarded FROM users WHERE id = ?",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await;
+
+    let now = Utc::now().timestamp();
+    let idle = issued_at.map(|issued_at| now - issued_at);
+    let timeout = idle_timeout_seconds();
+    let timed_out = is_timed_out(idle, timeout);
+
+    let user = match user_result {
+        Ok(user) => Some(user),
+        Err(sqlx::Error::RowNotFound) => None,
+        Err(err) => {
+            error!("Failed to load user {} for their session: {}", user_id, err);
+            return Err(());
+        }
+    };
+
+    match user {
+        Some(user) if user.session_epoch == session_epoch && !timed_out && !user.disabled => {
+            if timeout.is_some_and(|timeout| needs_refresh(idle, timeout)) {
+                set_login_cookie(cookies, user.id, user.session_epoch);
             }
-        } else {
-            Outcome::Error((Status::Unauthorized, ()))
+            Ok(Some(user))
+        }
+        _ => {
+            // The cookie was valid but the session no longer is (deleted or
+            // disabled user, revoked sessions, or idle timeout); let the 401
+            // catcher explain why
+            request.local_cache(|| StaleSession(true));
+            cookies.remove_private(Cookie::from("user_id"));
+            Ok(None)
         }
     }
 }
 
+/// Whether a session has been idle for longer than the idle timeout.
+///
+/// Cookies without an issue time never time out.
+///
+/// # Arguments
+/// * `idle` - Seconds since the cookie was issued, if it records that
+/// * `timeout` - The idle timeout in seconds, if one is configured
+fn is_timed_out(idle: Option<i64>, timeout: Option<i64>) -> bool {
+    timeout.is_some_and(|timeout| idle.is_some_and(|idle| idle > timeout))
+}
+
+/// Whether a live session's cookie should be re-issued.
+///
+/// Refreshes only once a tenth of the timeout has passed, so busy sessions
+/// don't get a new cookie on every request. Cookies without an issue time
+/// are always re-issued, which upgrades them to the current format.
+///
+/// # Arguments
+/// * `idle` - Seconds since the cookie was issued, if it records that
+/// * `timeout` - The idle timeout in seconds
+fn needs_refresh(idle: Option<i64>, timeout: i64) -> bool {
+    idle.is_none_or(|idle| idle > timeout / 10)
+}
+
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for AdminUser {
     type Error = ();
@@ -148,7 +280,7 @@ impl<'r> FromRequest<'r> for AdminUser {
     /// # Returns
     /// - `Outcome::Success(AdminUser)` if user is authenticated admin
     /// - `Outcome::Error(Forbidden)` if user lacks admin privileges
-    /// - Inherits authentication errors from AuthenticatedUser guard
+    /// - `Outcome::Error(ServiceUnavailable)` if the user couldn't be loaded
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
         let user_outcome = request.guard::<AuthenticatedUser>().await;
 
@@ -156,6 +288,9 @@ impl<'r> FromRequest<'r> for AdminUser {
             Outcome::Success(auth_user) if auth_user.is_admin => Outcome::Success(AdminUser {
                 user: auth_user.user,
             }),
+            Outcome::Error((status, ())) if status == Status::ServiceUnavailable => {
+                Outcome::Error((status, ()))
+            }
             _ => Outcome::Error((Status::Forbidden, ())),
         }
     }
@@ -166,18 +301,56 @@ impl<'r> FromRequest<'r> for AdminUser {
 // ============================================================================
 /// Sets an encrypted session cookie for the authenticated user.
 /// 
-/// This function creates a private (encrypted) cookie containing the user's ID
-/// that will be used for subsequent authentication checks.
-/// 
+/// This function creates a private (encrypted) cookie containing the user's ID,
+/// session epoch, and issue time that will be used for subsequent authentication
+/// checks. With an idle timeout configured, the cookie expires after it.
+///
 /// # Arguments
 /// * `cookies` - The cookie jar from the current request
 /// * `user_id` - The ID of the user to authenticate
-/// 
+/// * `session_epoch` - The user's current session epoch
+///
 /// # Security Note
 /// The cookie is encrypted using Rocket's private cookie functionality,
 /// which requires a valid ROCKET_SECRET_KEY in the environment.
-pub fn set_login_cookie(cookies: &CookieJar<'_>, user_id: i64) {
-    cookies.add_private(Cookie::new("user_id", user_id.to_string()));
+pub fn set_login_cookie(cookies: &CookieJar<'_>, user_id: i64, session_epoch: i64) {
+    let mut cookie = Cookie::new(
+        "user_id",
+        format!("{}:{}:{}", user_id, session_epoch, Utc::now().timestamp()),
+    );
+    if let Some(timeout) = idle_timeout_seconds() {
+        cookie.set_max_age(rocket::time::Duration::seconds(timeout));
+    }
+    cookies.add_private(cookie);
+}
+
+/// Parses a session cookie value of the form `user_id:session_epoch:issued_at`.
+///
+/// Older cookies are still accepted: `user_id:session_epoch` has no issue
+/// time, and a bare `user_id` (from before sessions could be revoked) is
+/// read as epoch 0, the column's default. Such a cookie stays valid until
+/// the user's sessions are revoked.
+///
+/// # Arguments
+/// * `value` - The decrypted cookie value
+///
+/// # Returns
+/// `Some((user_id, session_epoch, issued_at))` if the value is well-formed, `None` otherwise
+fn parse_session_cookie(value: &str) -> Option<(i64, i64, Option<i64>)> {
+    let mut parts = value.split(':');
+    let user_id = parts.next()?.parse().ok()?;
+    let session_epoch = match parts.next() {
+        Some(session_epoch) => session_epoch.parse().ok()?,
+        None => 0,
+    };
+    let issued_at = match parts.next() {
+        Some(issued_at) => Some(issued_at.parse().ok()?),
+        None => None,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((user_id, session_epoch, issued_at))
 }
 
 /// Removes the session cookie, effectively logging out the user.
@@ -189,6 +362,55 @@ pub fn set_login_cookie(cookies: &CookieJar<'_>, user_id: i64) {
 /// * `cookies` - The cookie jar from the current request
 pub fn clear_login_cookie(cookies: &CookieJar<'_>) {
     cookies.remove_private(Cookie::from("user_id"));
+    cookies.remove_private(Cookie::from("reauth"));
+}
+
+/// Records that the user has just confirmed their password.
+///
+/// Sets a private cookie holding the user's ID, session epoch, and the
+/// confirmation time, which [`has_recent_reauth`] checks against
+/// `REAUTH_WINDOW_MINUTES`.
+///
+/// # Arguments
+/// * `cookies` - The cookie jar from the current request
+/// * `user` - The user who confirmed their password
+pub fn set_reauth_cookie(cookies: &CookieJar<'_>, user: &User) {
+    let mut cookie = Cookie::new(
+        "reauth",
+        format!("{}:{}:{}", user.id, user.session_epoch, Utc::now().timestamp()),
+    );
+    cookie.set_max_age(rocket::time::Duration::seconds(reauth_window_seconds()));
+    cookies.add_private(cookie);
+}
+
+/// Returns whether the user confirmed their password within the
+/// re-authentication window, in their current session.
+///
+/// # Arguments
+/// * `cookies` - The cookie jar from the current request
+/// * `user` - The user attempting a sensitive action
+pub fn has_recent_reauth(cookies: &CookieJar<'_>, user: &User) -> bool {
+    let Some((user_id, session_epoch, Some(confirmed_at))) = cookies
+        .get_private("reauth")
+        .and_then(|cookie| parse_session_cookie(cookie.value()))
+    else {
+        return false;
+    };
+
+    let age = Utc::now().timestamp() - confirmed_at;
+    user_id == user.id && session_epoch == user.session_epoch && (0..=reauth_window_seconds()).contains(&age)
+}
+
+/// Returns whether a secret key for encrypting session cookies is configured.
+///
+/// Rocket's defaults include an all-zero key, which it treats as unset.
+///
+/// # Arguments
+/// * `figment` - The resolved Rocket configuration
+pub fn secret_key_configured(figment: &Figment) -> bool {
+    figment
+        .extract_inner::<SecretKey>("secret_key")
+        .is_ok_and(|key| !key.is_zero())
 }
 
 /// Verifies user credentials and returns the authenticated user.
@@ -208,6 +430,9 @@ pub fn clear_login_cookie(cookies: &CookieJar<'_>) {
 /// # Errors
 /// * "User not found" - Username doesn't exist in database
 /// * "Invalid password" - Password doesn't match stored hash
+/// * Account disabled - The password is correct but the account is disabled
+/// * Password reset needed - The stored hash is malformed, so no password
+///   can match it (logged as an error)
 /// * "Database error" - Database query failed
 pub async fn login_user(
     pool: &SqlitePool,
@@ -215,16 +440,110 @@ pub async fn login_user(
     password: &str,
 ) -> Result<User, &'static str> {
     let user_result = sqlx::query_as::<_, User>(
-        "SELECT id, username, password_hash, is_admin, created_at FROM users WHERE username = ?",
+        "SELECT id, username, password_hash, is_admin, created_at, session_epoch, last_login_at, vote_weight, disabled, has_onboarded FROM users WHERE username = ?",
     )
     .bind(username)
     .fetch_optional(pool)
     .await;
 
-    match user_result {
-        Ok(Some(user)) if user.verify_password(password) => Ok(user),
-        Ok(Some(_)) => Err("Invalid password"),
-        Ok(None) => Err("User not found"),
-        Err(_) => Err("Database error"),
+    let user = match user_result {
+        Ok(Some(user)) => user,
+        Ok(None) => return Err("User not found"),
+        Err(_) => return Err("Database error"),
+    };
+
+    match user.check_password(password) {
+        Ok(true) if user.disabled => {
+            Err("This account has been disabled for inactivity; contact an admin to re-enable it")
+        }
+        Ok(true) => Ok(user),
+        Ok(false) => Err("Invalid password"),
+        Err(err) => {
+            error!("Stored password for {} can't be checked: {}", user.username, err);
+            Err(PASSWORD_RESET_NEEDED)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::password::{self, HashAlgorithm};
+
+    async fn add_user(pool: &SqlitePool, username: &str, password_hash: &str) {
+        sqlx::query("INSERT INTO users (username, password_hash) VALUES (?, ?)")
+            .bind(username)
+            .bind(password_hash)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn parses_current_session_cookie() {
+        assert_eq!(parse_session_cookie("7:3:1700000000"), Some((7, 3, Some(1700000000))));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn accepts_cookie_without_issue_time() {
+        assert_eq!(parse_session_cookie("7:3"), Some((7, 3, None)));
+    }
+
+    #[test]
+    fn reads_bare_user_id_as_epoch_zero() {
+        assert_eq!(parse_session_cookie("7"), Some((7, 0, None)));
+    }
+
+    #[test]
+    fn rejects_malformed_cookies() {
+        assert_eq!(parse_session_cookie(""), None);
+        assert_eq!(parse_session_cookie("abc"), None);
+        assert_eq!(parse_session_cookie("7:x"), None);
+        assert_eq!(parse_session_cookie("7:3:x"), None);
+        assert_eq!(parse_session_cookie("7:3:1700000000:1"), None);
+    }
+
+    #[test]
+    fn sessions_time_out_only_past_the_idle_timeout() {
+        assert!(!is_timed_out(Some(600), Some(600)));
+        assert!(is_timed_out(Some(601), Some(600)));
+        assert!(!is_timed_out(None, Some(600)));
+        assert!(!is_timed_out(Some(1_000_000), None));
+    }
+
+    #[test]
+    fn cookie_is_refreshed_after_a_tenth_of_the_timeout() {
+        assert!(!needs_refresh(Some(60), 600));
+        assert!(needs_refresh(Some(61), 600));
+        assert!(needs_refresh(None, 600));
+    }
+
+    #[test]
+    fn secret_key_must_be_set_and_non_zero() {
+        assert!(!secret_key_configured(&Figment::new()));
+        assert!(!secret_key_configured(&Figment::new().merge(("secret_key", "00".repeat(32)))));
+        assert!(secret_key_configured(&Figment::new().merge(("secret_key", "ab".repeat(32)))));
+    }
+
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn logs_in_with_bcrypt_and_argon2_hashes(pool: SqlitePool) -> sqlx::Result<()> {
+        add_user(&pool, "old", &bcrypt::hash("hunter2", 4).unwrap()).await;
+        add_user(&pool, "new", &password::hash_with(HashAlgorithm::Argon2, "hunter2").unwrap()).await;
+
+        for username in ["old", "new"] {
+            let user = login_user(&pool, username, "hunter2").await.unwrap();
+            assert_eq!(user.username, username);
+            assert_eq!(login_user(&pool, username, "hunter3").await.unwrap_err(), "Invalid password");
+        }
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn malformed_hash_asks_for_a_password_reset(pool: SqlitePool) -> sqlx::Result<()> {
+        add_user(&pool, "imported", "plaintext").await;
+
+        assert_eq!(login_user(&pool, "imported", "plaintext").await.unwrap_err(), PASSWORD_RESET_NEEDED);
+        Ok(())
+    }
+}
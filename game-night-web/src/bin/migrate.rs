@@ -0,0 +1,40 @@
+use dotenv::dotenv;
+use game_night_web::db;
+use std::env;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv().ok();
+    env_logger::init();
+
+    let dry_run = match env::args().nth(1).as_deref() {
+        None => false,
+        Some("--dry-run") => true,
+        Some(_) => {
+            eprintln!("Usage: migrate [--dry-run]");
+            std::process::exit(2);
+        }
+    };
+
+    let pool = db::init_pool().await;
+    let pending = db::pending_migrations(&pool).await?;
+
+    if pending.is_empty() {
+        println!("Database is up to date. Nothing to do.");
+        return Ok(());
+    }
+
+    for migration in &pending {
+        println!("Pending: {} {}", migration.version, migration.description);
+    }
+
+    if dry_run {
+        println!("{} migration(s) pending (dry run, nothing applied)", pending.len());
+        return Ok(());
+    }
+
+    db::MIGRATOR.run(&pool).await?;
+    println!("✅ Applied {} migration(s)", pending.len());
+
+    Ok(())
+}
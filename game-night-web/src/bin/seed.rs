@@ -0,0 +1,182 @@
+use chrono::{Duration, Utc};
+use dotenv::dotenv;
+use game_night_web::controllers::polls;
+use game_night_web::db;
+use game_night_web::models::{NewPollForm, User};
+use sqlx::SqlitePool;
+use std::env;
+
+/// Development users, all with the password `password`.
+const SEED_USERS: [&str; 3] = ["alice", "bob", "carol"];
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv().ok();
+    env_logger::init();
+
+    let force = match env::args().nth(1).as_deref() {
+        None => false,
+        Some("--force") => true,
+        Some(_) => {
+            eprintln!("Usage: seed [--force]");
+            std::process::exit(2);
+        }
+    };
+
+    // Seed data has well-known passwords, so keep it out of release builds
+    if !cfg!(debug_assertions) {
+        eprintln!("Refusing to seed from a release build. Use a debug build for development data.");
+        std::process::exit(1);
+    }
+
+    let pool = db::init_pool().await;
+    db::MIGRATOR.run(&pool).await?;
+    db::init_default_admin(&pool).await?;
+
+    match seed(&pool, force).await? {
+        Some(seeded_polls) => println!(
+            "Seeded {} users (password 'password') and {} polls",
+            SEED_USERS.len(),
+            seeded_polls
+        ),
+        None => {
+            println!("The database already has polls. Nothing to do (use --force to seed anyway).")
+        }
+    }
+
+    Ok(())
+}
+
+/// Adds the development users and polls, with votes.
+///
+/// # Arguments
+/// * `pool` - Database connection pool, already migrated
+/// * `force` - Whether to seed even if the database already has polls
+///
+/// # Returns
+/// * `Ok(Some(usize))` - Number of polls created
+/// * `Ok(None)` - The database already has polls, so nothing was added
+/// * `Err` - A user, poll or vote could not be added
+async fn seed(pool: &SqlitePool, force: bool) -> Result<Option<usize>, Box<dyn std::error::Error>> {
+    let poll_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM polls")
+        .fetch_one(pool)
+        .await?;
+    if poll_count > 0 && !force {
+        return Ok(None);
+    }
+
+    let password_hash = User::hash_password("password")?;
+    let mut user_ids = Vec::new();
+    for username in SEED_USERS {
+        sqlx::query("INSERT OR IGNORE INTO users (username, password_hash, is_admin) VALUES (?, ?, 0)")
+            .bind(username)
+            .bind(&password_hash)
+            .execute(pool)
+            .await?;
+        let id: i64 = sqlx::query_scalar("SELECT id FROM users WHERE username = ?")
+            .bind(username)
+            .fetch_one(pool)
+            .await?;
+        user_ids.push(id);
+    }
+
+    let next_week = Utc::now() + Duration::days(7);
+    let date_option = |days: i64| {
+        (Utc::now() + Duration::days(days))
+            .format("%Y-%m-%dT19:00")
+            .to_string()
+    };
+
+    let polls_to_seed = [
+        ("What should we play this week?", "text", "Catan\nAzul\nWingspan\nTicket to Ride".to_string(), false),
+        (
+            "When is the next game night?",
+            "dates",
+            format!("{}\n{}\n{}", date_option(3), date_option(4), date_option(5)),
+            false,
+        ),
+        ("Last month's game", "mixed", "Codenames\nDixit\nSplendor".to_string(), true),
+    ];
+
+    let seeded_polls = polls_to_seed.len();
+    for (index, (title, kind, options, expired)) in polls_to_seed.into_iter().enumerate() {
+        let form = NewPollForm {
+            title: title.to_string(),
+            description: Some("Seeded for local development.".to_string()),
+            expires_at: next_week.format("%Y-%m-%dT%H:%M").to_string(),
+            votes_lock_at: None,
+            quorum: None,
+            open_voters: true,
+            require_vote_comment: false,
+            auto_close_at_quorum: false,
+            poll_kind: Some(kind.to_string()),
+            creator_can_vote: None,
+            multi_select: None,
+            allow_write_in: false,
+            options,
+            options_separator: Some("newline".to_string()),
+            copy_options_from: None,
+            // With --force the same titles are seeded again on purpose
+            confirm_duplicate: force,
+        };
+        let creator_id = user_ids[index % user_ids.len()];
+        let poll_id = polls::create_poll(pool, &form, creator_id).await?;
+
+        // Seed polls skip the approval queue, so they can be voted on
+        sqlx::query("UPDATE polls SET published = 1 WHERE id = ?")
+            .bind(poll_id)
+            .execute(pool)
+            .await?;
+
+        // Spread votes across the options so results aren't all ties
+        let options = polls::get_poll_options(pool, poll_id).await?;
+        for (voter, user_id) in user_ids.iter().enumerate() {
+            for option in options.iter().skip(voter % 2).step_by(2).take(voter + 1) {
+                polls::vote_on_poll(pool, poll_id, option.id, *user_id, None).await?;
+            }
+        }
+
+        // Votes can't be cast on an expired poll, so expire it afterwards
+        if expired {
+            sqlx::query("UPDATE polls SET expires_at = ? WHERE id = ?")
+                .bind(Utc::now() - Duration::days(30))
+                .bind(poll_id)
+                .execute(pool)
+                .await?;
+        }
+
+        println!("✅ Created poll '{}'", title);
+    }
+
+    Ok(Some(seeded_polls))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrator = "game_night_web::db::MIGRATOR")]
+    async fn seeds_once_unless_forced(pool: SqlitePool) -> sqlx::Result<()> {
+        assert_eq!(seed(&pool, false).await.unwrap(), Some(3));
+
+        let (users, polls, expired, votes): (i64, i64, i64, i64) = sqlx::query_as(
+            "SELECT (SELECT COUNT(*) FROM users),
+                    (SELECT COUNT(*) FROM polls),
+                    (SELECT COUNT(*) FROM polls WHERE julianday(expires_at) <= julianday('now')),
+                    (SELECT COUNT(*) FROM votes)",
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!((users, polls, expired), (3, 3, 1));
+        assert!(votes > 0);
+
+        assert_eq!(seed(&pool, false).await.unwrap(), None);
+        assert_eq!(seed(&pool, true).await.unwrap(), Some(3));
+        let (users, polls): (i64, i64) =
+            sqlx::query_as("SELECT (SELECT COUNT(*) FROM users), (SELECT COUNT(*) FROM polls)")
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!((users, polls), (3, 6));
+        Ok(())
+    }
+}
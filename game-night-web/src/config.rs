@@ -0,0 +1,467 @@
+//! # Configuration Module
+//!
+//! This module gathers every environment variable the application reads
+//! into a single [`Config`], loaded once and validated up front, so all the
+//! knobs are listed in one place and a mistyped value is reported at
+//! startup instead of being silently replaced by a default.
+//!
+//! ## Loading
+//! The server calls [`init`] before building Rocket and stops with the
+//! error if any value is invalid; the resulting [`Config`] is also managed
+//! by Rocket. Everything else reads it through [`get`], which loads it on
+//! first use for the command-line examples that don't call [`init`].
+//!
+//! Rocket's own `ROCKET_*` variables are still read by Rocket.
+//!
+//! ## Values
+//! Unset and empty variables take their defaults. Anything else must parse
+//! and be in range; the error names the variable, the value, and what was
+//! expected. The one exception is `DASHBOARD_COLUMNS`, a purely cosmetic
+//! setting: an out-of-range number there is logged as a warning and
+//! ignored.
+
+use std::env;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use log::warn;
+
+use crate::content_filter;
+use crate::controllers::users::UserDeletePolicy;
+use crate::db;
+use crate::password::HashAlgorithm;
+
+/// Default database URL, used when `DATABASE_URL` is not set.
+const DEFAULT_DATABASE_URL: &str = "sqlite:game_night.db";
+
+/// Default limit on the size of a submitted form, in bytes.
+const DEFAULT_MAX_FORM_BYTES: u64 = 256 * 1024;
+
+/// Default bcrypt cost factor for new hashes.
+const DEFAULT_BCRYPT_COST: u32 = 12;
+
+/// Default prefix applied to every metric name.
+const DEFAULT_METRICS_PREFIX: &str = "game_night";
+
+/// Default minimum number of seconds between metric refreshes from the database.
+const DEFAULT_METRICS_REFRESH_SECONDS: u64 = 10;
+
+/// The loaded configuration, set by [`init`] or the first call to [`get`].
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Error returned when an environment variable holds an unusable value.
+#[derive(Debug, thiserror::Error)]
+#[error("{name} must be {expected}, but is \"{value}\"")]
+pub struct ConfigError {
+    /// Name of the environment variable
+    pub name: &'static str,
+    /// The value it was set to
+    pub value: String,
+    /// Description of the values it accepts
+    pub expected: String,
+}
+
+/// Application settings read from the environment.
+#[derive(Debug, Clone)]
+pub struct Config {
+    // Server
+    /// TLS certificate chain path, from `TLS_CERT`
+    pub tls_cert: Option<String>,
+    /// TLS private key path, from `TLS_KEY`
+    pub tls_key: Option<String>,
+    /// Largest accepted form submission in bytes, from `MAX_FORM_BYTES` (default 256 KB)
+    pub max_form_bytes: u64,
+
+    // Database
+    /// Absolute path of the database file, resolved from `DATABASE_URL`
+    /// (default `sqlite:game_night.db`)
+    pub database_path: PathBuf,
+    /// Whether the server applies pending migrations as it starts, from
+    /// `RUN_MIGRATIONS_ON_START` (default true)
+    pub run_migrations_on_start: bool,
+
+    // Sessions
+    /// Minutes a session may go unused before it ends, from
+    /// `IDLE_TIMEOUT_MINUTES`; `None` (unset or 0) disables the timeout
+    pub idle_timeout_minutes: Option<i64>,
+    /// Minutes a password confirmation allows sensitive actions, from
+    /// `REAUTH_WINDOW_MINUTES` (default 5)
+    pub reauth_window_minutes: i64,
+    /// Minimum delay before a failed login is answered, from `FAILED_LOGIN_DELAY_MS` (default 200)
+    pub failed_login_delay_ms: u64,
+    /// Upper bound on the random extra delay, from `FAILED_LOGIN_JITTER_MS` (default 300)
+    pub failed_login_jitter_ms: u64,
+
+    // Passwords
+    /// Algorithm for new password hashes, from `PASSWORD_HASH_ALGO` (default bcrypt)
+    pub password_hash_algo: HashAlgorithm,
+    /// bcrypt cost factor for new hashes, from `BCRYPT_COST` (default 12)
+    pub bcrypt_cost: u32,
+
+    // Metrics
+    /// Prefix of every metric name, from `METRICS_PREFIX` (default `game_night`)
+    pub metrics_prefix: String,
+    /// Minimum seconds between metric refreshes, from `METRICS_REFRESH_SECONDS` (default 10)
+    pub metrics_refresh_seconds: u64,
+    /// Whether poll metrics count archived polls, from `METRICS_INCLUDE_ARCHIVED` (default true)
+    pub metrics_include_archived: bool,
+
+    // Polls
+    /// Options shown on the poll page before the rest are collapsed, from
+    /// `MAX_VISIBLE_OPTIONS`; `None` (unset or 0) shows every option
+    pub max_visible_options: Option<usize>,
+    /// Minimum hours between nudges for the same poll, from `NUDGE_INTERVAL_HOURS` (default 24)
+    pub nudge_interval_hours: i64,
+    /// Poll cards per row on the poll listings, from `DASHBOARD_COLUMNS`
+    /// (1 to 4, other numbers are ignored with a warning); `None` fits as
+    /// many as the screen allows
+    pub dashboard_columns: Option<usize>,
+    /// Most options a poll can have, from `MAX_OPTIONS_PER_POLL` (default 50)
+    pub max_options_per_poll: i64,
+    /// Unique voters above which the voters page is summarized, from
+    /// `VOTERS_SUMMARY_THRESHOLD` (default 200)
+    pub voters_summary_threshold: i64,
+    /// Blocked words and phrases for poll content, read from the file at
+    /// `CONTENT_BLOCKLIST_PATH`; empty when unset
+    pub content_blocklist: Vec<Vec<String>>,
+
+    // Users
+    /// What happens to a deleted user's data, from `USER_DELETE_POLICY` (default reassign)
+    pub user_delete_policy: UserDeletePolicy,
+    /// Days without a login after which an account is disabled, from
+    /// `INACTIVE_ACCOUNT_DAYS`; 0 (the default) turns this off
+    pub inactive_account_days: i64,
+    /// Whether accounts that never logged in can be disabled for
+    /// inactivity, from `INACTIVE_INCLUDE_NEVER_LOGGED_IN` (default false)
+    pub inactive_include_never_logged_in: bool,
+    /// Whether new users see the welcome page after their first login, from
+    /// `ONBOARDING_ENABLED` (default true)
+    pub onboarding_enabled: bool,
+    /// Whether new polls wait for an admin's approval before they're listed,
+    /// from `REQUIRE_POLL_APPROVAL` (default false)
+    pub require_poll_approval: bool,
+}
+
+impl Config {
+    /// Loads the configuration from the process environment.
+    ///
+    /// # Returns
+    /// * `Ok(Config)` - Every value parsed and in range
+    /// * `Err(ConfigError)` - The first invalid value found
+    pub fn from_env() -> Result<Config, ConfigError> {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    /// Loads the configuration using `lookup` to read each variable.
+    ///
+    /// # Arguments
+    /// * `lookup` - Returns a variable's value, or `None` when unset
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Config, ConfigError> {
+        let vars = Vars(&lookup);
+
+        let database_url = vars.string("DATABASE_URL").unwrap_or_else(|| DEFAULT_DATABASE_URL.to_string());
+        let database_path = db::resolve_database_path(&database_url).map_err(|err| ConfigError {
+            name: "DATABASE_URL",
+            value: database_url.clone(),
+            expected: format!("a usable SQLite database ({})", err),
+        })?;
+
+        Ok(Config {
+            tls_cert: vars.string("TLS_CERT"),
+            tls_key: vars.string("TLS_KEY"),
+            max_form_bytes: vars.number("MAX_FORM_BYTES", DEFAULT_MAX_FORM_BYTES, 1..)?,
+
+            database_path,
+            run_migrations_on_start: vars.flag("RUN_MIGRATIONS_ON_START", true)?,
+
+            idle_timeout_minutes: Some(vars.number("IDLE_TIMEOUT_MINUTES", 0, 0..)?)
+                .filter(|minutes| *minutes > 0),
+            reauth_window_minutes: vars.number("REAUTH_WINDOW_MINUTES", 5, 1..)?,
+            failed_login_delay_ms: vars.number("FAILED_LOGIN_DELAY_MS", 200, 0..)?,
+            failed_login_jitter_ms: vars.number("FAILED_LOGIN_JITTER_MS", 300, 0..)?,
+
+            password_hash_algo: vars.choice(
+                "PASSWORD_HASH_ALGO",
+                HashAlgorithm::Bcrypt,
+                &[("bcrypt", HashAlgorithm::Bcrypt), ("argon2", HashAlgorithm::Argon2)],
+            )?,
+            bcrypt_cost: vars.number("BCRYPT_COST", DEFAULT_BCRYPT_COST, 4..=31)?,
+
+            metrics_prefix: vars.metrics_prefix()?,
+            metrics_refresh_seconds: vars.number("METRICS_REFRESH_SECONDS", DEFAULT_METRICS_REFRESH_SECONDS, 0..)?,
+            metrics_include_archived: vars.flag("METRICS_INCLUDE_ARCHIVED", true)?,
+
+            max_visible_options: Some(vars.number("MAX_VISIBLE_OPTIONS", 0, 0..)?)
+                .filter(|max| *max > 0),
+            nudge_interval_hours: vars.number("NUDGE_INTERVAL_HOURS", 24, 0..)?,
+            dashboard_columns: vars.lenient_optional_number("DASHBOARD_COLUMNS", 1..=4)?,
+            max_options_per_poll: vars.number("MAX_OPTIONS_PER_POLL", 50, 1..)?,
+            voters_summary_threshold: vars.number("VOTERS_SUMMARY_THRESHOLD", 200, 1..)?,
+            content_blocklist: vars.content_blocklist()?,
+
+            user_delete_policy: vars.choice(
+                "USER_DELETE_POLICY",
+                UserDeletePolicy::Reassign,
+                &[("reassign", UserDeletePolicy::Reassign), ("cascade", UserDeletePolicy::Cascade)],
+            )?,
+            inactive_account_days: vars.number("INACTIVE_ACCOUNT_DAYS", 0, 0..)?,
+            inactive_include_never_logged_in: vars.flag("INACTIVE_INCLUDE_NEVER_LOGGED_IN", false)?,
+            onboarding_enabled: vars.flag("ONBOARDING_ENABLED", true)?,
+            require_poll_approval: vars.flag("REQUIRE_POLL_APPROVAL", false)?,
+        })
+    }
+}
+
+/// Loads the configuration from the environment and makes it the one
+/// returned by [`get`].
+///
+/// Call once at startup, before anything reads the configuration.
+///
+/// # Returns
+/// * `Ok(&Config)` - The loaded configuration
+/// * `Err(ConfigError)` - The first invalid value found
+pub fn init() -> Result<&'static Config, ConfigError> {
+    let config = Config::from_env()?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+/// Returns the application configuration, loading it on first use.
+///
+/// # Panics
+/// Panics with the error if the configuration hasn't been loaded by
+/// [`init`] and the environment holds an invalid value.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        Config::from_env().unwrap_or_else(|err| panic!("Invalid configuration: {}", err))
+    })
+}
+
+/// Typed access to the variables returned by a lookup function.
+struct Vars<'a>(&'a dyn Fn(&str) -> Option<String>);
+
+impl Vars<'_> {
+    /// Returns a variable's trimmed value, treating empty as unset.
+    fn string(&self, name: &str) -> Option<String> {
+        (self.0)(name)
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Parses an optional number that must fall in `range` when set.
+    fn optional_number<T, R>(&self, name: &'static str, range: R) -> Result<Option<T>, ConfigError>
+    where
+        T: FromStr + PartialOrd + std::fmt::Debug,
+        R: std::ops::RangeBounds<T> + std::fmt::Debug,
+    {
+        let Some(value) = self.string(name) else {
+            return Ok(None);
+        };
+
+        match value.parse::<T>() {
+            Ok(number) if range.contains(&number) => Ok(Some(number)),
+            _ => Err(ConfigError {
+                name,
+                value,
+                expected: describe_range(&range),
+            }),
+        }
+    }
+
+    /// Parses an optional number like [`Vars::optional_number`], but treats a
+    /// number outside `range` as unset, with a warning, instead of an error.
+    fn lenient_optional_number<T, R>(&self, name: &'static str, range: R) -> Result<Option<T>, ConfigError>
+    where
+        T: FromStr + PartialOrd + std::fmt::Debug,
+        R: std::ops::RangeBounds<T> + std::fmt::Debug,
+    {
+        let Some(value) = self.string(name) else {
+            return Ok(None);
+        };
+
+        match value.parse::<T>() {
+            Ok(number) if range.contains(&number) => Ok(Some(number)),
+            Ok(_) => {
+                warn!("Ignoring {}=\"{}\": expected {}", name, value, describe_range(&range));
+                Ok(None)
+            }
+            Err(_) => Err(ConfigError {
+                name,
+                value,
+                expected: describe_range(&range),
+            }),
+        }
+    }
+
+    /// Parses a number that must fall in `range`, with a default when unset.
+    fn number<T, R>(&self, name: &'static str, default: T, range: R) -> Result<T, ConfigError>
+    where
+        T: FromStr + PartialOrd + std::fmt::Debug,
+        R: std::ops::RangeBounds<T> + std::fmt::Debug,
+    {
+        Ok(self.optional_number(name, range)?.unwrap_or(default))
+    }
+
+    /// Parses an on/off flag (`true`/`false`, `1`/`0`, or `yes`/`no`).
+    fn flag(&self, name: &'static str, default: bool) -> Result<bool, ConfigError> {
+        let Some(value) = self.string(name) else {
+            return Ok(default);
+        };
+
+        match value.to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" => Ok(true),
+            "false" | "0" | "no" => Ok(false),
+            _ => Err(ConfigError {
+                name,
+                value,
+                expected: "true or false".to_string(),
+            }),
+        }
+    }
+
+    /// Parses one of a fixed set of names (case-insensitively).
+    fn choice<T: Copy>(
+        &self,
+        name: &'static str,
+        default: T,
+        choices: &[(&str, T)],
+    ) -> Result<T, ConfigError> {
+        let Some(value) = self.string(name) else {
+            return Ok(default);
+        };
+
+        choices
+            .iter()
+            .find(|(choice, _)| choice.eq_ignore_ascii_case(&value))
+            .map(|(_, choice)| *choice)
+            .ok_or_else(|| ConfigError {
+                name,
+                value,
+                expected: format!(
+                    "one of {}",
+                    choices.iter().map(|(choice, _)| *choice).collect::<Vec<_>>().join(", ")
+                ),
+            })
+    }
+
+    /// Reads the blocklist file named by `CONTENT_BLOCKLIST_PATH`, if set.
+    fn content_blocklist(&self) -> Result<Vec<Vec<String>>, ConfigError> {
+        let Some(path) = self.string("CONTENT_BLOCKLIST_PATH") else {
+            return Ok(Vec::new());
+        };
+
+        content_filter::load_blocklist(&path).map_err(|err| ConfigError {
+            name: "CONTENT_BLOCKLIST_PATH",
+            value: path,
+            expected: format!("a readable blocklist file ({})", err),
+        })
+    }
+
+    /// Reads `METRICS_PREFIX`, which must be a valid Prometheus metric name.
+    fn metrics_prefix(&self) -> Result<String, ConfigError> {
+        let Some(prefix) = self.string("METRICS_PREFIX") else {
+            return Ok(DEFAULT_METRICS_PREFIX.to_string());
+        };
+
+        let valid = prefix.chars().enumerate().all(|(i, c)| {
+            c.is_ascii_alphabetic() || c == '_' || c == ':' || (i > 0 && c.is_ascii_digit())
+        });
+        if !valid {
+            return Err(ConfigError {
+                name: "METRICS_PREFIX",
+                value: prefix,
+                expected: "a Prometheus metric name (letters, digits, '_' and ':', not starting with a digit)"
+                    .to_string(),
+            });
+        }
+
+        Ok(prefix)
+    }
+}
+
+/// Describes the numbers a range accepts, e.g. "a number from 1 to 4".
+fn describe_range<T: std::fmt::Debug>(range: &impl std::ops::RangeBounds<T>) -> String {
+    use std::ops::Bound;
+
+    match (range.start_bound(), range.end_bound()) {
+        (Bound::Included(start), Bound::Included(end)) => {
+            format!("a whole number from {:?} to {:?}", start, end)
+        }
+        (Bound::Included(start), _) => format!("a whole number of at least {:?}", start),
+        _ => "a whole number".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Loads a configuration from the given variables only.
+    fn load(vars: &[(&str, &str)]) -> Result<Config, ConfigError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        Config::from_lookup(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn unreadable_blocklist_is_a_startup_error() {
+        let err = load(&[("CONTENT_BLOCKLIST_PATH", "/nonexistent/blocklist.txt")]).unwrap_err();
+
+        assert_eq!(err.name, "CONTENT_BLOCKLIST_PATH");
+        assert!(
+            err.to_string().starts_with("CONTENT_BLOCKLIST_PATH must be a readable blocklist file"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn loads_blocklist_file() {
+        let path = std::env::temp_dir().join(format!("blocklist-{}.txt", std::process::id()));
+        std::fs::write(&path, "# words\nspam\n").unwrap();
+
+        let config = load(&[("CONTENT_BLOCKLIST_PATH", path.to_str().unwrap())]).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(config.content_blocklist, vec![vec!["spam".to_string()]]);
+    }
+
+    #[test]
+    fn invalid_numeric_env_value_produces_a_descriptive_startup_error() {
+        let err = load(&[("MAX_FORM_BYTES", "abc")]).unwrap_err();
+        assert_eq!(err.name, "MAX_FORM_BYTES");
+        assert_eq!(err.to_string(), "MAX_FORM_BYTES must be a whole number of at least 1, but is \"abc\"");
+
+        let err = load(&[("BCRYPT_COST", "40")]).unwrap_err();
+        assert_eq!(err.to_string(), "BCRYPT_COST must be a whole number from 4 to 31, but is \"40\"");
+    }
+
+    #[test]
+    fn out_of_range_dashboard_columns_is_ignored() {
+        assert_eq!(load(&[("DASHBOARD_COLUMNS", "3")]).unwrap().dashboard_columns, Some(3));
+        assert_eq!(load(&[("DASHBOARD_COLUMNS", "7")]).unwrap().dashboard_columns, None);
+        assert_eq!(load(&[("DASHBOARD_COLUMNS", "0")]).unwrap().dashboard_columns, None);
+
+        let err = load(&[("DASHBOARD_COLUMNS", "wide")]).unwrap_err();
+        assert_eq!(err.name, "DASHBOARD_COLUMNS");
+    }
+
+    #[test]
+    fn metrics_prefix_must_be_a_metric_name() {
+        assert_eq!(load(&[]).unwrap().metrics_prefix, "game_night");
+        assert_eq!(load(&[("METRICS_PREFIX", "lan_party")]).unwrap().metrics_prefix, "lan_party");
+
+        let err = load(&[("METRICS_PREFIX", "9lives")]).unwrap_err();
+        assert_eq!(err.name, "METRICS_PREFIX");
+    }
+
+    #[test]
+    fn zero_max_visible_options_shows_every_option() {
+        assert_eq!(load(&[]).unwrap().max_visible_options, None);
+        assert_eq!(load(&[("MAX_VISIBLE_OPTIONS", "0")]).unwrap().max_visible_options, None);
+        assert_eq!(load(&[("MAX_VISIBLE_OPTIONS", "5")]).unwrap().max_visible_options, Some(5));
+    }
+}
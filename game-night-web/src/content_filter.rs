@@ -0,0 +1,124 @@
+//! # Content Filter Module
+//!
+//! This module provides an optional word blocklist for user-submitted poll
+//! content, so shared game night instances can keep titles and options clean.
+//!
+//! ## Configuration
+//! Set `CONTENT_BLOCKLIST_PATH` to a text file containing one blocked word or
+//! phrase per line. Blank lines and lines starting with `#` are ignored. When
+//! the variable is unset the filter allows everything. The file is read once
+//! while the configuration loads, so an unreadable file stops the server at
+//! startup.
+//!
+//! ## Matching
+//! Matching is case-insensitive and only considers whole words, so a blocked
+//! word never matches inside a longer, innocent word (the "Scunthorpe problem").
+
+use std::fs;
+use std::io;
+
+use crate::config;
+
+/// Reads and parses the blocklist file at `path`.
+///
+/// # Arguments
+/// * `path` - Path of the blocklist file
+///
+/// # Returns
+/// * `Ok(Vec<Vec<String>>)` - The parsed entries, each split into lowercase words
+/// * `Err(io::Error)` - If the file can't be read
+pub fn load_blocklist(path: &str) -> io::Result<Vec<Vec<String>>> {
+    let entries = parse_blocklist(&fs::read_to_string(path)?);
+    log::info!("Loaded {} blocked term(s) from {}", entries.len(), path);
+    Ok(entries)
+}
+
+/// Parses blocklist file contents into lowercase word sequences.
+///
+/// # Arguments
+/// * `contents` - Blocklist text with one word or phrase per line
+///
+/// # Returns
+/// One entry per non-empty, non-comment line
+fn parse_blocklist(contents: &str) -> Vec<Vec<String>> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(words)
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+/// Splits text into lowercase words on any non-alphanumeric character.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Checks whether text contains a blocked word or phrase.
+///
+/// Always returns `false` when no blocklist is configured.
+///
+/// # Arguments
+/// * `text` - User-submitted text to check
+///
+/// # Returns
+/// `true` if any blocked entry appears in the text as whole words
+pub fn contains_blocked(text: &str) -> bool {
+    matches_blocklist(&config::get().content_blocklist, text)
+}
+
+/// Checks text against the given blocklist entries.
+fn matches_blocklist(blocklist: &[Vec<String>], text: &str) -> bool {
+    if blocklist.is_empty() {
+        return false;
+    }
+
+    let text_words = words(text);
+    blocklist.iter().any(|entry| {
+        text_words
+            .windows(entry.len())
+            .any(|window| window == entry.as_slice())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_blank_lines_and_comments() {
+        let entries = parse_blocklist("# comment\n\n  Spam  \nBad Word\n");
+        assert_eq!(entries, vec![vec!["spam"], vec!["bad", "word"]]);
+    }
+
+    #[test]
+    fn matches_whole_words_case_insensitively() {
+        let entries = parse_blocklist("ass");
+        assert!(matches_blocklist(&entries, "What an ASS"));
+        assert!(matches_blocklist(&entries, "ass, really"));
+        assert!(!matches_blocklist(&entries, "A classic game"));
+        assert!(!matches_blocklist(&entries, "assassin"));
+    }
+
+    #[test]
+    fn matches_phrases_as_consecutive_words() {
+        let entries = parse_blocklist("bad word");
+        assert!(matches_blocklist(&entries, "That's a bad-word!"));
+        assert!(!matches_blocklist(&entries, "bad other word"));
+        assert!(!matches_blocklist(&entries, "badword"));
+    }
+
+    #[test]
+    fn empty_blocklist_allows_everything() {
+        assert!(!matches_blocklist(&[], "anything at all"));
+    }
+
+    #[test]
+    fn unreadable_file_is_an_error() {
+        assert!(load_blocklist("/nonexistent/blocklist.txt").is_err());
+    }
+}
@@ -0,0 +1,61 @@
+//! # Audit Controller Module
+//!
+//! This module records administrative and security-relevant actions to the
+//! `audit_log` table and retrieves them for review by administrators.
+//!
+//! ## Key Functions
+//! - Recording audit entries for sensitive actions
+//! - Listing recent audit entries for the admin audit view
+
+use log::error;
+use sqlx::SqlitePool;
+
+use crate::models::AuditEntry;
+
+/// Records an entry in the audit log.
+///
+/// Audit logging must never prevent the underlying action from completing,
+/// so failures are logged rather than returned to the caller.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `actor_id` - ID of the user who performed the action, if any
+/// * `action` - Short dotted identifier for the action (e.g. `user.revoke_sessions`)
+/// * `details` - Human-readable description of the action
+pub async fn record(pool: &SqlitePool, actor_id: Option<i64>, action: &str, details: &str) {
+    let result = sqlx::query("INSERT INTO audit_log (actor_id, action, details) VALUES (?, ?, ?)")
+        .bind(actor_id)
+        .bind(action)
+        .bind(details)
+        .execute(pool)
+        .await;
+
+    if let Err(err) = result {
+        error!("Failed to record audit entry '{}': {}", action, err);
+    }
+}
+
+/// Retrieves the most recent audit log entries.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `limit` - Maximum number of entries to return
+///
+/// # Returns
+/// * `Ok(Vec<AuditEntry>)` - Entries ordered from newest to oldest
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_recent_entries(
+    pool: &SqlitePool,
+    limit: i64,
+) -> Result<Vec<AuditEntry>, sqlx::Error> {
+    sqlx::query_as::<_, AuditEntry>(
+        "SELECT a.id, a.actor_id, u.username as actor_username, a.action, a.details, a.created_at
+         FROM audit_log a
+         LEFT JOIN users u ON a.actor_id = u.id
+         ORDER BY a.created_at DESC, a.id DESC
+         LIMIT ?",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
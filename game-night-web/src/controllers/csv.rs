@@ -0,0 +1,58 @@
+//! # CSV Export Module
+//!
+//! This module provides the shared plumbing for CSV downloads. Exports are
+//! produced by a background task that writes rows into a bounded channel as
+//! they are fetched from the database, so memory use stays flat no matter how
+//! many rows are exported.
+//!
+//! ## Key Functions
+//! - Spawning a channel-fed CSV stream
+//! - Escaping individual CSV fields
+
+use std::future::Future;
+
+use rocket::futures::stream::{self, BoxStream, StreamExt};
+use tokio::sync::mpsc;
+
+/// Number of rows that may be buffered ahead of the client.
+const CHANNEL_CAPACITY: usize = 64;
+
+/// A CSV document delivered as a stream of chunks (typically one row each).
+pub type CsvStream = BoxStream<'static, String>;
+
+/// Spawns a CSV producer and returns the stream of chunks it writes.
+///
+/// The producer receives the sending half of a bounded channel. Sends wait
+/// while the client is behind, and fail once the client has disconnected,
+/// which is the producer's cue to stop.
+///
+/// # Arguments
+/// * `produce` - Closure that writes the CSV rows into the channel
+///
+/// # Returns
+/// A stream yielding each chunk in the order it was sent
+pub fn spawn<F, Fut>(produce: F) -> CsvStream
+where
+    F: FnOnce(mpsc::Sender<String>) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+    tokio::spawn(produce(tx));
+
+    stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|chunk| (chunk, rx)) }).boxed()
+}
+
+/// Quotes a CSV field if it contains a delimiter, quote, or line break.
+///
+/// # Arguments
+/// * `value` - Raw field value
+///
+/// # Returns
+/// The field, quoted and with embedded quotes doubled when necessary
+pub fn escape_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
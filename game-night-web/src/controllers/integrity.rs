@@ -0,0 +1,175 @@
+//! # Integrity Controller Module
+//!
+//! This module finds and removes rows left behind by deletions that did
+//! not clean up after themselves, such as votes for options that no longer
+//! exist. Older databases were written without foreign keys enforced, so
+//! these orphans can predate any current code path.
+//!
+//! ## Key Functions
+//! - Counting orphaned rows and polls without options
+//! - Deleting orphaned rows in one transaction (admin only)
+
+use log::info;
+use sqlx::SqlitePool;
+
+use crate::controllers::audit;
+use crate::models::IntegrityReport;
+
+/// Rows whose option is missing or belongs to a missing poll.
+const ORPHANED_VOTES: &str = "option_id NOT IN (SELECT o.id FROM options o JOIN polls p ON o.poll_id = p.id)
+     OR user_id NOT IN (SELECT id FROM users)";
+
+/// Options whose poll is missing.
+const ORPHANED_OPTIONS: &str = "poll_id NOT IN (SELECT id FROM polls)";
+
+/// Comments whose poll, option, or author is missing.
+const ORPHANED_COMMENTS: &str = "poll_id NOT IN (SELECT id FROM polls)
+     OR (option_id IS NOT NULL
+         AND option_id NOT IN (SELECT o.id FROM options o JOIN polls p ON o.poll_id = p.id))
+     OR user_id NOT IN (SELECT id FROM users)";
+
+/// Orphan checks as (table, condition), in the order repairs delete them.
+/// Votes and comments go before options so they are judged against the
+/// options that are about to be removed.
+const ORPHAN_CHECKS: [(&str, &str); 3] = [
+    ("votes", ORPHANED_VOTES),
+    ("comments", ORPHANED_COMMENTS),
+    ("options", ORPHANED_OPTIONS),
+];
+
+/// Counts orphaned rows and polls without any options.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(IntegrityReport)` - Number of problem rows of each kind
+/// * `Err(sqlx::Error)` - Database error if a query fails
+pub async fn check(pool: &SqlitePool) -> Result<IntegrityReport, sqlx::Error> {
+    let mut counts = [0; ORPHAN_CHECKS.len()];
+    for (count, (table, condition)) in counts.iter_mut().zip(ORPHAN_CHECKS) {
+        *count = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {} WHERE {}", table, condition))
+            .fetch_one(pool)
+            .await?;
+    }
+
+    let polls_without_options: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM polls p WHERE NOT EXISTS (SELECT 1 FROM options o WHERE o.poll_id = p.id)",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let [orphaned_votes, orphaned_comments, orphaned_options] = counts;
+    Ok(IntegrityReport {
+        orphaned_votes,
+        orphaned_comments,
+        orphaned_options,
+        polls_without_options,
+    })
+}
+
+/// Deletes all orphaned rows in a single transaction (admin only).
+///
+/// Polls without options are only reported, not deleted, since their
+/// creator may still add options to them.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `admin_id` - ID of the admin running the repair
+///
+/// # Returns
+/// * `Ok(IntegrityReport)` - Number of rows deleted of each kind, with the
+///   number of polls without options left as found
+/// * `Err(sqlx::Error)` - Database error; nothing is deleted
+pub async fn repair(pool: &SqlitePool, admin_id: i64) -> Result<IntegrityReport, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let mut counts = [0; ORPHAN_CHECKS.len()];
+    for (count, (table, condition)) in counts.iter_mut().zip(ORPHAN_CHECKS) {
+        *count = sqlx::query(&format!("DELETE FROM {} WHERE {}", table, condition))
+            .execute(&mut *tx)
+            .await?
+            .rows_affected() as i64;
+    }
+
+    let polls_without_options: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM polls p WHERE NOT EXISTS (SELECT 1 FROM options o WHERE o.poll_id = p.id)",
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let [orphaned_votes, orphaned_comments, orphaned_options] = counts;
+    let removed = IntegrityReport {
+        orphaned_votes,
+        orphaned_comments,
+        orphaned_options,
+        polls_without_options,
+    };
+
+    audit::record(
+        pool,
+        Some(admin_id),
+        "integrity.repair",
+        &format!(
+            "Removed {} orphaned vote(s), {} orphaned comment(s), and {} orphaned option(s)",
+            removed.orphaned_votes, removed.orphaned_comments, removed.orphaned_options
+        ),
+    )
+    .await;
+    info!("Integrity repair by user {}: {:?}", admin_id, removed);
+
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Seeds one healthy poll with a vote, one poll without options, and one
+    /// orphaned vote, comment, and option pointing at rows that never existed.
+    async fn seed(pool: &SqlitePool) -> sqlx::Result<()> {
+        let mut conn = pool.acquire().await?;
+        for sql in [
+            "PRAGMA foreign_keys = OFF",
+            "INSERT INTO users (id, username, password_hash) VALUES (1, 'admin', '!')",
+            "INSERT INTO polls (id, title, creator_id, expires_at)
+             VALUES (1, 'Next game', 1, '2030-01-01 00:00:00'),
+                    (2, 'Empty', 1, '2030-01-01 00:00:00')",
+            "INSERT INTO options (id, poll_id, text) VALUES (1, 1, 'Catan'), (2, 99, 'Azul')",
+            "INSERT INTO votes (user_id, option_id) VALUES (1, 1), (1, 98)",
+            "INSERT INTO comments (poll_id, user_id, body) VALUES (1, 1, 'Fun'), (99, 1, 'Lost')",
+            "PRAGMA foreign_keys = ON",
+        ] {
+            sqlx::query(sql).execute(&mut *conn).await?;
+        }
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn repair_removes_only_orphans(pool: SqlitePool) -> sqlx::Result<()> {
+        seed(&pool).await?;
+        let counts = |report: IntegrityReport| {
+            (
+                report.orphaned_votes,
+                report.orphaned_comments,
+                report.orphaned_options,
+                report.polls_without_options,
+            )
+        };
+
+        assert_eq!(counts(check(&pool).await?), (1, 1, 1, 1));
+        assert_eq!(counts(repair(&pool, 1).await?), (1, 1, 1, 1));
+        assert_eq!(counts(check(&pool).await?), (0, 0, 0, 1));
+
+        let votes: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM votes").fetch_one(&pool).await?;
+        let comments: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM comments").fetch_one(&pool).await?;
+        assert_eq!((votes, comments), (1, 1));
+
+        let entries = audit::get_recent_entries(&pool, 1).await?;
+        assert_eq!(entries[0].action, "integrity.repair");
+        Ok(())
+    }
+}
@@ -5,7 +5,13 @@
 //! and prepare data for the presentation layer.
 //!
 //! ## Submodules
+//! - [`audit`] - Audit log recording and review
+//! - [`csv`] - Streaming CSV exports
+//! - [`integrity`] - Database consistency checks and repairs
+//! - [`notify`] - In-app notifications
 //! - [`polls`] - Poll management, voting, and statistics
+//! - [`settings`] - Runtime settings managed by administrators
+//! - [`tombstone`] - Short-lived copies of deleted polls for undo
 //! - [`users`] - User management, authentication, and roles
 //!
 //! ## Architecture
@@ -15,8 +21,26 @@
 //! - Interacting with the database layer
 //! - Returning formatted responses for the view layer
 
+/// Audit log recording for administrative and security-relevant actions.
+pub mod audit;
+
+/// Streaming CSV export helpers shared by the user and poll exports.
+pub mod csv;
+
+/// Checks for and removal of orphaned rows, for administrators.
+pub mod integrity;
+
+/// In-app notifications such as nudges to vote.
+pub mod notify;
+
 /// Poll-related business logic including creation, voting, deletion, and statistics.
 pub mod polls;
 
+/// Runtime settings such as the global poll creation switch.
+pub mod settings;
+
+/// In-memory copies of recently deleted polls, so deletions can be undone.
+pub mod tombstone;
+
 /// User-related business logic including authentication, management, and roles.
 pub mod users;
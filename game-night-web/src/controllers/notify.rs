@@ -0,0 +1,366 @@
+//! # Notification Controller Module
+//!
+//! This module records in-app notifications for users and retrieves them
+//! for display.
+//!
+//! ## Key Functions
+//! - Pushing a notification to a user
+//! - Broadcasting a notification to every user (admin only)
+//! - Listing a user's notifications and counting unread ones
+//! - Marking notifications as read
+
+use chrono::{Duration, Utc};
+use log::info;
+use sqlx::SqlitePool;
+
+use crate::controllers::audit;
+use crate::models::Notification;
+
+/// Minimum time between broadcasts, so a double submit can't spam everyone.
+const BROADCAST_INTERVAL_MINUTES: i64 = 10;
+
+/// Settings key recording when the last broadcast was sent.
+const LAST_BROADCAST_AT: &str = "last_broadcast_at";
+
+/// Longest broadcast subject accepted, in characters.
+const MAX_SUBJECT_CHARS: usize = 100;
+
+/// Longest broadcast message accepted, in characters.
+const MAX_BODY_CHARS: usize = 500;
+
+/// Builds the error returned when broadcast input fails validation.
+///
+/// # Arguments
+/// * `message` - Human-readable description of the problem
+fn invalid_input(message: &str) -> sqlx::Error {
+    sqlx::Error::ColumnDecode {
+        index: "".to_string(),
+        source: Box::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            message.to_string(),
+        )),
+    }
+}
+
+/// Records a notification for a user.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `user_id` - ID of the user to notify
+/// * `kind` - Short identifier for the type of notification (e.g. `nudge`)
+/// * `body` - Human-readable notification text
+/// * `link` - Optional path the notification points to
+///
+/// # Returns
+/// * `Ok(())` - Notification recorded
+/// * `Err(sqlx::Error)` - Database error if the insert fails
+pub async fn push(
+    pool: &SqlitePool,
+    user_id: i64,
+    kind: &str,
+    body: &str,
+    link: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO notifications (user_id, kind, body, link) VALUES (?, ?, ?, ?)")
+        .bind(user_id)
+        .bind(kind)
+        .bind(body)
+        .bind(link)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Sends a notification to every active user except the sender (admin only).
+///
+/// Broadcasts are limited to one every `BROADCAST_INTERVAL_MINUTES`. The
+/// throttle is claimed before anything is sent, so concurrent requests
+/// can't both go out, and all notifications are written in one statement.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `sender_id` - ID of the admin sending the broadcast
+/// * `subject` - Short subject line
+/// * `body` - Text of the message
+///
+/// # Returns
+/// * `Ok(u64)` - Number of users notified
+/// * `Err(sqlx::Error)` - Database error, or ColumnDecode if the subject or
+///   message is empty or too long, or a broadcast was sent too recently
+pub async fn broadcast(
+    pool: &SqlitePool,
+    sender_id: i64,
+    subject: &str,
+    body: &str,
+) -> Result<u64, sqlx::Error> {
+    let subject = subject.trim();
+    let body = body.trim();
+    if subject.is_empty() || body.is_empty() {
+        return Err(invalid_input("A broadcast needs both a subject and a message."));
+    }
+    if subject.chars().count() > MAX_SUBJECT_CHARS {
+        return Err(invalid_input(&format!(
+            "The subject can be at most {} characters.",
+            MAX_SUBJECT_CHARS
+        )));
+    }
+    if body.chars().count() > MAX_BODY_CHARS {
+        return Err(invalid_input(&format!(
+            "The message can be at most {} characters.",
+            MAX_BODY_CHARS
+        )));
+    }
+
+    let now = Utc::now();
+    let cutoff = now - Duration::minutes(BROADCAST_INTERVAL_MINUTES);
+    let mut tx = pool.begin().await?;
+
+    // Claim the broadcast atomically; timestamps are stored as RFC 3339,
+    // which compares correctly as text
+    let claimed = sqlx::query(
+        "INSERT INTO settings (key, value, updated_at) VALUES (?, ?, CURRENT_TIMESTAMP)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+         WHERE settings.value <= ?",
+    )
+    .bind(LAST_BROADCAST_AT)
+    .bind(now.to_rfc3339())
+    .bind(cutoff.to_rfc3339())
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if claimed == 0 {
+        return Err(invalid_input(&format!(
+            "A broadcast was already sent in the last {} minutes.",
+            BROADCAST_INTERVAL_MINUTES
+        )));
+    }
+
+    let sent = sqlx::query(
+        "INSERT INTO notifications (user_id, kind, body, link)
+         SELECT id, 'broadcast', ?, NULL FROM users WHERE id != ? AND disabled = 0",
+    )
+    .bind(format!("{}: {}", subject, body))
+    .bind(sender_id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    tx.commit().await?;
+
+    audit::record(
+        pool,
+        Some(sender_id),
+        "notification.broadcast",
+        &format!("Broadcast \"{}\" to {} users", subject, sent),
+    )
+    .await;
+
+    info!("User {} broadcast a notification to {} users", sender_id, sent);
+    Ok(sent)
+}
+
+/// Retrieves a user's most recent unread notifications.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `user_id` - ID of the user
+/// * `limit` - Maximum number of notifications to return
+///
+/// # Returns
+/// * `Ok(Vec<Notification>)` - Unread notifications ordered from newest to oldest
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_unread(
+    pool: &SqlitePool,
+    user_id: i64,
+    limit: i64,
+) -> Result<Vec<Notification>, sqlx::Error> {
+    sqlx::query_as::<_, Notification>(
+        "SELECT id, user_id, kind, body, link, read_at, created_at
+         FROM notifications
+         WHERE user_id = ? AND read_at IS NULL
+         ORDER BY created_at DESC, id DESC
+         LIMIT ?",
+    )
+    .bind(user_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Retrieves a user's most recent notifications, read or not.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `user_id` - ID of the user
+/// * `limit` - Maximum number of notifications to return
+///
+/// # Returns
+/// * `Ok(Vec<Notification>)` - Notifications ordered from newest to oldest
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_recent(
+    pool: &SqlitePool,
+    user_id: i64,
+    limit: i64,
+) -> Result<Vec<Notification>, sqlx::Error> {
+    sqlx::query_as::<_, Notification>(
+        "SELECT id, user_id, kind, body, link, read_at, created_at
+         FROM notifications
+         WHERE user_id = ?
+         ORDER BY created_at DESC, id DESC
+         LIMIT ?",
+    )
+    .bind(user_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Counts a user's unread notifications.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `user_id` - ID of the user
+///
+/// # Returns
+/// * `Ok(i64)` - Number of unread notifications
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn unread_count(pool: &SqlitePool, user_id: i64) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("SELECT COUNT(*) FROM notifications WHERE user_id = ? AND read_at IS NULL")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+}
+
+/// Marks one of a user's notifications as read.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `user_id` - ID of the user the notification belongs to
+/// * `notification_id` - ID of the notification
+///
+/// # Returns
+/// * `Ok(())` - Notification marked as read (or already read)
+/// * `Err(sqlx::Error)` - Database error, or RowNotFound if the user has no
+///   such notification
+pub async fn mark_read(
+    pool: &SqlitePool,
+    user_id: i64,
+    notification_id: i64,
+) -> Result<(), sqlx::Error> {
+    let updated = sqlx::query(
+        "UPDATE notifications SET read_at = COALESCE(read_at, ?) WHERE id = ? AND user_id = ?",
+    )
+    .bind(Utc::now())
+    .bind(notification_id)
+    .bind(user_id)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    if updated == 0 {
+        return Err(sqlx::Error::RowNotFound);
+    }
+
+    Ok(())
+}
+
+/// Marks all of a user's unread notifications as read.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `user_id` - ID of the user
+///
+/// # Returns
+/// * `Ok(u64)` - Number of notifications marked as read
+/// * `Err(sqlx::Error)` - Database error if the update fails
+pub async fn mark_all_read(pool: &SqlitePool, user_id: i64) -> Result<u64, sqlx::Error> {
+    let updated = sqlx::query("UPDATE notifications SET read_at = ? WHERE user_id = ? AND read_at IS NULL")
+        .bind(Utc::now())
+        .bind(user_id)
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Inserts a user and returns their ID.
+    async fn add_user(pool: &SqlitePool, username: &str, disabled: bool) -> i64 {
+        sqlx::query("INSERT INTO users (username, password_hash, disabled) VALUES (?, '!', ?)")
+            .bind(username)
+            .bind(disabled)
+            .execute(pool)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    /// Returns the message of an error built by [`invalid_input`].
+    fn message(err: sqlx::Error) -> String {
+        match err {
+            sqlx::Error::ColumnDecode { source, .. } => source.to_string(),
+            other => panic!("expected invalid input, got {}", other),
+        }
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn broadcast_reaches_active_users_once(pool: SqlitePool) -> sqlx::Result<()> {
+        let admin = add_user(&pool, "admin", false).await;
+        let alice = add_user(&pool, "alice", false).await;
+        let bob = add_user(&pool, "bob", false).await;
+        add_user(&pool, "gone", true).await;
+
+        let err = broadcast(&pool, admin, "  ", "Pizza at 7").await.unwrap_err();
+        assert_eq!(message(err), "A broadcast needs both a subject and a message.");
+        let long = "x".repeat(MAX_SUBJECT_CHARS + 1);
+        assert!(broadcast(&pool, admin, &long, "Pizza at 7").await.is_err());
+
+        assert_eq!(broadcast(&pool, admin, " Friday ", " Pizza at 7 ").await?, 2);
+        let sent: Vec<(i64, String)> = sqlx::query_as(
+            "SELECT user_id, body FROM notifications WHERE kind = 'broadcast' ORDER BY user_id",
+        )
+        .fetch_all(&pool)
+        .await?;
+        let body = "Friday: Pizza at 7".to_string();
+        assert_eq!(sent, [(alice, body.clone()), (bob, body)]);
+
+        let err = broadcast(&pool, admin, "Again", "Pizza at 8").await.unwrap_err();
+        assert!(message(err).starts_with("A broadcast was already sent"));
+        assert_eq!(unread_count(&pool, alice).await?, 1);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn notifications_are_read_only_by_their_owner(pool: SqlitePool) -> sqlx::Result<()> {
+        let alice = add_user(&pool, "alice", false).await;
+        let bob = add_user(&pool, "bob", false).await;
+        for body in ["First", "Second", "Third"] {
+            push(&pool, alice, "vote", body, Some("/polls/1")).await?;
+        }
+        push(&pool, bob, "vote", "Bob's", None).await?;
+
+        let recent = get_recent(&pool, alice, 2).await?;
+        let bodies: Vec<&str> = recent.iter().map(|n| n.body.as_str()).collect();
+        assert_eq!(bodies, ["Third", "Second"]);
+
+        // Someone else's notification can't be marked read
+        let err = mark_read(&pool, bob, recent[0].id).await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::RowNotFound), "{}", err);
+        mark_read(&pool, alice, recent[0].id).await?;
+        mark_read(&pool, alice, recent[0].id).await?;
+        assert_eq!(unread_count(&pool, alice).await?, 2);
+        assert_eq!(get_unread(&pool, alice, 10).await?.len(), 2);
+
+        assert_eq!(mark_all_read(&pool, alice).await?, 2);
+        assert_eq!(unread_count(&pool, alice).await?, 0);
+        assert_eq!(get_recent(&pool, alice, 10).await?.len(), 3);
+        assert_eq!(unread_count(&pool, bob).await?, 1);
+        Ok(())
+    }
+}
@@ -5,25 +5,388 @@
 //!
 //! ## Key Functions
 //! - Poll creation with options and expiration dates
+//! - Exporting a poll's structure as a template, and creating polls from one
 //! - Voting and vote toggling functionality
-//! - Poll deletion (by creator or admin)
+//! - Poll deletion and reopening (by creator or admin)
+//! - Nudging users who haven't voted yet
+//! - Comments on polls and their options
 //! - Poll querying (active, expired, detailed views)
 //! - Voter statistics and detailed voting information
 //! - Template data formatting
+//!
+//! ## Expiry
+//! "Now" always comes from the server clock via `Utc::now()`. Queries that
+//! filter on expiry bind it as a parameter rather than using SQLite's
+//! `datetime('now')`, and compare both sides through `julianday()`, since
+//! bound and stored timestamps aren't always formatted alike. A poll is
+//! expired once `expires_at <= now`, everywhere.
 
-use chrono::Utc;
+use chrono::{DateTime, DurationRound, Utc};
 use log::{error, info};
-use sqlx::{Row, SqlitePool};
+use rocket::futures::StreamExt;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 
+use crate::config;
+use crate::content_filter;
+use crate::controllers::{audit, csv, notify, settings, tombstone};
 use crate::models::{
-    NewOptionsForm, NewPollForm, OptionWithVoters, PollOption, PollVotingDetails, PollWithCreator,
-    User, VoteWithUser,
+    Ballot, CalendarEntry, Comment, DashboardPoll, NewOptionsForm, NewPollForm, OptionVotersPage,
+    OptionWithVoters, PollMeta, PollOption, PollTemplate, PollTemplateOption, PollTemplateSettings,
+    PollVotingDetails, PollWithCreator, ResultsSnapshot, TimelineBucket, TimelinePoint, User, VoteCounts,
+    VoteOutcome, VoteWithUser,
 };
 
+/// Parses a `datetime-local` form value (YYYY-MM-DDTHH:MM) as a UTC timestamp.
+///
+/// # Arguments
+/// * `value` - The raw form value
+///
+/// # Returns
+/// `Some(DateTime<Utc>)` if the value is well-formed, `None` otherwise
+fn parse_form_datetime(value: &str) -> Option<DateTime<Utc>> {
+    chrono::DateTime::parse_from_rfc3339(&format!("{}:00Z", value))
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Builds the error returned when poll form input fails validation.
+///
+/// # Arguments
+/// * `message` - Human-readable description of the problem
+fn invalid_input(message: &str) -> sqlx::Error {
+    sqlx::Error::ColumnDecode {
+        index: "".to_string(),
+        source: Box::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            message.to_string(),
+        )),
+    }
+}
+
+/// Builds the error returned when a new poll repeats the title of one of
+/// the creator's open polls and the duplicate wasn't confirmed.
+///
+/// Routes match on the `duplicate` index to ask for confirmation instead of
+/// reporting a failure.
+fn duplicate_poll(title: &str) -> sqlx::Error {
+    sqlx::Error::ColumnDecode {
+        index: "duplicate".to_string(),
+        source: Box::new(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("You already have an open poll titled \"{}\"", title),
+        )),
+    }
+}
+
+/// Builds the error returned when a user acts on a poll they can see but
+/// don't own.
+///
+/// Seeing the poll already tells them it exists, so refusing the action
+/// doesn't reveal anything; routes report it as forbidden rather than
+/// not found. See the policy in [`crate::routes`], and [`refused`] for
+/// polls that may be waiting for approval.
+pub fn forbidden() -> sqlx::Error {
+    sqlx::Error::ColumnDecode {
+        index: "forbidden".to_string(),
+        source: Box::new(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "You don't have permission to modify this poll",
+        )),
+    }
+}
+
+/// Builds the error returned when a user acts on a poll they don't own.
+///
+/// Published polls are visible to every signed-in user, so the refusal is
+/// [`forbidden`]. A poll waiting for approval is hidden from everyone but
+/// its creator and admins, so it gives `RowNotFound`, as if it didn't exist.
+fn refused(published: bool) -> sqlx::Error {
+    if published {
+        forbidden()
+    } else {
+        sqlx::Error::RowNotFound
+    }
+}
+
+/// Maximum accepted length of an option image URL.
+const MAX_IMAGE_URL_LEN: usize = 2048;
+
+/// Maximum accepted length of a vote comment, in characters.
+const MAX_VOTE_COMMENT_LEN: usize = 500;
+
+/// Maximum accepted length of a write-in option, in characters.
+const MAX_WRITE_IN_LEN: usize = 200;
+
+/// Maximum number of write-in options one voter may add to a poll.
+const MAX_WRITE_INS_PER_USER: i64 = 3;
+
+/// A poll option parsed from form input, ready to be inserted.
+struct ParsedOption<'a> {
+    text: &'a str,
+    is_date: bool,
+    date_time: Option<DateTime<Utc>>,
+    image_url: Option<&'a str>,
+    max_votes: Option<i64>,
+}
+
+/// Parses a list of poll options from form input.
+///
+/// Options are split on `separator` (a comma, or a newline for one option
+/// per line), trimmed, and blank entries dropped.
+///
+/// Each option is either plain text or a date in format YYYY-MM-DDTHH:MM,
+/// optionally followed by `|`-separated extras: an http(s) image URL and/or
+/// `max=N` to cap the option at N votes, for example
+/// `Catan|https://example.com/catan.jpg` or `2024-03-01T19:00|max=6`.
+///
+/// # Arguments
+/// * `raw` - The raw options value
+/// * `separator` - The character separating options
+///
+/// # Returns
+/// * `Ok(Vec<ParsedOption>)` - The non-empty options in input order
+/// * `Err(sqlx::Error)` - An option contains blocked words, an invalid image URL,
+///   or an invalid vote cap
+fn parse_options(raw: &str, separator: char) -> Result<Vec<ParsedOption<'_>>, sqlx::Error> {
+    let mut options = Vec::new();
+
+    for option in raw.split(separator).map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        let mut parts = option.split('|').map(str::trim);
+        let text = parts.next().unwrap_or_default();
+
+        if text.is_empty() {
+            return Err(invalid_input(&format!("Option \"{}\" is missing its text", option)));
+        }
+
+        let mut image_url = None;
+        let mut max_votes = None;
+        for part in parts.filter(|part| !part.is_empty()) {
+            match part.strip_prefix("max=") {
+                Some(max) => match max.trim().parse::<i64>() {
+                    Ok(max) if max > 0 => max_votes = Some(max),
+                    _ => {
+                        return Err(invalid_input(&format!(
+                            "Option \"{}\" has an invalid vote cap (must be a whole number above 0)",
+                            text
+                        )));
+                    }
+                },
+                None => image_url = Some(part),
+            }
+        }
+
+        check_option_content(text, image_url)?;
+
+        // Check if the option is a date/time
+        let is_date = text.contains("T") && text.len() >= 16;
+        let date_time = if is_date {
+            parse_form_datetime(text)
+        } else {
+            None
+        };
+
+        options.push(ParsedOption {
+            text,
+            is_date,
+            date_time,
+            image_url,
+            max_votes,
+        });
+    }
+
+    Ok(options)
+}
+
+/// Checks an option's text and image URL before it is added to a poll.
+///
+/// # Arguments
+/// * `text` - The option text
+/// * `image_url` - The option's image URL, if any
+///
+/// # Returns
+/// * `Ok(())` - The option is acceptable
+/// * `Err(sqlx::Error)` - The text contains blocked words, or the image URL is invalid
+fn check_option_content(text: &str, image_url: Option<&str>) -> Result<(), sqlx::Error> {
+    if content_filter::contains_blocked(text) {
+        return Err(invalid_input(&format!("Option \"{}\" contains blocked words", text)));
+    }
+
+    if let Some(url) = image_url {
+        let is_http = url.starts_with("http://") || url.starts_with("https://");
+        if !is_http || url.len() > MAX_IMAGE_URL_LEN || url.contains(char::is_whitespace) {
+            return Err(invalid_input(&format!(
+                "Option \"{}\" has an invalid image URL (must be http or https)",
+                text
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks a new poll's quorum settings.
+///
+/// # Arguments
+/// * `quorum` - Number of voters needed, if any
+/// * `auto_close_at_quorum` - Whether the poll should close at the quorum
+///
+/// # Returns
+/// * `Ok(())` - The settings are consistent
+/// * `Err(sqlx::Error)` - The quorum is negative, or closing at quorum was
+///   asked for without one
+fn check_quorum(quorum: Option<i64>, auto_close_at_quorum: bool) -> Result<(), sqlx::Error> {
+    if quorum.is_some_and(|quorum| quorum < 0) {
+        return Err(invalid_input("Quorum cannot be negative"));
+    }
+
+    if auto_close_at_quorum && quorum.is_none_or(|quorum| quorum == 0) {
+        return Err(invalid_input("Set a quorum to close the poll automatically when it is reached"));
+    }
+
+    Ok(())
+}
+
+/// Poll kinds accepted on creation. `mixed` allows any combination of
+/// options; the others require every option to be of that kind.
+const POLL_KINDS: [&str; 3] = ["mixed", "text", "dates"];
+
+/// Checks that every option matches the poll's kind.
+///
+/// An option counts as a date only if it parses as YYYY-MM-DDTHH:MM.
+///
+/// # Arguments
+/// * `poll_kind` - The poll's kind: `mixed`, `text`, or `dates`
+/// * `options` - The parsed options to check
+///
+/// # Returns
+/// * `Ok(())` - Every option matches
+/// * `Err(sqlx::Error)` - The first option that doesn't match, by name
+fn check_poll_kind(poll_kind: &str, options: &[ParsedOption<'_>]) -> Result<(), sqlx::Error> {
+    for option in options {
+        let is_date = option.date_time.is_some();
+        match poll_kind {
+            "dates" if !is_date => {
+                return Err(invalid_input(&format!(
+                    "Option \"{}\" is not a date (YYYY-MM-DDTHH:MM), but this poll only accepts dates",
+                    option.text
+                )));
+            }
+            "text" if is_date => {
+                return Err(invalid_input(&format!(
+                    "Option \"{}\" is a date, but this poll only accepts text options",
+                    option.text
+                )));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Turns a violation of the unique option text index into a message naming
+/// the repeated option, passing any other error through.
+///
+/// Option text is unique within a poll, ignoring case. Checking first would
+/// still race with concurrent additions, so the database is the one place
+/// the rule is enforced.
+fn duplicate_option(error: sqlx::Error, text: &str) -> sqlx::Error {
+    match error {
+        sqlx::Error::Database(e) if e.is_unique_violation() => {
+            invalid_input(&format!("Option \"{}\" is already in this poll", text))
+        }
+        other => other,
+    }
+}
+
+/// Inserts a parsed option into a poll.
+///
+/// Fails with a ColumnDecode error naming the option if the poll already
+/// has one with the same text (see [`duplicate_option`]).
+async fn insert_option(
+    conn: &mut SqliteConnection,
+    poll_id: i64,
+    option: &ParsedOption<'_>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO options (poll_id, text, is_date, date_time, image_url, max_votes)
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(poll_id)
+    .bind(option.text)
+    .bind(option.is_date)
+    .bind(option.date_time)
+    .bind(option.image_url)
+    .bind(option.max_votes)
+    .execute(conn)
+    .await
+    .map_err(|e| duplicate_option(e, option.text))?;
+
+    Ok(())
+}
+
+/// Returns the configured number of poll cards per row, if any.
+///
+/// `None` means the default layout, which fits as many cards as the
+/// screen allows.
+pub fn dashboard_columns() -> Option<usize> {
+    config::get().dashboard_columns
+}
+
+/// Returns whether new polls wait for an admin's approval before they're
+/// listed (`REQUIRE_POLL_APPROVAL`).
+pub fn poll_approval_required() -> bool {
+    config::get().require_poll_approval
+}
+
+/// Voters listed per option on a summarized voters page.
+pub const SUMMARY_VOTERS_PER_OPTION: i64 = 10;
+
+/// Voters returned per page when paging through an option's voters.
+pub const OPTION_VOTERS_PAGE_SIZE: i64 = 50;
+
+/// Checks that adding options keeps a poll within `MAX_OPTIONS_PER_POLL`.
+///
+/// Run this in the transaction that inserts the options, so concurrent
+/// additions can't push the poll past the limit between check and insert.
+///
+/// # Arguments
+/// * `conn` - Transaction the options will be inserted in
+/// * `poll_id` - ID of the poll
+/// * `adding` - Number of options about to be inserted
+///
+/// # Returns
+/// * `Ok(())` - The options fit
+/// * `Err(sqlx::Error)` - Database error, or ColumnDecode naming the limit
+async fn check_option_limit(
+    conn: &mut SqliteConnection,
+    poll_id: i64,
+    adding: usize,
+) -> Result<(), sqlx::Error> {
+    let existing: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM options WHERE poll_id = ?")
+        .bind(poll_id)
+        .fetch_one(conn)
+        .await?;
+
+    let max = config::get().max_options_per_poll;
+    if existing + adding as i64 > max {
+        return Err(invalid_input(&format!(
+            "A poll can have at most {} options (this poll has {}, and {} more were given)",
+            max, existing, adding
+        )));
+    }
+
+    Ok(())
+}
+
 /// Retrieves all active (non-expired) polls from the database.
 ///
 /// This function queries for polls that have not yet reached their
 /// expiration date, ordered by creation date (most recent first).
+/// Archived polls and polls awaiting approval are excluded.
 ///
 /// # Arguments
 /// * `pool` - Database connection pool
@@ -34,12 +397,15 @@ use crate::models::{
 pub async fn get_active_polls(pool: &SqlitePool) -> Result<Vec<PollWithCreator>, sqlx::Error> {
     sqlx::query_as::<_, PollWithCreator>(
         "SELECT p.id, p.title, p.description, p.creator_id, u.username as creator_username,
-         p.created_at, p.expires_at
+         p.created_at, p.expires_at, p.votes_lock_at, p.quorum, p.open_voters, p.require_vote_comment,
+         p.auto_close_at_quorum, p.poll_kind, p.archived, p.creator_can_vote, p.multi_select, p.finalized,
+         p.allow_write_in, p.published
          FROM polls p
          JOIN users u ON p.creator_id = u.id
-         WHERE p.expires_at > datetime('now')
+         WHERE julianday(p.expires_at) > julianday(?) AND p.archived = 0 AND p.published = 1
          ORDER BY p.created_at DESC",
     )
+    .bind(Utc::now())
     .fetch_all(pool)
     .await
 }
@@ -48,6 +414,7 @@ pub async fn get_active_polls(pool: &SqlitePool) -> Result<Vec<PollWithCreator>,
 ///
 /// This function queries for polls that have passed their expiration
 /// date, ordered by creation date (most recent first).
+/// Archived polls and polls awaiting approval are excluded.
 ///
 /// # Arguments
 /// * `pool` - Database connection pool
@@ -58,10 +425,69 @@ pub async fn get_active_polls(pool: &SqlitePool) -> Result<Vec<PollWithCreator>,
 pub async fn get_expired_polls(pool: &SqlitePool) -> Result<Vec<PollWithCreator>, sqlx::Error> {
     sqlx::query_as::<_, PollWithCreator>(
         "SELECT p.id, p.title, p.description, p.creator_id, u.username as creator_username,
-         p.created_at, p.expires_at
+         p.created_at, p.expires_at, p.votes_lock_at, p.quorum, p.open_voters, p.require_vote_comment,
+         p.auto_close_at_quorum, p.poll_kind, p.archived, p.creator_can_vote, p.multi_select, p.finalized,
+         p.allow_write_in, p.published
+         FROM polls p
+         JOIN users u ON p.creator_id = u.id
+         WHERE julianday(p.expires_at) <= julianday(?) AND p.archived = 0 AND p.published = 1
+         ORDER BY p.created_at DESC",
+    )
+    .bind(Utc::now())
+    .fetch_all(pool)
+    .await
+}
+
+/// Retrieves every poll created by a user, including expired, archived, and
+/// unapproved ones, ordered by creation date (oldest first).
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `creator_id` - ID of the user whose polls to fetch
+///
+/// # Returns
+/// * `Ok(Vec<PollWithCreator>)` - The user's polls
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_polls_by_creator(
+    pool: &SqlitePool,
+    creator_id: i64,
+) -> Result<Vec<PollWithCreator>, sqlx::Error> {
+    sqlx::query_as::<_, PollWithCreator>(
+        "SELECT p.id, p.title, p.description, p.creator_id, u.username as creator_username,
+         p.created_at, p.expires_at, p.votes_lock_at, p.quorum, p.open_voters, p.require_vote_comment,
+         p.auto_close_at_quorum, p.poll_kind, p.archived, p.creator_can_vote, p.multi_select, p.finalized,
+         p.allow_write_in, p.published
+         FROM polls p
+         JOIN users u ON p.creator_id = u.id
+         WHERE p.creator_id = ?
+         ORDER BY p.created_at, p.id",
+    )
+    .bind(creator_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Retrieves all archived polls from the database.
+///
+/// Archived polls are hidden from the active and expired listings but stay
+/// reachable by direct link. Ordered by creation date (most recent first).
+/// Polls awaiting approval are excluded.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Vec<PollWithCreator>)` - Vector of archived polls with creator information
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_archived_polls(pool: &SqlitePool) -> Result<Vec<PollWithCreator>, sqlx::Error> {
+    sqlx::query_as::<_, PollWithCreator>(
+        "SELECT p.id, p.title, p.description, p.creator_id, u.username as creator_username,
+         p.created_at, p.expires_at, p.votes_lock_at, p.quorum, p.open_voters, p.require_vote_comment,
+         p.auto_close_at_quorum, p.poll_kind, p.archived, p.creator_can_vote, p.multi_select, p.finalized,
+         p.allow_write_in, p.published
          FROM polls p
          JOIN users u ON p.creator_id = u.id
-         WHERE p.expires_at <= datetime('now')
+         WHERE p.archived = 1 AND p.published = 1
          ORDER BY p.created_at DESC",
     )
     .fetch_all(pool)
@@ -74,56 +500,195 @@ pub async fn get_expired_polls(pool: &SqlitePool) -> Result<Vec<PollWithCreator>
 /// the creator's username for display purposes.
 ///
 /// # Arguments
-/// * `pool` - Database connection pool
+/// * `executor` - Database pool, connection or transaction
 /// * `poll_id` - Unique identifier of the poll to retrieve
 ///
 /// # Returns
 /// * `Ok(PollWithCreator)` - The poll with creator information
 /// * `Err(sqlx::Error)` - Database error if poll not found or query fails
-pub async fn get_poll_by_id(
-    pool: &SqlitePool,
-    poll_id: i64,
-) -> Result<PollWithCreator, sqlx::Error> {
+pub async fn get_poll_by_id<'e, E>(executor: E, poll_id: i64) -> Result<PollWithCreator, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
     sqlx::query_as::<_, PollWithCreator>(
         "SELECT p.id, p.title, p.description, p.creator_id, u.username as creator_username,
-         p.created_at, p.expires_at
+         p.created_at, p.expires_at, p.votes_lock_at, p.quorum, p.open_voters, p.require_vote_comment,
+         p.auto_close_at_quorum, p.poll_kind, p.archived, p.creator_can_vote, p.multi_select, p.finalized,
+         p.allow_write_in, p.published
          FROM polls p
          JOIN users u ON p.creator_id = u.id
          WHERE p.id = ?",
     )
     .bind(poll_id)
-    .fetch_one(pool)
+    .fetch_one(executor)
     .await
 }
 
 /// Retrieves all voting options for a specific poll.
 ///
 /// This function fetches all options for a poll including their
-/// vote counts calculated from the votes table.
+/// vote counts calculated from the votes table, both as raw counts and
+/// weighted by each voter's vote weight.
 ///
 /// # Arguments
-/// * `pool` - Database connection pool
+/// * `executor` - Database pool, connection or transaction
 /// * `poll_id` - ID of the poll to get options for
 ///
 /// # Returns
 /// * `Ok(Vec<PollOption>)` - Vector of poll options with vote counts
 /// * `Err(sqlx::Error)` - Database error if query fails
-pub async fn get_poll_options(
+pub async fn get_poll_options<'e, E>(executor: E, poll_id: i64) -> Result<Vec<PollOption>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query_as::<_, PollOption>(
+        "SELECT o.id, o.poll_id, o.text, o.is_date, o.date_time, o.image_url, o.max_votes,
+         o.suggested_by, su.username as suggested_by_username,
+         (SELECT COUNT(*) FROM votes v WHERE v.option_id = o.id) as vote_count,
+         (SELECT COALESCE(SUM(u.vote_weight), 0) FROM votes v JOIN users u ON v.user_id = u.id
+          WHERE v.option_id = o.id) as weighted_votes
+         FROM options o
+         LEFT JOIN users su ON o.suggested_by = su.id
+         WHERE o.poll_id = ?
+         ORDER BY o.id",
+    )
+    .bind(poll_id)
+    .fetch_all(executor)
+    .await
+}
+
+/// Parses one end of a date range, as YYYY-MM-DDTHH:MM or RFC 3339.
+///
+/// # Arguments
+/// * `value` - The raw query value
+///
+/// # Returns
+/// `Some(DateTime<Utc>)` if the value is well-formed, `None` otherwise
+fn parse_range_bound(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    parse_form_datetime(value).or_else(|| {
+        chrono::DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    })
+}
+
+/// Retrieves a poll's date options that fall within a date range.
+///
+/// Both ends of the range are inclusive, and either may be left open. With
+/// no range at all, every option is returned, as by [`get_poll_options`].
+/// Text options have no date, so they are left out of a range unless
+/// `include_text` is set.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll to get options for
+/// * `from` - Optional start of the range, as YYYY-MM-DDTHH:MM or RFC 3339
+/// * `to` - Optional end of the range, as YYYY-MM-DDTHH:MM or RFC 3339
+/// * `include_text` - Whether to also return options without a date
+///
+/// # Returns
+/// * `Ok(Vec<PollOption>)` - Matching options with vote counts, in poll order
+/// * `Err(sqlx::Error)` - Database error, or ColumnDecode if a bound is
+///   malformed or `from` is after `to`
+pub async fn get_options_in_range(
     pool: &SqlitePool,
     poll_id: i64,
+    from: Option<&str>,
+    to: Option<&str>,
+    include_text: bool,
 ) -> Result<Vec<PollOption>, sqlx::Error> {
+    let parse_bound = |name: &str, value: Option<&str>| match value.map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(value) => parse_range_bound(value).map(Some).ok_or_else(|| {
+            invalid_input(&format!(
+                "Invalid {} date \"{}\"; use YYYY-MM-DDTHH:MM",
+                name, value
+            ))
+        }),
+    };
+    let from = parse_bound("from", from)?;
+    let to = parse_bound("to", to)?;
+
+    if from.is_none() && to.is_none() {
+        return get_poll_options(pool, poll_id).await;
+    }
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(invalid_input("The start of the range must not be after its end"));
+        }
+    }
+
+    // Compare as Julian days, since stored and bound timestamps may differ
+    // in how they write fractional seconds
     sqlx::query_as::<_, PollOption>(
-        "SELECT o.id, o.poll_id, o.text, o.is_date, o.date_time,
-         (SELECT COUNT(*) FROM votes v WHERE v.option_id = o.id) as vote_count
+        "SELECT o.id, o.poll_id, o.text, o.is_date, o.date_time, o.image_url, o.max_votes,
+         o.suggested_by, su.username as suggested_by_username,
+         (SELECT COUNT(*) FROM votes v WHERE v.option_id = o.id) as vote_count,
+         (SELECT COALESCE(SUM(u.vote_weight), 0) FROM votes v JOIN users u ON v.user_id = u.id
+          WHERE v.option_id = o.id) as weighted_votes
          FROM options o
+         LEFT JOIN users su ON o.suggested_by = su.id
          WHERE o.poll_id = ?
+           AND ((o.date_time IS NOT NULL
+                 AND (? IS NULL OR julianday(o.date_time) >= julianday(?))
+                 AND (? IS NULL OR julianday(o.date_time) <= julianday(?)))
+                OR (? AND o.date_time IS NULL))
          ORDER BY o.id",
     )
     .bind(poll_id)
+    .bind(from)
+    .bind(from)
+    .bind(to)
+    .bind(to)
+    .bind(include_text)
     .fetch_all(pool)
     .await
 }
 
+/// Pairs polls with their vote and voter totals.
+///
+/// The totals for every listed poll come from one grouped query rather
+/// than a query per poll.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `polls` - The polls to annotate, e.g. from [`get_active_polls`]
+///
+/// # Returns
+/// * `Ok(Vec<DashboardPoll>)` - The polls in the same order, with their totals
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn with_vote_totals(
+    pool: &SqlitePool,
+    polls: Vec<PollWithCreator>,
+) -> Result<Vec<DashboardPoll>, sqlx::Error> {
+    let totals: HashMap<i64, (i64, i64)> = sqlx::query_as::<_, (i64, i64, i64)>(
+        "SELECT o.poll_id, COUNT(v.id), COUNT(DISTINCT v.user_id)
+         FROM options o
+         JOIN polls p ON o.poll_id = p.id
+         JOIN votes v ON v.option_id = o.id
+         WHERE p.archived = 0
+         GROUP BY o.poll_id",
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|(poll_id, votes, voters)| (poll_id, (votes, voters)))
+    .collect();
+
+    Ok(polls
+        .into_iter()
+        .map(|poll| {
+            let (total_votes, voter_count) = totals.get(&poll.id).copied().unwrap_or((0, 0));
+            DashboardPoll {
+                poll,
+                total_votes,
+                voter_count,
+            }
+        })
+        .collect())
+}
+
 /// Retrieves all option IDs that a specific user has voted for in a poll.
 ///
 /// This function is used to determine which options a user has already
@@ -156,571 +721,4351 @@ pub async fn get_user_votes(
     Ok(rows.iter().map(|row| row.get::<i64, _>(0)).collect())
 }
 
-/// Creates a new poll with options in the database.
+/// Retrieves the date options a user has voted for across open polls.
 ///
-/// This function handles the complete poll creation process:
-/// 1. Parses and validates the expiration date
-/// 2. Creates the poll record in a transaction
-/// 3. Parses comma-separated options
-/// 4. Detects and handles date/time options
-/// 5. Inserts all options for the poll
+/// Only options with a date/time on polls that have not yet expired are
+/// included, ordered chronologically, so the result acts as the user's
+/// calendar of upcoming game nights.
 ///
 /// # Arguments
 /// * `pool` - Database connection pool
-/// * `form` - New poll form data containing title, description, expiration, and options
-/// * `user_id` - ID of the user creating the poll
+/// * `user_id` - ID of the user
 ///
 /// # Returns
-/// * `Ok(i64)` - The ID of the newly created poll
-/// * `Err(sqlx::Error)` - Database error or invalid date format
-///
-/// # Date Format
-/// Expiration dates should be in format: YYYY-MM-DDTHH:MM
-/// Options can include dates in the same format for date-based voting
-pub async fn create_poll(
+/// * `Ok(Vec<CalendarEntry>)` - Voted date options sorted by date/time
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_user_calendar(
     pool: &SqlitePool,
-    form: &NewPollForm,
     user_id: i64,
-) -> Result<i64, sqlx::Error> {
-    let mut tx = pool.begin().await?;
-
-    // Parse expiration date
-    let expires_at =
-        match chrono::DateTime::parse_from_rfc3339(&format!("{}:00Z", &form.expires_at)) {
-            Ok(dt) => dt.with_timezone(&Utc),
-            Err(_) => {
-                error!("Invalid date format: {}", form.expires_at);
-                return Err(sqlx::Error::ColumnDecode {
-                    index: "".to_string(),
-                    source: Box::new(std::io::Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        "Invalid date format",
-                    )),
-                });
-            }
-        };
-
-    // Insert poll
-    let poll_id = sqlx::query(
-        "INSERT INTO polls (title, description, creator_id, expires_at) VALUES (?, ?, ?, ?)",
+) -> Result<Vec<CalendarEntry>, sqlx::Error> {
+    sqlx::query_as::<_, CalendarEntry>(
+        "SELECT p.id as poll_id, p.title as poll_title, o.id as option_id, o.text, o.date_time
+         FROM votes v
+         JOIN options o ON v.option_id = o.id
+         JOIN polls p ON o.poll_id = p.id
+         WHERE v.user_id = ? AND o.is_date = 1 AND o.date_time IS NOT NULL
+           AND julianday(p.expires_at) > julianday(?)
+         ORDER BY o.date_time ASC",
     )
-    .bind(&form.title)
-    .bind(&form.description)
     .bind(user_id)
-    .bind(expires_at)
-    .execute(&mut *tx)
-    .await?
-    .last_insert_rowid();
-
-    // Parse and insert options
-    let options = form
-        .options
-        .split(',')
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<&str>>();
+    .bind(Utc::now())
+    .fetch_all(pool)
+    .await
+}
 
-    for option in options {
-        // Check if the option is a date/time
-        let is_date = option.contains("T") && option.len() >= 16;
-        let date_time = if is_date {
-            match chrono::DateTime::parse_from_rfc3339(&format!("{}:00Z", option)) {
-                Ok(dt) => Some(dt.with_timezone(&Utc)),
-                Err(_) => None,
-            }
-        } else {
-            None
-        };
+/// Determines whether a user may see a poll at all.
+///
+/// Published polls are visible to everyone. Polls waiting for approval are
+/// visible only to their creator and admins.
+///
+/// # Arguments
+/// * `poll` - The poll being viewed
+/// * `user` - The user requesting access
+pub fn can_view_poll(poll: &PollWithCreator, user: &User) -> bool {
+    poll.published || user.is_admin || poll.creator_id == user.id
+}
 
-        sqlx::query("INSERT INTO options (poll_id, text, is_date, date_time) VALUES (?, ?, ?, ?)")
-            .bind(poll_id)
-            .bind(option)
-            .bind(is_date)
-            .bind(date_time)
-            .execute(&mut *tx)
-            .await?;
+/// Determines whether a user may see who voted on a poll.
+///
+/// Poll creators and admins always have access. When the poll has
+/// `open_voters` set, any user who has cast at least one vote on it may
+/// also see the voters list.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll` - The poll being viewed
+/// * `user` - The user requesting access
+///
+/// # Returns
+/// * `Ok(bool)` - Whether access is allowed
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn can_view_voters(
+    pool: &SqlitePool,
+    poll: &PollWithCreator,
+    user: &User,
+) -> Result<bool, sqlx::Error> {
+    if user.is_admin || poll.creator_id == user.id {
+        return Ok(true);
     }
 
-    tx.commit().await?;
+    if !poll.open_voters {
+        return Ok(false);
+    }
 
-    info!("New poll created with ID: {}", poll_id);
-    Ok(poll_id)
+    Ok(!get_user_votes(pool, poll.id, user.id).await?.is_empty())
 }
 
-/// Add new options to an existing poll
-pub async fn add_poll_options(
-    pool: &SqlitePool,
-    poll_id: i64,
-    form: &NewOptionsForm,
-) -> Result<i64, sqlx::Error> {
-    let mut tx = pool.begin().await?;
-    let options = form
-        .options
-        .split(',')
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<&str>>();
-
-    for option in options {
-        let is_date = option.contains("T") && option.len() >= 16;
-        let date_time = if is_date {
-            match chrono::DateTime::parse_from_rfc3339(&format!("{}:00Z", option)) {
-                Ok(dt) => Some(dt.with_timezone(&Utc)),
-                Err(_) => None,
-            }
-        } else {
-            None
-        };
-        sqlx::query("INSERT INTO options (poll_id, text, is_date, date_time) VALUES(?, ?, ?, ?)")
-            .bind(poll_id)
-            .bind(option)
-            .bind(is_date)
-            .bind(date_time)
-            .execute(&mut *tx)
-            .await?;
+/// Whether a new poll by this user is published right away.
+///
+/// When `REQUIRE_POLL_APPROVAL` is set, polls by anyone but an admin wait
+/// in the moderation queue until an admin approves them.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `user_id` - ID of the user creating the poll
+///
+/// # Returns
+/// * `Ok(bool)` - Whether the poll should be created published
+/// * `Err(sqlx::Error)` - Database error if the user lookup fails
+async fn publishes_immediately(pool: &SqlitePool, user_id: i64) -> Result<bool, sqlx::Error> {
+    if !poll_approval_required() {
+        return Ok(true);
     }
-    tx.commit().await?;
 
-    info!("Added new options to poll {}", poll_id);
-    Ok(poll_id)
+    user_is_admin(pool, user_id).await
 }
 
-/// Remove a specific option from a poll (creator/admin only)
+/// Looks up whether a user is an admin.
 ///
-/// This function removes a poll option and all associated votes.
-/// Only the poll creator or admin users can remove options.
+/// # Returns
+/// * `Ok(bool)` - Whether the user is an admin
+/// * `Err(sqlx::Error)` - Database error, or RowNotFound if the user doesn't exist
+async fn user_is_admin(pool: &SqlitePool, user_id: i64) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar("SELECT is_admin FROM users WHERE id = ?")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+}
+
+/// Finds an open poll by `creator_id` whose title matches `title`,
+/// ignoring case and surrounding whitespace.
+///
+/// Only the creator's own polls that haven't expired or been archived are
+/// considered, so reusing another organizer's title never matches.
+///
+/// # Returns
+/// * `Ok(Some((id, title)))` - The first matching poll
+/// * `Ok(None)` - No open poll by the creator has this title
+pub async fn find_duplicate_poll(
+    pool: &SqlitePool,
+    creator_id: i64,
+    title: &str,
+) -> Result<Option<(i64, String)>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT id, title FROM polls
+         WHERE creator_id = ? AND LOWER(TRIM(title)) = LOWER(TRIM(?))
+           AND julianday(expires_at) > julianday(?) AND archived = 0
+         ORDER BY created_at DESC
+         LIMIT 1",
+    )
+    .bind(creator_id)
+    .bind(title)
+    .bind(Utc::now())
+    .fetch_optional(pool)
+    .await
+}
+
+/// Creates a new poll with options in the database.
+///
+/// This function handles the complete poll creation process:
+/// 1. Parses and validates the expiration date
+/// 2. Creates the poll record in a transaction
+/// 3. Parses options, separated by commas or (with `options_separator`) newlines
+/// 4. Detects and handles date/time options
+/// 5. Prepends the options of the `copy_options_from` poll, if given, dropping
+///    typed options that repeat one of them (case-insensitively)
+/// 6. Checks the options match the poll kind (`text` or `dates` polls reject mixing)
+/// 7. Unless `confirm_duplicate` is set, refuses a title matching one of the
+///    creator's open polls (case-insensitively)
+/// 8. Inserts all options for the poll, leaving the poll unpublished if it
+///    needs approval (see [`publishes_immediately`])
 ///
 /// # Arguments
 /// * `pool` - Database connection pool
-/// * `poll_id` - ID of the poll containing the option
-/// * `option_id` - ID of the option to remove
-/// * `user_id` - ID of the user requesting removal
-/// * `is_admin` - Whether the requesting user is an admin
+/// * `form` - New poll form data containing title, description, expiration, and options
+/// * `user_id` - ID of the user creating the poll
 ///
 /// # Returns
-/// * `Ok(())` - Option removed successfully
-/// * `Err(sqlx::Error)` - Database error or permission denied (RowNotFound)
-pub async fn remove_poll_option(
+/// * `Ok(i64)` - The ID of the newly created poll
+/// * `Err(sqlx::Error)` - Database error or invalid date format
+///
+/// # Date Format
+/// Expiration dates should be in format: YYYY-MM-DDTHH:MM
+/// Options can include dates in the same format for date-based voting
+pub async fn create_poll(
     pool: &SqlitePool,
-    poll_id: i64,
-    option_id: i64,
+    form: &NewPollForm,
     user_id: i64,
-    is_admin: bool,
-) -> Result<(), sqlx::Error> {
-    // First check if the poll exists and user has permission
-    let poll = sqlx::query_as::<_, crate::models::Poll>(
-        "SELECT id, title, description, creator_id, created_at, expires_at FROM polls WHERE id = ?"
-    )
-    .bind(poll_id)
-    .fetch_optional(pool)
-    .await?;
+) -> Result<i64, sqlx::Error> {
+    if !settings::poll_creation_enabled(pool).await? {
+        return Err(invalid_input("Poll creation is currently disabled by an administrator"));
+    }
 
-    match poll {
-        Some(poll) => {
-            // Check if user has permission (creator or admin)
-            if !is_admin && poll.creator_id != user_id {
-                return Err(sqlx::Error::RowNotFound);
+    let mut tx = pool.begin().await?;
+
+    // Parse expiration date
+    let expires_at = match parse_form_datetime(&form.expires_at) {
+        Some(dt) => dt,
+        None => {
+            error!("Invalid date format: {}", form.expires_at);
+            return Err(invalid_input("Invalid date format"));
+        }
+    };
+
+    // Parse the optional vote lock date, which must not fall after expiration
+    let votes_lock_at = match form.votes_lock_at.as_deref().map(str::trim) {
+        Some(value) if !value.is_empty() => match parse_form_datetime(value) {
+            Some(dt) if dt <= expires_at => Some(dt),
+            Some(_) => {
+                return Err(invalid_input(
+                    "Vote lock time must not be later than the expiration time",
+                ));
             }
-            
-            // Check if poll is expired
-            if poll.expires_at <= Utc::now() {
-                return Err(sqlx::Error::ColumnDecode {
-                    index: "expired".to_string(),
-                    source: Box::new(std::io::Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        "Cannot modify expired poll",
-                    )),
-                });
+            None => {
+                error!("Invalid vote lock date format: {}", value);
+                return Err(invalid_input("Invalid vote lock date format"));
+            }
+        },
+        _ => None,
+    };
+
+    check_quorum(form.quorum, form.auto_close_at_quorum)?;
+
+    // Reject blocked content, naming the offending field
+    if content_filter::contains_blocked(&form.title) {
+        return Err(invalid_input("Title contains blocked words"));
+    }
+    if form.description.as_deref().is_some_and(content_filter::contains_blocked) {
+        return Err(invalid_input("Description contains blocked words"));
+    }
+
+    let poll_kind = match form.poll_kind.as_deref().map(str::trim) {
+        None | Some("") => "mixed",
+        Some(kind) => match POLL_KINDS.iter().find(|known| **known == kind) {
+            Some(known) => known,
+            None => return Err(invalid_input(&format!("Unknown poll kind \"{}\"", kind))),
+        },
+    };
+
+    let separator = match form.options_separator.as_deref().map(str::trim) {
+        None | Some("") | Some("comma") => ',',
+        Some("newline") => '\n',
+        Some(other) => {
+            return Err(invalid_input(&format!("Unknown options separator \"{}\"", other)));
+        }
+    };
+
+    let typed_options = parse_options(&form.options, separator)?;
+
+    // Start from an earlier poll's options when asked to. Only polls the
+    // creator can see may be copied; others are reported as not found, so
+    // polls waiting for approval can't be read this way.
+    let source_options = match form.copy_options_from {
+        Some(source_id) => {
+            let visible = match get_poll_by_id(pool, source_id).await {
+                Ok(source) => {
+                    source.published || source.creator_id == user_id || user_is_admin(pool, user_id).await?
+                }
+                Err(sqlx::Error::RowNotFound) => false,
+                Err(err) => return Err(err),
+            };
+            if !visible {
+                return Err(invalid_input("The poll to copy options from was not found"));
             }
+            get_poll_options(pool, source_id).await?
         }
-        None => {
-            return Err(sqlx::Error::RowNotFound);
+        None => Vec::new(),
+    };
+
+    let mut options: Vec<ParsedOption<'_>> = source_options
+        .iter()
+        .map(|option| ParsedOption {
+            text: &option.text,
+            is_date: option.is_date,
+            date_time: option.date_time,
+            image_url: option.image_url.as_deref(),
+            max_votes: option.max_votes,
+        })
+        .collect();
+
+    // Typed options that repeat a copied one are dropped
+    for option in typed_options {
+        let duplicate = source_options
+            .iter()
+            .any(|copied| copied.text.eq_ignore_ascii_case(option.text));
+        if !duplicate {
+            options.push(option);
         }
     }
 
-    // Verify the option belongs to this poll
-    let option = sqlx::query("SELECT id FROM options WHERE id = ? AND poll_id = ?")
-        .bind(option_id)
-        .bind(poll_id)
-        .fetch_optional(pool)
-        .await?;
+    check_poll_kind(poll_kind, &options)?;
 
-    if option.is_none() {
-        return Err(sqlx::Error::RowNotFound);
+    if options.len() as i64 > config::get().max_options_per_poll {
+        return Err(invalid_input(&format!(
+            "A poll can have at most {} options ({} were given)",
+            config::get().max_options_per_poll,
+            options.len()
+        )));
     }
 
-    let mut tx = pool.begin().await?;
+    if !form.confirm_duplicate {
+        if let Some((_, title)) = find_duplicate_poll(pool, user_id, &form.title).await? {
+            return Err(duplicate_poll(&title));
+        }
+    }
 
-    // Delete all votes for this option
-    sqlx::query("DELETE FROM votes WHERE option_id = ?")
-        .bind(option_id)
-        .execute(&mut *tx)
-        .await?;
+    let published = publishes_immediately(pool, user_id).await?;
 
-    // Delete the option
-    sqlx::query("DELETE FROM options WHERE id = ?")
-        .bind(option_id)
-        .execute(&mut *tx)
-        .await?;
+    // Insert poll
+    let poll_id = sqlx::query(
+        "INSERT INTO polls (title, description, creator_id, expires_at, votes_lock_at, quorum,
+                            open_voters, require_vote_comment, auto_close_at_quorum, poll_kind,
+                            creator_can_vote, multi_select, allow_write_in, published)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&form.title)
+    .bind(&form.description)
+    .bind(user_id)
+    .bind(expires_at)
+    .bind(votes_lock_at)
+    .bind(form.quorum)
+    .bind(form.open_voters)
+    .bind(form.require_vote_comment)
+    .bind(form.auto_close_at_quorum)
+    .bind(poll_kind)
+    .bind(form.creator_can_vote.unwrap_or(true))
+    .bind(form.multi_select.unwrap_or(true))
+    .bind(form.allow_write_in)
+    .bind(published)
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    // Insert options
+    for option in &options {
+        insert_option(&mut tx, poll_id, option).await?;
+    }
 
     tx.commit().await?;
 
-    info!("Option {} removed from poll {} by user {}", option_id, poll_id, user_id);
-    Ok(())
+    crate::db::increment_polls_created();
+    info!("New poll created with ID: {}", poll_id);
+    Ok(poll_id)
 }
 
-/// Handles voting on a poll option (toggle functionality).
+/// Current version of the poll template format. Imports of any other
+/// version are refused.
+pub const POLL_TEMPLATE_VERSION: u32 = 1;
+
+/// Builds a shareable template of a poll's structure.
 ///
-/// This function implements vote toggling - if the user has already
-/// voted for the option, it removes their vote. If they haven't
-/// voted for the option, it adds their vote.
+/// The template holds the title, description, options, and voting
+/// settings. Votes, comments, the creator, and the poll's times are left
+/// out.
 ///
-/// # Vote Logic
-/// - If user has already voted for this option: Remove the vote
-/// - If user has not voted for this option: Add the vote
-/// - Users can vote for multiple options in the same poll
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll to export
+///
+/// # Returns
+/// * `Ok(PollTemplate)` - The poll's template
+/// * `Err(sqlx::Error)` - Database error, or RowNotFound if the poll doesn't exist
+pub async fn get_poll_template(pool: &SqlitePool, poll_id: i64) -> Result<PollTemplate, sqlx::Error> {
+    let poll = get_poll_by_id(pool, poll_id).await?;
+    let options = get_poll_options(pool, poll_id).await?;
+
+    Ok(PollTemplate {
+        version: POLL_TEMPLATE_VERSION,
+        title: poll.title,
+        description: poll.description,
+        settings: PollTemplateSettings {
+            poll_kind: poll.poll_kind,
+            open_voters: poll.open_voters,
+            require_vote_comment: poll.require_vote_comment,
+            quorum: poll.quorum,
+            auto_close_at_quorum: poll.auto_close_at_quorum,
+            creator_can_vote: poll.creator_can_vote,
+            multi_select: poll.multi_select,
+            allow_write_in: poll.allow_write_in,
+        },
+        options: options
+            .into_iter()
+            .map(|option| PollTemplateOption {
+                text: option.text,
+                is_date: option.is_date,
+                date_time: option.date_time,
+                image_url: option.image_url,
+                max_votes: option.max_votes,
+            })
+            .collect(),
+    })
+}
+
+/// Creates a poll from a template exported by [`get_poll_template`],
+/// possibly on another instance.
+///
+/// The document's version is checked before anything else, so a template
+/// from a newer format gets a clear message. Unknown fields are rejected by
+/// name. The poll then goes through the same checks as one created from
+/// the form: the blocklist, option image URLs, the poll kind, the quorum,
+/// and the option limit. The expiration time must be in the future. The new poll belongs to the importer, starts
+/// with no votes, and needs approval like any other new poll.
 ///
 /// # Arguments
 /// * `pool` - Database connection pool
-/// * `option_id` - ID of the poll option to vote for/against
-/// * `user_id` - ID of the user casting the vote
+/// * `document` - The template JSON
+/// * `expires_at` - Expiration date/time for the new poll in format YYYY-MM-DDTHH:MM
+/// * `user_id` - ID of the user importing the poll
 ///
 /// # Returns
-/// * `Ok(())` - Vote operation completed successfully
-/// * `Err(sqlx::Error)` - Database error if operation fails
-pub async fn vote_on_poll(
+/// * `Ok(i64)` - The ID of the newly created poll
+/// * `Err(sqlx::Error)` - Database error, or invalid input describing what
+///   is wrong with the template
+pub async fn import_poll_template(
     pool: &SqlitePool,
-    option_id: i64,
+    document: &str,
+    expires_at: &str,
     user_id: i64,
-) -> Result<(), sqlx::Error> {
-    // Check if user has already voted for this option
-    let existing_vote = sqlx::query("SELECT id FROM votes WHERE user_id = ? AND option_id = ?")
-        .bind(user_id)
-        .bind(option_id)
-        .fetch_optional(pool)
-        .await?;
+) -> Result<i64, sqlx::Error> {
+    if !settings::poll_creation_enabled(pool).await? {
+        return Err(invalid_input("Poll creation is currently disabled by an administrator"));
+    }
 
-    if existing_vote.is_some() {
-        // User has already voted for this option, remove the vote
-        sqlx::query("DELETE FROM votes WHERE user_id = ? AND option_id = ?")
-            .bind(user_id)
-            .bind(option_id)
-            .execute(pool)
-            .await?;
+    let value: serde_json::Value = serde_json::from_str(document)
+        .map_err(|err| invalid_input(&format!("The template is not valid JSON ({})", err)))?;
+    match value.get("version").and_then(serde_json::Value::as_u64) {
+        Some(version) if version == POLL_TEMPLATE_VERSION as u64 => {}
+        Some(version) => {
+            return Err(invalid_input(&format!(
+                "Unsupported template version {} (this server reads version {})",
+                version, POLL_TEMPLATE_VERSION
+            )));
+        }
+        None => return Err(invalid_input("The template has no version number")),
+    }
+    let template: PollTemplate = serde_json::from_value(value)
+        .map_err(|err| invalid_input(&format!("Invalid poll template: {}", err)))?;
 
-        info!("User {} removed vote for option {}", user_id, option_id);
-    } else {
-        // User has not voted for this option, add the vote
-        sqlx::query("INSERT INTO votes (user_id, option_id) VALUES (?, ?)")
-            .bind(user_id)
-            .bind(option_id)
-            .execute(pool)
-            .await?;
+    let expires_at = match parse_form_datetime(expires_at) {
+        Some(dt) if dt > Utc::now() => dt,
+        Some(_) => return Err(invalid_input("Expiration time must be in the future")),
+        None => return Err(invalid_input("Invalid date format")),
+    };
 
-        info!("User {} voted for option {}", user_id, option_id);
+    let title = template.title.trim();
+    if title.is_empty() {
+        return Err(invalid_input("The template has no title"));
+    }
+    if content_filter::contains_blocked(title) {
+        return Err(invalid_input("Title contains blocked words"));
+    }
+    if template.description.as_deref().is_some_and(content_filter::contains_blocked) {
+        return Err(invalid_input("Description contains blocked words"));
     }
 
-    Ok(())
-}
+    let settings = &template.settings;
+    let poll_kind = POLL_KINDS
+        .iter()
+        .find(|known| **known == settings.poll_kind)
+        .ok_or_else(|| invalid_input(&format!("Unknown poll kind \"{}\"", settings.poll_kind)))?;
+    check_quorum(settings.quorum, settings.auto_close_at_quorum)?;
 
-// Get poll results
-// pub async fn get_poll_results(
-//     pool: &SqlitePool,
-//     poll_id: i64,
-// ) -> Result<Vec<(PollOption, i64)>, sqlx::Error> {
-//     let options = get_poll_options(pool, poll_id).await?;
+    let mut options = Vec::with_capacity(template.options.len());
+    for option in &template.options {
+        let text = option.text.trim();
+        if text.is_empty() {
+            return Err(invalid_input("The template has an option without text"));
+        }
+        check_option_content(text, option.image_url.as_deref())?;
+        if option.max_votes.is_some_and(|max| max <= 0) {
+            return Err(invalid_input(&format!(
+                "Option \"{}\" has an invalid vote cap (must be a whole number above 0)",
+                text
+            )));
+        }
+        if option.is_date && option.date_time.is_none() {
+            return Err(invalid_input(&format!("Date option \"{}\" has no date_time", text)));
+        }
 
-//     let mut results = Vec::new();
-//     for option in options {
-//         let count = sqlx::query_scalar("SELECT COUNT(*) FROM votes WHERE option_id = ?")
-//             .bind(option.id)
-//             .fetch_one(pool)
-//             .await?;
+        options.push(ParsedOption {
+            text,
+            is_date: option.is_date,
+            date_time: option.date_time.filter(|_| option.is_date),
+            image_url: option.image_url.as_deref(),
+            max_votes: option.max_votes,
+        });
+    }
 
-//         results.push((option, count));
-//     }
+    check_poll_kind(poll_kind, &options)?;
 
-//     Ok(results)
-// }
+    if options.len() as i64 > config::get().max_options_per_poll {
+        return Err(invalid_input(&format!(
+            "A poll can have at most {} options ({} were given)",
+            config::get().max_options_per_poll,
+            options.len()
+        )));
+    }
 
-/// Deletes a poll and all associated data (admin or creator only).
-///
-/// This function performs a cascading delete of a poll, removing:
-/// 1. All votes for the poll's options
-/// 2. All options for the poll
-/// 3. The poll itself
+    let published = publishes_immediately(pool, user_id).await?;
+
+    let mut tx = pool.begin().await?;
+
+    let poll_id = sqlx::query(
+        "INSERT INTO polls (title, description, creator_id, expires_at, quorum, open_voters,
+                            require_vote_comment, auto_close_at_quorum, poll_kind,
+                            creator_can_vote, multi_select, allow_write_in, published)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(title)
+    .bind(&template.description)
+    .bind(user_id)
+    .bind(expires_at)
+    .bind(settings.quorum)
+    .bind(settings.open_voters)
+    .bind(settings.require_vote_comment)
+    .bind(settings.auto_close_at_quorum)
+    .bind(poll_kind)
+    .bind(settings.creator_can_vote)
+    .bind(settings.multi_select)
+    .bind(settings.allow_write_in)
+    .bind(published)
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    for option in &options {
+        insert_option(&mut tx, poll_id, option).await?;
+    }
+
+    tx.commit().await?;
+
+    crate::db::increment_polls_created();
+    info!("Poll {} imported from a template by user_id={}", poll_id, user_id);
+    Ok(poll_id)
+}
+
+/// Creates a runoff poll from the highest-voted options of an existing poll.
 ///
-/// # Permission Checks
-/// - Admins can delete any poll
-/// - Regular users can only delete polls they created
-/// - Returns RowNotFound error if user lacks permission
+/// The new poll is owned by the requesting user, contains copies of the
+/// top `top_n` options (ties broken by original order), and starts with no
+/// votes. Only the source poll's creator or an admin may start a runoff.
+/// The runoff is published only if the source poll is and the user's new
+/// polls don't need approval (see [`publishes_immediately`]).
 ///
 /// # Arguments
 /// * `pool` - Database connection pool
-/// * `poll_id` - ID of the poll to delete
-/// * `user_id` - ID of the user requesting deletion
+/// * `poll_id` - ID of the source poll
+/// * `top_n` - Number of options to carry over (at least 2)
+/// * `new_expiry` - Expiration date/time for the runoff in format YYYY-MM-DDTHH:MM
+/// * `user_id` - ID of the user creating the runoff
 /// * `is_admin` - Whether the requesting user is an admin
 ///
 /// # Returns
-/// * `Ok(())` - Poll deleted successfully
-/// * `Err(sqlx::Error)` - Database error or permission denied (RowNotFound)
-pub async fn delete_poll(
+/// * `Ok(i64)` - The ID of the newly created runoff poll
+/// * `Err(sqlx::Error)` - Database error, poll not found (RowNotFound), permission
+///   denied ([`forbidden`]), or invalid input
+pub async fn create_runoff(
     pool: &SqlitePool,
     poll_id: i64,
+    top_n: i64,
+    new_expiry: &str,
     user_id: i64,
     is_admin: bool,
-) -> Result<(), sqlx::Error> {
-    // First check if user has permission to delete this poll
-    if !is_admin {
-        let poll = sqlx::query_as::<_, crate::models::Poll>(
-            "SELECT id, title, description, creator_id, created_at, expires_at FROM polls WHERE id = ?"
-        )
-        .bind(poll_id)
-        .fetch_optional(pool)
-        .await?;
+) -> Result<i64, sqlx::Error> {
+    if !settings::poll_creation_enabled(pool).await? {
+        return Err(invalid_input("Poll creation is currently disabled by an administrator"));
+    }
 
-        match poll {
-            Some(poll) if poll.creator_id != user_id => {
-                return Err(sqlx::Error::RowNotFound);
-            }
-            None => {
-                return Err(sqlx::Error::RowNotFound);
-            }
-            _ => {} // User is the creator, proceed with deletion
+    let source = get_poll_by_id(pool, poll_id).await?;
+    if !is_admin && source.creator_id != user_id {
+        return Err(refused(source.published));
+    }
+
+    let expires_at = match parse_form_datetime(new_expiry) {
+        Some(dt) if dt > Utc::now() => dt,
+        Some(_) => return Err(invalid_input("Runoff expiration time must be in the future")),
+        None => {
+            error!("Invalid date format: {}", new_expiry);
+            return Err(invalid_input("Invalid date format"));
         }
+    };
+
+    let mut options = get_poll_options(pool, poll_id).await?;
+    if top_n < 2 {
+        return Err(invalid_input("A runoff needs at least 2 options"));
+    }
+    if top_n as usize > options.len() {
+        return Err(invalid_input(&format!("The poll only has {} options", options.len())));
     }
 
-    let mut tx = pool.begin().await?;
+    // Highest-voted first; the sort is stable so ties keep their original order
+    options.sort_by_key(|option| std::cmp::Reverse(option.weighted_votes));
+    options.truncate(top_n as usize);
 
-    // Delete all votes for this poll's options
-    sqlx::query("DELETE FROM votes WHERE option_id IN (SELECT id FROM options WHERE poll_id = ?)")
-        .bind(poll_id)
-        .execute(&mut *tx)
-        .await?;
+    let published = source.published && publishes_immediately(pool, user_id).await?;
 
-    // Delete all options for this poll
-    sqlx::query("DELETE FROM options WHERE poll_id = ?")
-        .bind(poll_id)
-        .execute(&mut *tx)
-        .await?;
+    let mut tx = pool.begin().await?;
 
-    // Delete the poll itself
-    sqlx::query("DELETE FROM polls WHERE id = ?")
-        .bind(poll_id)
+    let runoff_id = sqlx::query(
+        "INSERT INTO polls (title, description, creator_id, expires_at, votes_lock_at, quorum,
+                            open_voters, require_vote_comment, auto_close_at_quorum, poll_kind,
+                            creator_can_vote, multi_select, allow_write_in, published)
+         VALUES (?, ?, ?, ?, NULL, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(format!("Runoff: {}", source.title))
+    .bind(format!("Runoff between the top {} options of \"{}\".", top_n, source.title))
+    .bind(user_id)
+    .bind(expires_at)
+    .bind(source.quorum)
+    .bind(source.open_voters)
+    .bind(source.require_vote_comment)
+    .bind(source.auto_close_at_quorum)
+    .bind(&source.poll_kind)
+    .bind(source.creator_can_vote)
+    .bind(source.multi_select)
+    .bind(source.allow_write_in)
+    .bind(published)
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    for option in &options {
+        sqlx::query(
+            "INSERT INTO options (poll_id, text, is_date, date_time, image_url, max_votes)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(runoff_id)
+        .bind(&option.text)
+        .bind(option.is_date)
+        .bind(option.date_time)
+        .bind(&option.image_url)
+        .bind(option.max_votes)
         .execute(&mut *tx)
         .await?;
+    }
 
     tx.commit().await?;
 
-    info!("Poll {} deleted by user {}", poll_id, user_id);
-    Ok(())
+    crate::db::increment_polls_created();
+    info!("Runoff poll {} created from poll {} by user {}", runoff_id, poll_id, user_id);
+    Ok(runoff_id)
 }
 
-/// Retrieves all users who voted for a specific poll option.
+/// Add new options to an existing poll
 ///
-/// This function returns the list of users who cast votes for
-/// a particular option, ordered by when they voted.
+/// New options must match the poll's kind, as on creation. Options can be
+/// added to an expired poll, which stays expired unless `also_extend` is set.
+/// With it, the poll's expiration moves to the form's `expires_at` in the
+/// same transaction as the inserts, so an expired poll reopens with its new
+/// options all at once.
 ///
 /// # Arguments
 /// * `pool` - Database connection pool
-/// * `option_id` - ID of the poll option to get voters for
+/// * `poll_id` - ID of the poll to add options to
+/// * `form` - The new options, and the new expiration if extending
+/// * `user_id` - ID of the user adding the options
 ///
 /// # Returns
-/// * `Ok(Vec<User>)` - Vector of users who voted for this option
-/// * `Err(sqlx::Error)` - Database error if query fails
-pub async fn get_voters_for_option(
+/// * `Ok(i64)` - ID of the poll
+/// * `Err(sqlx::Error)` - Database error, or ColumnDecode if the options or
+///   the new expiration are invalid
+pub async fn add_poll_options(
     pool: &SqlitePool,
-    option_id: i64,
-) -> Result<Vec<User>, sqlx::Error> {
-    sqlx::query_as::<_, User>(
-        "SELECT u.id, u.username, u.is_admin, u.created_at, u.password_hash
-         FROM users u
-         JOIN votes v ON u.id = v.user_id
-         WHERE v.option_id = ?
-         ORDER BY v.created_at ASC",
-    )
-    .bind(option_id)
-    .fetch_all(pool)
-    .await
+    poll_id: i64,
+    form: &NewOptionsForm,
+    user_id: i64,
+) -> Result<i64, sqlx::Error> {
+    let options = parse_options(&form.options, ',')?;
+
+    let poll_kind = sqlx::query_scalar::<_, String>("SELECT poll_kind FROM polls WHERE id = ?")
+        .bind(poll_id)
+        .fetch_one(pool)
+        .await?;
+    check_poll_kind(&poll_kind, &options)?;
+
+    let new_expires_at = if form.also_extend {
+        let expires_at = form.expires_at.as_deref().unwrap_or("").trim();
+        match parse_form_datetime(expires_at) {
+            Some(dt) if dt > Utc::now() => Some(dt),
+            Some(_) => return Err(invalid_input("New expiration time must be in the future")),
+            None if expires_at.is_empty() => {
+                return Err(invalid_input("Choose a new expiration time to extend the poll"));
+            }
+            None => {
+                error!("Invalid date format: {}", expires_at);
+                return Err(invalid_input("Invalid date format"));
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut tx = pool.begin().await?;
+    check_option_limit(&mut tx, poll_id, options.len()).await?;
+    for option in &options {
+        insert_option(&mut tx, poll_id, option).await?;
+    }
+    if let Some(expires_at) = new_expires_at {
+        sqlx::query("UPDATE polls SET expires_at = ? WHERE id = ?")
+            .bind(expires_at)
+            .bind(poll_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+
+    if let Some(expires_at) = new_expires_at {
+        audit::record(
+            pool,
+            Some(user_id),
+            "poll.extend",
+            &format!(
+                "Added {} option(s) to poll {} and set it to expire {}",
+                options.len(),
+                poll_id,
+                expires_at.to_rfc3339()
+            ),
+        )
+        .await;
+    }
+
+    info!("Added new options to poll {} by user {}", poll_id, user_id);
+    Ok(poll_id)
 }
 
-/// Retrieves all voters for a poll with their complete voting choices.
+/// Remove a specific option from a poll (creator/admin only)
 ///
-/// This function returns each unique voter along with all the option IDs
-/// they voted for in the specified poll. Used for detailed voter analysis.
+/// This function removes a poll option and all associated votes.
+/// Only the poll creator or admin users can remove options.
 ///
 /// # Arguments
 /// * `pool` - Database connection pool
-/// * `poll_id` - ID of the poll to get voters for
+/// * `poll_id` - ID of the poll containing the option
+/// * `option_id` - ID of the option to remove
+/// * `user_id` - ID of the user requesting removal
+/// * `is_admin` - Whether the requesting user is an admin
 ///
 /// # Returns
-/// * `Ok(Vec<(User, Vec<i64>)>)` - Vector of tuples containing each voter and their option IDs
-/// * `Err(sqlx::Error)` - Database error if query fails
-pub async fn get_poll_voters(
+/// * `Ok(())` - Option removed successfully
+/// * `Err(sqlx::Error)` - Database error, not found (RowNotFound), or permission
+///   denied ([`forbidden`])
+pub async fn remove_poll_option(
     pool: &SqlitePool,
     poll_id: i64,
-) -> Result<Vec<(User, Vec<i64>)>, sqlx::Error> {
-    // Get all users who voted in this poll
-    let voters = sqlx::query_as::<_, User>(
-        "SELECT DISTINCT u.id, u.username, u.is_admin, u.created_at, u.password_hash
-         FROM users u
-         JOIN votes v ON u.id = v.user_id
-         JOIN options o ON v.option_id = o.id
-         WHERE o.poll_id = ?
-         ORDER BY u.username",
+    option_id: i64,
+    user_id: i64,
+    is_admin: bool,
+) -> Result<(), sqlx::Error> {
+    // First check if the poll exists and user has permission
+    let poll = sqlx::query_as::<_, crate::models::Poll>(
+        "SELECT id, title, description, creator_id, created_at, expires_at, votes_lock_at, quorum, open_voters, require_vote_comment, auto_close_at_quorum, poll_kind, archived, creator_can_vote, multi_select, finalized, allow_write_in, published FROM polls WHERE id = ?"
     )
     .bind(poll_id)
-    .fetch_all(pool)
+    .fetch_optional(pool)
     .await?;
 
-    let mut result = Vec::new();
+    match poll {
+        Some(poll) => {
+            // Check if user has permission (creator or admin)
+            if !is_admin && poll.creator_id != user_id {
+                return Err(refused(poll.published));
+            }
+            
+            // Check if poll is expired
+            if poll.expires_at <= Utc::now() {
+                return Err(sqlx::Error::ColumnDecode {
+                    index: "expired".to_string(),
+                    source: Box::new(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "Cannot modify expired poll",
+                    )),
+                });
+            }
+        }
+        None => {
+            return Err(sqlx::Error::RowNotFound);
+        }
+    }
 
-    for voter in voters {
-        // Get all option IDs this user voted for in this poll
-        let voted_options = sqlx::query_scalar::<_, i64>(
-            "SELECT o.id
-             FROM votes v
-             JOIN options o ON v.option_id = o.id
-             WHERE v.user_id = ? AND o.poll_id = ?
-             ORDER BY o.id",
-        )
-        .bind(voter.id)
+    // Verify the option belongs to this poll
+    let option = sqlx::query("SELECT id FROM options WHERE id = ? AND poll_id = ?")
+        .bind(option_id)
         .bind(poll_id)
-        .fetch_all(pool)
+        .fetch_optional(pool)
         .await?;
 
-        result.push((voter, voted_options));
+    if option.is_none() {
+        return Err(sqlx::Error::RowNotFound);
     }
 
-    Ok(result)
+    let mut tx = pool.begin().await?;
+
+    // Delete all votes and comments for this option
+    sqlx::query("DELETE FROM votes WHERE option_id = ?")
+        .bind(option_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("DELETE FROM comments WHERE option_id = ?")
+        .bind(option_id)
+        .execute(&mut *tx)
+        .await?;
+
+    // Delete the option
+    sqlx::query("DELETE FROM options WHERE id = ?")
+        .bind(option_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    info!("Option {} removed from poll {} by user {}", option_id, poll_id, user_id);
+    Ok(())
 }
 
-/// Retrieves comprehensive voting details for a poll.
-///
-/// This function aggregates all voting information for a poll into
-/// a single structure containing the poll, all options with their voters,
-/// and summary statistics.
-///
-/// # Data Collected
-/// - Poll information with creator details
-/// - All options with individual vote details and voter information
-/// - Total vote count across all options
-/// - Count of unique voters who participated
+/// Checks whether a user has cast any vote in a poll.
 ///
 /// # Arguments
 /// * `pool` - Database connection pool
-/// * `poll_id` - ID of the poll to get detailed information for
+/// * `poll_id` - ID of the poll
+/// * `user_id` - ID of the user
 ///
 /// # Returns
-/// * `Ok(PollVotingDetails)` - Complete voting details structure
+/// * `Ok(bool)` - Whether the user has at least one vote in the poll
 /// * `Err(sqlx::Error)` - Database error if query fails
-pub async fn get_poll_voting_details(
+pub async fn has_voted_in_poll(
     pool: &SqlitePool,
     poll_id: i64,
-) -> Result<PollVotingDetails, sqlx::Error> {
-    // Get the poll
-    let poll = get_poll_by_id(pool, poll_id).await?;
-
-    // Get all options for this poll
-    let options = get_poll_options(pool, poll_id).await?;
-
-    let mut options_with_voters = Vec::new();
-    let mut total_votes = 0;
-    let mut all_voters = std::collections::HashSet::new();
+    user_id: i64,
+) -> Result<bool, sqlx::Error> {
+    let vote_count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*)
+         FROM votes v
+         JOIN options o ON v.option_id = o.id
+         WHERE o.poll_id = ? AND v.user_id = ?",
+    )
+    .bind(poll_id)
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
 
-    for option in options {
-        // Get votes for this option with user information
-        let votes_with_users = sqlx::query_as::<_, VoteWithUser>(
-            "SELECT v.id as vote_id, v.user_id, u.username, v.option_id, v.created_at
-             FROM votes v
-             JOIN users u ON v.user_id = u.id
-             WHERE v.option_id = ?
-             ORDER BY v.created_at ASC",
-        )
-        .bind(option.id)
-        .fetch_all(pool)
-        .await?;
+    Ok(vote_count > 0)
+}
 
-        total_votes += votes_with_users.len() as i64;
+/// Returns the text of the option a user voted for in a single-choice poll.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll
+/// * `user_id` - ID of the user, who must have voted in the poll
+///
+/// # Returns
+/// * `Ok(String)` - The voted option's text (the latest, should there be several)
+/// * `Err(sqlx::Error)` - Database error, or RowNotFound if the user hasn't voted
+async fn current_choice(pool: &SqlitePool, poll_id: i64, user_id: i64) -> Result<String, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT o.text
+         FROM votes v
+         JOIN options o ON v.option_id = o.id
+         WHERE o.poll_id = ? AND v.user_id = ?
+         ORDER BY v.created_at DESC, v.id DESC
+         LIMIT 1",
+    )
+    .bind(poll_id)
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+}
+
+/// Handles voting on a poll option (toggle functionality).
+///
+/// This function implements vote toggling - if the user has already
+/// voted for the option, it removes their vote. If they haven't
+/// voted for the option, it adds their vote.
+///
+/// # Vote Logic
+/// - If user has already voted for this option: Remove the vote and its comment
+/// - If user has not voted for this option: Add the vote with its comment
+/// - Users can vote for multiple options in the same poll
+/// - Polls with `require_vote_comment` set reject new votes without a comment
+/// - Polls with `auto_close_at_quorum` set close when a new voter brings them to quorum
+/// - Options with `max_votes` set reject new votes once full; unvoting is always allowed
+/// - Once the poll expires, its `votes_lock_at` passes, or it is finalized, votes can be
+///   neither cast nor removed
+/// - Polls with `creator_can_vote` unset reject votes and unvotes by their creator
+/// - Polls with `multi_select` unset are single-choice: a new vote replaces the
+///   user's other vote in the poll, and clicking the voted option removes it
+/// - The poll's creator is notified of votes cast with a comment, and when a
+///   new voter brings the poll to its quorum
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll the option belongs to
+/// * `option_id` - ID of the poll option to vote for/against
+/// * `user_id` - ID of the user casting the vote
+/// * `comment` - Optional comment explaining the vote
+///
+/// # Returns
+/// * `Ok(VoteOutcome)` - Which kind of change was made
+/// * `Err(sqlx::Error)` - Database error, RowNotFound if the option is not in the poll,
+///   or ColumnDecode if the comment is missing or invalid, the option is full,
+///   the poll is expired or locked, or the creator may not vote on it
+pub async fn vote_on_poll(
+    pool: &SqlitePool,
+    poll_id: i64,
+    option_id: i64,
+    user_id: i64,
+    comment: Option<&str>,
+) -> Result<VoteOutcome, sqlx::Error> {
+    // Make sure the option belongs to the poll being voted on
+    // Expiry and the vote lock are compared in SQL, the same way as the
+    // listings, so a poll shown as expired never accepts a vote
+    let now = Utc::now();
+    let poll_settings = sqlx::query(
+        "SELECT p.title, p.require_vote_comment, p.quorum, p.auto_close_at_quorum,
+                julianday(p.expires_at) <= julianday(?) AS expired,
+                COALESCE(julianday(p.votes_lock_at) <= julianday(?), 0) AS locked,
+                p.creator_id, p.creator_can_vote, p.multi_select, p.finalized,
+                p.published, o.text AS option_text
+         FROM options o
+         JOIN polls p ON o.poll_id = p.id
+         WHERE o.id = ? AND o.poll_id = ?",
+    )
+    .bind(now)
+    .bind(now)
+    .bind(option_id)
+    .bind(poll_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let poll_settings = match poll_settings {
+        Some(poll_settings) => poll_settings,
+        None => return Err(sqlx::Error::RowNotFound),
+    };
+    let title: String = poll_settings.try_get("title")?;
+    let require_comment: bool = poll_settings.try_get("require_vote_comment")?;
+    let quorum: Option<i64> = poll_settings.try_get("quorum")?;
+    let auto_close: bool = poll_settings.try_get("auto_close_at_quorum")?;
+    let expired: bool = poll_settings.try_get("expired")?;
+    let locked: bool = poll_settings.try_get("locked")?;
+    let creator_id: i64 = poll_settings.try_get("creator_id")?;
+    let creator_can_vote: bool = poll_settings.try_get("creator_can_vote")?;
+    let multi_select: bool = poll_settings.try_get("multi_select")?;
+    let finalized: bool = poll_settings.try_get("finalized")?;
+    let published: bool = poll_settings.try_get("published")?;
+    let option_text: String = poll_settings.try_get("option_text")?;
+
+    if !published {
+        return Err(invalid_input("This poll is waiting for approval and can't be voted on yet."));
+    }
+    if !creator_can_vote && creator_id == user_id {
+        return Err(invalid_input("The creator of this poll can't vote on it."));
+    }
+
+    if expired {
+        return Err(invalid_input("Cannot vote on expired poll."));
+    }
+    if finalized || locked {
+        return Err(invalid_input(
+            "Votes for this poll are locked in and can no longer be changed.",
+        ));
+    }
+
+    let comment = comment.map(str::trim).filter(|comment| !comment.is_empty());
 
-        // Track unique voters
-        for vote in &votes_with_users {
-            all_voters.insert(vote.user_id);
+    // Check if user has already voted for this option
+    let existing_vote = sqlx::query("SELECT id FROM votes WHERE user_id = ? AND option_id = ?")
+        .bind(user_id)
+        .bind(option_id)
+        .fetch_optional(pool)
+        .await?;
+
+    if existing_vote.is_some() {
+        // User has already voted for this option, remove the vote
+        sqlx::query("DELETE FROM votes WHERE user_id = ? AND option_id = ?")
+            .bind(user_id)
+            .bind(option_id)
+            .execute(pool)
+            .await?;
+
+        crate::db::increment_votes_removed();
+        info!("User {} removed vote for option {}", user_id, option_id);
+        Ok(VoteOutcome::Removed)
+    } else {
+        match comment {
+            None if require_comment => {
+                return Err(invalid_input("Please add a comment explaining your vote"));
+            }
+            Some(comment) if comment.chars().count() > MAX_VOTE_COMMENT_LEN => {
+                return Err(invalid_input(&format!(
+                    "Comments can be at most {} characters",
+                    MAX_VOTE_COMMENT_LEN
+                )));
+            }
+            Some(comment) if content_filter::contains_blocked(comment) => {
+                return Err(invalid_input("Comment contains blocked words"));
+            }
+            _ => {}
         }
 
-        let option_with_voters = OptionWithVoters {
-            id: option.id,
-            poll_id: option.poll_id,
-            text: option.text,
-            is_date: option.is_date,
-            date_time: option.date_time,
-            vote_count: votes_with_users.len() as i64,
-            voters: votes_with_users,
+        let outcome = if !has_voted_in_poll(pool, poll_id, user_id).await? {
+            VoteOutcome::FirstVote
+        } else if multi_select {
+            VoteOutcome::Added
+        } else {
+            VoteOutcome::Changed {
+                from: current_choice(pool, poll_id, user_id).await?,
+                to: option_text,
+            }
         };
 
-        options_with_voters.push(option_with_voters);
+        let mut tx = pool.begin().await?;
+
+        // Single-choice polls keep one vote per user, so clear the old one.
+        // It is restored by the rollback if the new option turns out full.
+        let replaced = if multi_select {
+            0
+        } else {
+            sqlx::query(
+                "DELETE FROM votes
+                 WHERE user_id = ? AND option_id IN (SELECT id FROM options WHERE poll_id = ?)",
+            )
+            .bind(user_id)
+            .bind(poll_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected()
+        };
+
+        // User has not voted for this option, add the vote unless the option
+        // is already at its cap. Checking the cap in the insert itself keeps
+        // concurrent votes from overfilling the option.
+        let inserted = sqlx::query(
+            "INSERT INTO votes (user_id, option_id, vote_comment)
+             SELECT ?, o.id, ? FROM options o
+             WHERE o.id = ?
+               AND (o.max_votes IS NULL
+                    OR (SELECT COUNT(*) FROM votes WHERE option_id = o.id) < o.max_votes)",
+        )
+        .bind(user_id)
+        .bind(comment)
+        .bind(option_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if inserted == 0 {
+            return Err(invalid_input("This option is full"));
+        }
+
+        tx.commit().await?;
+
+        for _ in 0..replaced {
+            crate::db::increment_votes_removed();
+        }
+        crate::db::increment_votes_cast();
+        info!("User {} voted for option {}", user_id, option_id);
+
+        if let Some(comment) = comment {
+            notify_creator(
+                pool,
+                creator_id,
+                user_id,
+                "vote_comment",
+                &format!("commented on their vote in \"{}\": {}", title, comment),
+                poll_id,
+            )
+            .await;
+        }
+
+        // Only a new voter can bring the poll to its quorum
+        if outcome == VoteOutcome::FirstVote {
+            if let Some(quorum) = quorum {
+                let closed = auto_close && close_at_quorum(pool, poll_id, quorum, user_id).await?;
+                notify_quorum_reached(pool, poll_id, &title, creator_id, quorum, closed).await;
+                if closed {
+                    return Ok(VoteOutcome::ClosedAtQuorum);
+                }
+            }
+        }
+
+        Ok(outcome)
     }
+}
 
-    Ok(PollVotingDetails {
-        poll,
-        options_with_voters,
-        total_votes,
-        total_voters: all_voters.len() as i64,
-    })
+/// Adds a voter's own option to a poll and votes for it.
+///
+/// The option and the vote are inserted in one transaction, so a write-in
+/// never exists without its suggester's vote. The same rules as
+/// [`vote_on_poll`] apply, except that no comment is needed since the
+/// suggestion speaks for itself.
+///
+/// # Write-in Rules
+/// - The poll must allow write-ins and be open for voting
+/// - The write-in must be a single option matching the poll's kind, without a vote cap
+/// - Options already in the poll are rejected, so voters vote for them instead
+/// - Each voter can add at most [`MAX_WRITE_INS_PER_USER`] write-ins per poll
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll
+/// * `text` - The suggested option, as typed
+/// * `user_id` - ID of the user suggesting the option
+///
+/// # Returns
+/// * `Ok(VoteOutcome)` - Which kind of change the vote made
+/// * `Err(sqlx::Error)` - Database error, RowNotFound if the poll doesn't exist,
+///   or ColumnDecode if write-ins are not allowed or the write-in is invalid
+pub async fn add_write_in(
+    pool: &SqlitePool,
+    poll_id: i64,
+    text: &str,
+    user_id: i64,
+) -> Result<VoteOutcome, sqlx::Error> {
+    let poll = get_poll_by_id(pool, poll_id).await?;
+
+    if !poll.published {
+        return Err(invalid_input("This poll is waiting for approval and can't be voted on yet."));
+    }
+    if !poll.allow_write_in {
+        return Err(invalid_input("This poll doesn't accept write-ins."));
+    }
+    if !poll.creator_can_vote && poll.creator_id == user_id {
+        return Err(invalid_input("The creator of this poll can't vote on it."));
+    }
+    let now = Utc::now();
+    if poll.expires_at <= now {
+        return Err(invalid_input("Cannot vote on expired poll."));
+    }
+    if poll.finalized || poll.votes_lock_at.is_some_and(|lock| lock <= now) {
+        return Err(invalid_input(
+            "Votes for this poll are locked in and can no longer be changed.",
+        ));
+    }
+
+    if text.trim().chars().count() > MAX_WRITE_IN_LEN {
+        return Err(invalid_input(&format!(
+            "Write-ins can be at most {} characters",
+            MAX_WRITE_IN_LEN
+        )));
+    }
+    let mut options = parse_options(text, '\n')?;
+    let mut option = match options.len() {
+        0 => return Err(invalid_input("Enter an option to suggest")),
+        1 => options.remove(0),
+        _ => return Err(invalid_input("Suggest one option at a time")),
+    };
+    check_poll_kind(&poll.poll_kind, std::slice::from_ref(&option))?;
+    // Capacity is the creator's call, not the suggester's
+    option.max_votes = None;
+
+    let outcome = if !has_voted_in_poll(pool, poll_id, user_id).await? {
+        VoteOutcome::FirstVote
+    } else if poll.multi_select {
+        VoteOutcome::Added
+    } else {
+        VoteOutcome::Changed {
+            from: current_choice(pool, poll_id, user_id).await?,
+            to: option.text.to_string(),
+        }
+    };
+
+    let mut tx = pool.begin().await?;
+
+    let duplicate: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM options
+         WHERE poll_id = ? AND (LOWER(text) = LOWER(?) OR (date_time IS NOT NULL AND date_time = ?))",
+    )
+    .bind(poll_id)
+    .bind(option.text)
+    .bind(option.date_time)
+    .fetch_one(&mut *tx)
+    .await?;
+    if duplicate > 0 {
+        return Err(invalid_input("That option is already in the poll. Vote for it instead."));
+    }
+
+    let write_ins: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM options WHERE poll_id = ? AND suggested_by = ?")
+            .bind(poll_id)
+            .bind(user_id)
+            .fetch_one(&mut *tx)
+            .await?;
+    if write_ins >= MAX_WRITE_INS_PER_USER {
+        return Err(invalid_input(&format!(
+            "You can suggest at most {} options per poll",
+            MAX_WRITE_INS_PER_USER
+        )));
+    }
+    check_option_limit(&mut tx, poll_id, 1).await?;
+
+    let option_id = sqlx::query(
+        "INSERT INTO options (poll_id, text, is_date, date_time, image_url, max_votes, suggested_by)
+         VALUES (?, ?, ?, ?, ?, NULL, ?)",
+    )
+    .bind(poll_id)
+    .bind(option.text)
+    .bind(option.is_date)
+    .bind(option.date_time)
+    .bind(option.image_url)
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| duplicate_option(e, option.text))?
+    .last_insert_rowid();
+
+    // Single-choice polls keep one vote per user, so the write-in replaces it
+    let replaced = if poll.multi_select {
+        0
+    } else {
+        sqlx::query(
+            "DELETE FROM votes
+             WHERE user_id = ? AND option_id IN (SELECT id FROM options WHERE poll_id = ?)",
+        )
+        .bind(user_id)
+        .bind(poll_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+    };
+
+    sqlx::query("INSERT INTO votes (user_id, option_id) VALUES (?, ?)")
+        .bind(user_id)
+        .bind(option_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    for _ in 0..replaced {
+        crate::db::increment_votes_removed();
+    }
+    crate::db::increment_votes_cast();
+    info!("User {} added write-in option {} to poll {}", user_id, option_id, poll_id);
+
+    notify_creator(
+        pool,
+        poll.creator_id,
+        user_id,
+        "write_in",
+        &format!("suggested \"{}\" in \"{}\"", option.text, poll.title),
+        poll_id,
+    )
+    .await;
+
+    if outcome == VoteOutcome::FirstVote {
+        if let Some(quorum) = poll.quorum {
+            let closed =
+                poll.auto_close_at_quorum && close_at_quorum(pool, poll_id, quorum, user_id).await?;
+            notify_quorum_reached(pool, poll_id, &poll.title, poll.creator_id, quorum, closed).await;
+            if closed {
+                return Ok(VoteOutcome::ClosedAtQuorum);
+            }
+        }
+    }
+
+    Ok(outcome)
 }
 
-/// Formats poll data into JSON structure for template rendering.
+/// Notifies a poll's creator of something another user did on their poll.
 ///
-/// This function converts poll and voting data into a JSON structure
-/// suitable for use in Tera templates, including vote counts, user voting
-/// status, and expiration information.
+/// Nothing is sent when the creator did it themselves. Notifications are a
+/// courtesy, so a failure is logged rather than failing the action.
 ///
-/// # Template Data Included
-/// - Poll basic information (title, description, creator, dates)
-/// - Expiration status (is_expired boolean)
-/// - All options with vote counts and user voting status
-/// - Total vote count across all options
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `creator_id` - ID of the poll's creator
+/// * `actor_id` - ID of the user who acted
+/// * `kind` - Notification kind (e.g. `vote_comment`)
+/// * `action` - What the user did, following their username (e.g. "commented on ...")
+/// * `poll_id` - ID of the poll, linked from the notification
+async fn notify_creator(
+    pool: &SqlitePool,
+    creator_id: i64,
+    actor_id: i64,
+    kind: &str,
+    action: &str,
+    poll_id: i64,
+) {
+    if creator_id == actor_id {
+        return;
+    }
+
+    let result = async {
+        let username: String = sqlx::query_scalar("SELECT username FROM users WHERE id = ?")
+            .bind(actor_id)
+            .fetch_one(pool)
+            .await?;
+        let link = format!("/polls/{}", poll_id);
+        notify::push(pool, creator_id, kind, &format!("{} {}", username, action), Some(&link)).await
+    }
+    .await;
+
+    if let Err(err) = result {
+        error!("Failed to notify user {} ({}): {}", creator_id, kind, err);
+    }
+}
+
+/// Notifies a poll's creator when its unique voter count has just reached
+/// the quorum.
+///
+/// Called after a new voter's vote, so the count only equals the quorum on
+/// the vote that reached it. A failure is logged rather than failing the vote.
 ///
 /// # Arguments
-/// * `poll` - Poll information with creator details
-/// * `options` - Array of poll options with vote counts
-/// * `user_votes` - Array of option IDs the current user has voted for
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll
+/// * `title` - Title of the poll
+/// * `creator_id` - ID of the poll's creator
+/// * `quorum` - Number of unique voters the poll needs
+/// * `closed` - Whether the poll was just closed for reaching its quorum
+async fn notify_quorum_reached(
+    pool: &SqlitePool,
+    poll_id: i64,
+    title: &str,
+    creator_id: i64,
+    quorum: i64,
+    closed: bool,
+) {
+    let result = async {
+        let total_voters: i64 = sqlx::query_scalar(
+            "SELECT COUNT(DISTINCT v.user_id)
+             FROM votes v
+             JOIN options o ON v.option_id = o.id
+             WHERE o.poll_id = ?",
+        )
+        .bind(poll_id)
+        .fetch_one(pool)
+        .await?;
+
+        if total_voters != quorum {
+            return Ok(());
+        }
+
+        let body = format!(
+            "Your poll \"{}\" reached its quorum of {} voter{}{}",
+            title,
+            quorum,
+            if quorum == 1 { "" } else { "s" },
+            if closed { " and is now closed" } else { "" }
+        );
+        let link = format!("/polls/{}", poll_id);
+        notify::push(pool, creator_id, "quorum", &body, Some(&link)).await
+    }
+    .await;
+
+    if let Err(err) = result {
+        error!("Failed to send quorum notification for poll {}: {}", poll_id, err);
+    }
+}
+
+/// Closes a poll if its unique voter count has reached the quorum.
+///
+/// The poll's expiry is moved to now. The update only applies to polls that
+/// are still open, so concurrent votes close the poll at most once.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll to check
+/// * `quorum` - Number of unique voters needed to close the poll
+/// * `user_id` - ID of the user whose vote triggered the check
 ///
 /// # Returns
-/// A JSON value containing all formatted poll data for template use
-pub fn format_poll_for_template(
-    poll: &PollWithCreator,
-    options: &[PollOption],
-    user_votes: &[i64],
-) -> serde_json::Value {
-    let options_json: Vec<serde_json::Value> = options
-        .iter()
-        .map(|option| {
-            let is_voted = user_votes.contains(&option.id);
+/// * `Ok(true)` - The poll reached its quorum and was closed by this call
+/// * `Ok(false)` - The quorum hasn't been reached, or the poll was already closed
+/// * `Err(sqlx::Error)` - Database error if a query fails
+async fn close_at_quorum(
+    pool: &SqlitePool,
+    poll_id: i64,
+    quorum: i64,
+    user_id: i64,
+) -> Result<bool, sqlx::Error> {
+    let total_voters: i64 = sqlx::query_scalar(
+        "SELECT COUNT(DISTINCT v.user_id)
+         FROM votes v
+         JOIN options o ON v.option_id = o.id
+         WHERE o.poll_id = ?",
+    )
+    .bind(poll_id)
+    .fetch_one(pool)
+    .await?;
 
-            serde_json::json!({
-                "id": option.id,
-                "text": option.text,
-                "is_date": option.is_date,
-                "date_time": option.date_time,
-                "vote_count": option.vote_count,
-                "is_voted": is_voted,
-            })
-        })
-        .collect();
+    if !quorum_status(Some(quorum), total_voters).0 {
+        return Ok(false);
+    }
 
-    let total_votes: i64 = options.iter().map(|o| o.vote_count).sum();
+    let now = Utc::now();
+    let closed = sqlx::query(
+        "UPDATE polls SET expires_at = ?
+         WHERE id = ? AND julianday(expires_at) > julianday(?)",
+    )
+    .bind(now)
+    .bind(poll_id)
+    .bind(now)
+    .execute(pool)
+    .await?
+    .rows_affected()
+        > 0;
 
-    serde_json::json!({
-        "id": poll.id,
-        "title": poll.title,
-        "description": poll.description,
-        "creator_id": poll.creator_id,
-        "creator_username": poll.creator_username,
-        "created_at": poll.created_at.to_rfc3339(),
-        "expires_at": poll.expires_at.to_rfc3339(),
-        "is_expired": poll.expires_at <= Utc::now(),
-        "options": options_json,
-        "total_votes": total_votes,
-    })
+    if closed {
+        audit::record(
+            pool,
+            Some(user_id),
+            "poll.auto_close",
+            &format!("Poll {} closed after reaching its quorum of {} voters", poll_id, quorum),
+        )
+        .await;
+
+        info!("Poll {} closed at quorum of {} voters", poll_id, quorum);
+    }
+
+    Ok(closed)
+}
+
+// Get poll results
+// pub async fn get_poll_results(
+//     pool: &SqlitePool,
+//     poll_id: i64,
+// ) -> Result<Vec<(PollOption, i64)>, sqlx::Error> {
+//     let options = get_poll_options(pool, poll_id).await?;
+
+//     let mut results = Vec::new();
+//     for option in options {
+//         let count = sqlx::query_scalar("SELECT COUNT(*) FROM votes WHERE option_id = ?")
+//             .bind(option.id)
+//             .fetch_one(pool)
+//             .await?;
+
+//         results.push((option, count));
+//     }
+
+//     Ok(results)
+// }
+
+/// Deletes a poll and all associated data (admin or creator only).
+///
+/// This function performs a cascading delete of a poll, removing:
+/// 1. All votes for the poll's options
+/// 2. All comments and the results snapshot for the poll
+/// 3. All options for the poll
+/// 4. The poll itself
+///
+/// A copy of the poll is kept in memory first, so the deletion can be
+/// undone with [`undo_delete_poll`] for a short time.
+///
+/// # Permission Checks
+/// - Admins can delete any poll
+/// - Regular users can only delete polls they created
+/// - Returns a [`forbidden`] error if user lacks permission
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll to delete
+/// * `user_id` - ID of the user requesting deletion
+/// * `is_admin` - Whether the requesting user is an admin
+///
+/// # Returns
+/// * `Ok(())` - Poll deleted successfully
+/// * `Err(sqlx::Error)` - Database error, not found (RowNotFound), or permission
+///   denied ([`forbidden`])
+pub async fn delete_poll(
+    pool: &SqlitePool,
+    poll_id: i64,
+    user_id: i64,
+    is_admin: bool,
+) -> Result<(), sqlx::Error> {
+    // First check if user has permission to delete this poll
+    if !is_admin {
+        let poll = sqlx::query_as::<_, crate::models::Poll>(
+            "SELECT id, title, description, creator_id, created_at, expires_at, votes_lock_at, quorum, open_voters, require_vote_comment, auto_close_at_quorum, poll_kind, archived, creator_can_vote, multi_select, finalized, allow_write_in, published FROM polls WHERE id = ?"
+        )
+        .bind(poll_id)
+        .fetch_optional(pool)
+        .await?;
+
+        match poll {
+            Some(poll) if poll.creator_id != user_id => {
+                return Err(refused(poll.published));
+            }
+            None => {
+                return Err(sqlx::Error::RowNotFound);
+            }
+            _ => {} // User is the creator, proceed with deletion
+        }
+    }
+
+    let mut tx = pool.begin().await?;
+
+    // Copy everything that is about to be deleted, for undo
+    let tombstone = tombstone::capture(&mut tx, poll_id, user_id).await?;
+
+    // Delete all votes for this poll's options
+    sqlx::query("DELETE FROM votes WHERE option_id IN (SELECT id FROM options WHERE poll_id = ?)")
+        .bind(poll_id)
+        .execute(&mut *tx)
+        .await?;
+
+    // Delete all comments on the poll and its options
+    sqlx::query("DELETE FROM comments WHERE poll_id = ?")
+        .bind(poll_id)
+        .execute(&mut *tx)
+        .await?;
+
+    // Delete the results snapshot, if the poll was finalized
+    sqlx::query("DELETE FROM poll_results_snapshots WHERE poll_id = ?")
+        .bind(poll_id)
+        .execute(&mut *tx)
+        .await?;
+
+    // Delete all options for this poll
+    sqlx::query("DELETE FROM options WHERE poll_id = ?")
+        .bind(poll_id)
+        .execute(&mut *tx)
+        .await?;
+
+    // Delete the poll itself; nothing to delete means it never existed
+    let creator_id: i64 = sqlx::query_scalar("DELETE FROM polls WHERE id = ? RETURNING creator_id")
+        .bind(poll_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    tx.commit().await?;
+    tombstone::store(poll_id, tombstone);
+
+    let deleter = if creator_id == user_id {
+        crate::db::PollDeleter::Creator
+    } else {
+        crate::db::PollDeleter::Admin
+    };
+    crate::db::increment_polls_deleted(deleter, 1);
+
+    info!("Poll {} deleted by user {}", poll_id, user_id);
+    Ok(())
+}
+
+/// Restores a recently deleted poll with all its options, votes, and comments.
+///
+/// Only possible for [`tombstone::UNDO_WINDOW_SECONDS`] after the deletion,
+/// and only once.
+///
+/// # Permission Checks
+/// - Admins can restore any recently deleted poll
+/// - Regular users can only restore polls they deleted
+/// - Returns a [`forbidden`] error if user lacks permission
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the deleted poll
+/// * `user_id` - ID of the user requesting the restore
+/// * `is_admin` - Whether the requesting user is an admin
+///
+/// # Returns
+/// * `Ok(())` - Poll restored
+/// * `Err(sqlx::Error)` - Database error, permission denied ([`forbidden`]),
+///   or ColumnDecode if the undo window has passed
+pub async fn undo_delete_poll(
+    pool: &SqlitePool,
+    poll_id: i64,
+    user_id: i64,
+    is_admin: bool,
+) -> Result<(), sqlx::Error> {
+    let expired = || {
+        invalid_input(&format!(
+            "This poll can no longer be restored. Deleted polls can only be restored for {} seconds.",
+            tombstone::UNDO_WINDOW_SECONDS
+        ))
+    };
+
+    let deleted = tombstone::take(poll_id).ok_or_else(expired)?;
+    if !is_admin && deleted.deleted_by != user_id {
+        tombstone::store(poll_id, deleted);
+        return Err(forbidden());
+    }
+
+    let restored = async {
+        let mut tx = pool.begin().await?;
+        tombstone::restore(&mut tx, &deleted).await?;
+        tx.commit().await
+    }
+    .await;
+
+    if let Err(err) = restored {
+        // Keep the tombstone so the undo can be retried within the window
+        tombstone::store(poll_id, deleted);
+        return Err(err);
+    }
+
+    info!("Poll {} restored by user {}", poll_id, user_id);
+    Ok(())
+}
+
+/// Reopens an expired poll by moving its expiration into the future.
+///
+/// Unlike a general extension, this only applies to polls that have already
+/// expired, so an active poll can't be reopened by accident. The expiry check
+/// and the update happen in a single statement so a concurrent change can't
+/// slip in between them.
+///
+/// # Permission Checks
+/// - Admins can reopen any poll
+/// - Regular users can only reopen polls they created
+/// - Returns a [`forbidden`] error if user lacks permission
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll to reopen
+/// * `expires_at` - New expiration date/time in format YYYY-MM-DDTHH:MM
+/// * `user_id` - ID of the user requesting the reopen
+/// * `is_admin` - Whether the requesting user is an admin
+///
+/// # Returns
+/// * `Ok(())` - Poll reopened successfully
+/// * `Err(sqlx::Error)` - Database error, poll not found (RowNotFound),
+///   permission denied ([`forbidden`]), invalid expiration, or the poll is
+///   still active
+pub async fn reopen_poll(
+    pool: &SqlitePool,
+    poll_id: i64,
+    expires_at: &str,
+    user_id: i64,
+    is_admin: bool,
+) -> Result<(), sqlx::Error> {
+    let poll = sqlx::query_as::<_, crate::models::Poll>(
+        "SELECT id, title, description, creator_id, created_at, expires_at, votes_lock_at, quorum, open_voters, require_vote_comment, auto_close_at_quorum, poll_kind, archived, creator_can_vote, multi_select, finalized, allow_write_in, published FROM polls WHERE id = ?"
+    )
+    .bind(poll_id)
+    .fetch_optional(pool)
+    .await?;
+
+    match poll {
+        Some(poll) if is_admin || poll.creator_id == user_id => {}
+        Some(poll) => return Err(refused(poll.published)),
+        None => return Err(sqlx::Error::RowNotFound),
+    }
+
+    let now = Utc::now();
+    let expires_at = match parse_form_datetime(expires_at) {
+        Some(dt) if dt > now => dt,
+        Some(_) => return Err(invalid_input("New expiration time must be in the future")),
+        None => {
+            error!("Invalid date format: {}", expires_at);
+            return Err(invalid_input("Invalid date format"));
+        }
+    };
+
+    let result = sqlx::query(
+        "UPDATE polls SET expires_at = ?
+         WHERE id = ? AND julianday(expires_at) <= julianday(?)",
+    )
+    .bind(expires_at)
+    .bind(poll_id)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(invalid_input("Poll is still active and cannot be reopened"));
+    }
+
+    audit::record(
+        pool,
+        Some(user_id),
+        "poll.reopen",
+        &format!("Reopened poll {} until {}", poll_id, expires_at.to_rfc3339()),
+    )
+    .await;
+
+    info!("Poll {} reopened by user {}", poll_id, user_id);
+    Ok(())
+}
+
+/// Closes an open poll now and announces its results to everyone who voted
+/// (creator/admin only).
+///
+/// The poll's expiration (and any later vote lock) is moved to now in a
+/// transaction that also collects the voters, so anyone who voted before
+/// the close hears about it. The announcements are sent in the background
+/// after the response (see [`announce_results`]), so a large poll doesn't
+/// hold up the request.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll to close
+/// * `user_id` - ID of the user closing the poll
+/// * `is_admin` - Whether the requesting user is an admin
+///
+/// # Returns
+/// * `Ok(usize)` - Number of voters who will be notified
+/// * `Err(sqlx::Error)` - Database error, poll not found (RowNotFound),
+///   permission denied ([`forbidden`]), or ColumnDecode if the poll has
+///   already closed
+pub async fn close_and_announce(
+    pool: &SqlitePool,
+    poll_id: i64,
+    user_id: i64,
+    is_admin: bool,
+) -> Result<usize, sqlx::Error> {
+    let poll = get_poll_by_id(pool, poll_id).await?;
+
+    if !is_admin && poll.creator_id != user_id {
+        return Err(refused(poll.published));
+    }
+
+    let now = Utc::now();
+    let mut tx = pool.begin().await?;
+
+    let closed = sqlx::query(
+        "UPDATE polls
+         SET expires_at = ?,
+             votes_lock_at = CASE WHEN julianday(votes_lock_at) > julianday(?) THEN ? ELSE votes_lock_at END
+         WHERE id = ? AND julianday(expires_at) > julianday(?)",
+    )
+    .bind(now)
+    .bind(now)
+    .bind(now)
+    .bind(poll_id)
+    .bind(now)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if closed == 0 {
+        return Err(invalid_input("This poll has already closed"));
+    }
+
+    let voter_ids: Vec<i64> = sqlx::query_scalar(
+        "SELECT DISTINCT v.user_id FROM votes v
+         JOIN options o ON v.option_id = o.id
+         WHERE o.poll_id = ?
+         ORDER BY v.user_id",
+    )
+    .bind(poll_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    audit::record(
+        pool,
+        Some(user_id),
+        "poll.close_announce",
+        &format!("Closed poll {} and announced the results to {} voter(s)", poll_id, voter_ids.len()),
+    )
+    .await;
+    info!("Poll {} closed by user {}", poll_id, user_id);
+
+    let count = voter_ids.len();
+    if count > 0 {
+        let pool = pool.clone();
+        tokio::spawn(async move { announce_results(&pool, poll_id, &voter_ids).await });
+    }
+
+    Ok(count)
+}
+
+/// Sends each voter an in-app notification with a closed poll's results.
+///
+/// The notification names the option with the most (weighted) votes, or
+/// the tied options. Failures are logged rather than returned, since the
+/// poll has already closed.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the closed poll
+/// * `voter_ids` - IDs of the users to notify
+async fn announce_results(pool: &SqlitePool, poll_id: i64, voter_ids: &[i64]) {
+    let details = match get_poll_voting_details(pool, poll_id).await {
+        Ok(details) => details,
+        Err(err) => {
+            error!("Failed to load results to announce for poll {}: {}", poll_id, err);
+            return;
+        }
+    };
+
+    let top = details
+        .options_with_voters
+        .iter()
+        .map(|option| option.weighted_votes)
+        .max()
+        .unwrap_or(0);
+    let leaders: Vec<String> = details
+        .options_with_voters
+        .iter()
+        .filter(|option| option.weighted_votes == top)
+        .map(|option| format!("\"{}\"", option.text))
+        .collect();
+    let votes = format!(
+        "{} {}vote{}",
+        top,
+        if details.total_weighted_votes != details.total_votes { "weighted " } else { "" },
+        if top == 1 { "" } else { "s" }
+    );
+    let result = match leaders.as_slice() {
+        [winner] => format!("{} won with {}", winner, votes),
+        _ => format!("{} tied with {} each", leaders.join(", "), votes),
+    };
+
+    let body = format!("Voting on \"{}\" has closed: {}", details.poll.title, result);
+    let link = format!("/polls/{}", poll_id);
+    for voter_id in voter_ids {
+        if let Err(err) = notify::push(pool, *voter_id, "results", &body, Some(&link)).await {
+            error!("Failed to announce results of poll {} to user {}: {}", poll_id, voter_id, err);
+        }
+    }
+}
+
+/// Archives or restores a poll (creator/admin only).
+///
+/// Archiving hides the poll from the active and expired listings without
+/// deleting anything; it stays reachable by direct link and on the archive
+/// page. Votes are unaffected.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll to archive or restore
+/// * `archived` - Whether to archive (true) or restore (false) the poll
+/// * `user_id` - ID of the user making the change
+/// * `is_admin` - Whether the requesting user is an admin
+///
+/// # Returns
+/// * `Ok(())` - Poll updated successfully
+/// * `Err(sqlx::Error)` - Database error, poll not found (RowNotFound), or
+///   permission denied ([`forbidden`])
+pub async fn set_poll_archived(
+    pool: &SqlitePool,
+    poll_id: i64,
+    archived: bool,
+    user_id: i64,
+    is_admin: bool,
+) -> Result<(), sqlx::Error> {
+    let poll = get_poll_by_id(pool, poll_id).await?;
+
+    if !is_admin && poll.creator_id != user_id {
+        return Err(refused(poll.published));
+    }
+
+    if poll.archived == archived {
+        return Ok(());
+    }
+
+    sqlx::query("UPDATE polls SET archived = ? WHERE id = ?")
+        .bind(archived)
+        .bind(poll_id)
+        .execute(pool)
+        .await?;
+
+    let (action, verb) = if archived {
+        ("poll.archive", "Archived")
+    } else {
+        ("poll.unarchive", "Restored")
+    };
+    audit::record(pool, Some(user_id), action, &format!("{} poll {}", verb, poll_id)).await;
+
+    info!("Poll {} archived={} by user {}", poll_id, archived, user_id);
+    Ok(())
+}
+
+/// Retrieves the polls waiting for approval, oldest first, for the
+/// moderation queue.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Vec<PollWithCreator>)` - Unpublished polls with creator information
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_pending_polls(pool: &SqlitePool) -> Result<Vec<PollWithCreator>, sqlx::Error> {
+    sqlx::query_as::<_, PollWithCreator>(
+        "SELECT p.id, p.title, p.description, p.creator_id, u.username as creator_username,
+         p.created_at, p.expires_at, p.votes_lock_at, p.quorum, p.open_voters, p.require_vote_comment,
+         p.auto_close_at_quorum, p.poll_kind, p.archived, p.creator_can_vote, p.multi_select, p.finalized,
+         p.allow_write_in, p.published
+         FROM polls p
+         JOIN users u ON p.creator_id = u.id
+         WHERE p.published = 0
+         ORDER BY p.created_at, p.id",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Approves a poll waiting in the moderation queue (admin only), so it
+/// appears in the listings and accepts votes.
+///
+/// The creator is notified. Approving a poll that is already published
+/// does nothing.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll to approve
+/// * `admin_id` - ID of the approving admin
+///
+/// # Returns
+/// * `Ok(())` - Poll published
+/// * `Err(sqlx::Error)` - Database error, or RowNotFound if the poll doesn't exist
+pub async fn approve_poll(
+    pool: &SqlitePool,
+    poll_id: i64,
+    admin_id: i64,
+) -> Result<(), sqlx::Error> {
+    let poll = get_poll_by_id(pool, poll_id).await?;
+
+    if poll.published {
+        return Ok(());
+    }
+
+    sqlx::query("UPDATE polls SET published = 1 WHERE id = ?")
+        .bind(poll_id)
+        .execute(pool)
+        .await?;
+
+    audit::record(pool, Some(admin_id), "poll.approve", &format!("Approved poll {}", poll_id)).await;
+
+    let body = format!("Your poll \"{}\" was approved and is now open for voting", poll.title);
+    let link = format!("/polls/{}", poll_id);
+    if let Err(err) = notify::push(pool, poll.creator_id, "approval", &body, Some(&link)).await {
+        error!("Failed to send approval notification for poll {}: {}", poll_id, err);
+    }
+
+    info!("Poll {} approved by admin {}", poll_id, admin_id);
+    Ok(())
+}
+
+/// Finalizes a poll, recording a snapshot of its results (creator/admin only).
+///
+/// The poll's [`PollVotingDetails`] are serialized to JSON and stored with
+/// the time of finalization, and the poll stops accepting votes as if it
+/// were locked. A poll can only be finalized once.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll to finalize
+/// * `user_id` - ID of the user finalizing the poll
+/// * `is_admin` - Whether the requesting user is an admin
+///
+/// # Returns
+/// * `Ok(())` - Poll finalized and snapshot stored
+/// * `Err(sqlx::Error)` - Database error, poll not found (RowNotFound), permission
+///   denied ([`forbidden`]), or ColumnDecode if the poll is already finalized
+pub async fn finalize_poll(
+    pool: &SqlitePool,
+    poll_id: i64,
+    user_id: i64,
+    is_admin: bool,
+) -> Result<(), sqlx::Error> {
+    let poll = get_poll_by_id(pool, poll_id).await?;
+
+    if !is_admin && poll.creator_id != user_id {
+        return Err(refused(poll.published));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    // Claim the poll first, so concurrent requests finalize it only once
+    let claimed = sqlx::query("UPDATE polls SET finalized = 1 WHERE id = ? AND finalized = 0")
+        .bind(poll_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    if claimed == 0 {
+        return Err(invalid_input("This poll has already been finalized."));
+    }
+
+    // Read through the transaction, so the snapshot sees the claim above
+    let details = load_voting_details(&mut tx, poll_id, None).await?;
+    let results = serde_json::to_string(&details).map_err(|err| {
+        error!("Failed to serialize results for poll {}: {}", poll_id, err);
+        invalid_input("Could not record the poll's results")
+    })?;
+
+    sqlx::query(
+        "INSERT INTO poll_results_snapshots (poll_id, results, finalized_by, created_at)
+         VALUES (?, ?, ?, ?)",
+    )
+    .bind(poll_id)
+    .bind(&results)
+    .bind(user_id)
+    .bind(Utc::now())
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    audit::record(
+        pool,
+        Some(user_id),
+        "poll.finalize",
+        &format!("Finalized poll {} with {} votes", poll_id, details.total_votes),
+    )
+    .await;
+
+    info!("Poll {} finalized by user {}", poll_id, user_id);
+    Ok(())
+}
+
+/// Retrieves the results snapshot recorded when a poll was finalized.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll
+///
+/// # Returns
+/// * `Ok(ResultsSnapshot)` - The stored snapshot
+/// * `Err(sqlx::Error)` - Database error, or RowNotFound if the poll hasn't been finalized
+pub async fn get_results_snapshot(
+    pool: &SqlitePool,
+    poll_id: i64,
+) -> Result<ResultsSnapshot, sqlx::Error> {
+    sqlx::query_as::<_, ResultsSnapshot>(
+        "SELECT poll_id, results, finalized_by, created_at
+         FROM poll_results_snapshots WHERE poll_id = ?",
+    )
+    .bind(poll_id)
+    .fetch_one(pool)
+    .await
+}
+
+/// Longest comment accepted, in characters.
+const MAX_COMMENT_CHARS: usize = 1000;
+
+/// Posts a comment on a poll, or on one of its options.
+///
+/// The poll's creator is notified unless they wrote the comment.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll being discussed
+/// * `option_id` - ID of the option the comment is about, or `None` for the whole poll
+/// * `user_id` - ID of the user posting the comment
+/// * `body` - Comment text
+///
+/// # Returns
+/// * `Ok(i64)` - ID of the new comment
+/// * `Err(sqlx::Error)` - Database error, RowNotFound if the poll doesn't exist
+///   or the option isn't part of it, or ColumnDecode if the text is empty,
+///   too long, or blocked by the content filter
+pub async fn add_comment(
+    pool: &SqlitePool,
+    poll_id: i64,
+    option_id: Option<i64>,
+    user_id: i64,
+    body: &str,
+) -> Result<i64, sqlx::Error> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Err(invalid_input("Comments can't be empty."));
+    }
+    if body.chars().count() > MAX_COMMENT_CHARS {
+        return Err(invalid_input(&format!(
+            "Comments can be at most {} characters.",
+            MAX_COMMENT_CHARS
+        )));
+    }
+    if content_filter::contains_blocked(body) {
+        return Err(invalid_input("Your comment contains blocked content."));
+    }
+
+    let poll = get_poll_by_id(pool, poll_id).await?;
+
+    // Make sure the option belongs to the poll being discussed
+    let option_text = match option_id {
+        Some(option_id) => Some(
+            sqlx::query_scalar::<_, String>("SELECT text FROM options WHERE id = ? AND poll_id = ?")
+                .bind(option_id)
+                .bind(poll_id)
+                .fetch_optional(pool)
+                .await?
+                .ok_or(sqlx::Error::RowNotFound)?,
+        ),
+        None => None,
+    };
+
+    let comment_id = sqlx::query(
+        "INSERT INTO comments (poll_id, option_id, user_id, body, created_at) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(poll_id)
+    .bind(option_id)
+    .bind(user_id)
+    .bind(body)
+    .bind(Utc::now())
+    .execute(pool)
+    .await?
+    .last_insert_rowid();
+
+    let action = match option_text {
+        Some(option_text) => format!("commented on \"{}\" in \"{}\"", option_text, poll.title),
+        None => format!("commented on \"{}\"", poll.title),
+    };
+    notify_creator(pool, poll.creator_id, user_id, "comment", &action, poll_id).await;
+
+    info!("User {} commented on poll {} (option {:?})", user_id, poll_id, option_id);
+    Ok(comment_id)
+}
+
+/// Retrieves every comment on a poll, including those on its options.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll
+///
+/// # Returns
+/// * `Ok(Vec<Comment>)` - Comments ordered from oldest to newest
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_poll_comments(pool: &SqlitePool, poll_id: i64) -> Result<Vec<Comment>, sqlx::Error> {
+    sqlx::query_as::<_, Comment>(
+        "SELECT c.id, c.poll_id, c.option_id, c.user_id, u.username, c.body, c.created_at
+         FROM comments c
+         JOIN users u ON c.user_id = u.id
+         WHERE c.poll_id = ?
+         ORDER BY c.created_at, c.id",
+    )
+    .bind(poll_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Adds comments to poll template data produced by [`format_poll_for_template`].
+///
+/// Option comments go under their option's `comments` key, and comments on
+/// the poll as a whole go under the poll's own `comments` key.
+///
+/// # Arguments
+/// * `poll_json` - Formatted poll data to extend
+/// * `comments` - The poll's comments, as returned by [`get_poll_comments`]
+pub fn attach_comments(poll_json: &mut serde_json::Value, comments: &[Comment]) {
+    let comment_json = |comment: &Comment| {
+        serde_json::json!({
+            "id": comment.id,
+            "username": comment.username,
+            "body": comment.body,
+            "created_at": comment.created_at.to_rfc3339(),
+        })
+    };
+
+    if let Some(options) = poll_json["options"].as_array_mut() {
+        for option in options {
+            let option_id = option["id"].as_i64();
+            option["comments"] = comments
+                .iter()
+                .filter(|comment| comment.option_id.is_some() && comment.option_id == option_id)
+                .map(comment_json)
+                .collect();
+        }
+    }
+
+    poll_json["comments"] = comments
+        .iter()
+        .filter(|comment| comment.option_id.is_none())
+        .map(comment_json)
+        .collect();
+}
+
+/// Retrieves all active users who have not voted in a poll.
+///
+/// Disabled accounts, including the placeholders left by deleted users,
+/// are skipped.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll
+///
+/// # Returns
+/// * `Ok(Vec<User>)` - Users without a vote in the poll, ordered by username
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_non_voters(pool: &SqlitePool, poll_id: i64) -> Result<Vec<User>, sqlx::Error> {
+    sqlx::query_as::<_, User>(
+        "SELECT u.id, u.username, u.password_hash, u.is_admin, u.created_at, u.session_epoch, u.last_login_at, u.vote_weight, u.disabled, u.has_onboarded
+         FROM users u
+         WHERE u.disabled = 0 AND NOT EXISTS (
+             SELECT 1 FROM votes v
+             JOIN options o ON v.option_id = o.id
+             WHERE v.user_id = u.id AND o.poll_id = ?
+         )
+         ORDER BY u.username",
+    )
+    .bind(poll_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Sends an in-app reminder to every user who hasn't voted in a poll (creator or admin only).
+///
+/// Each poll can be nudged at most once per `NUDGE_INTERVAL_HOURS`. When
+/// everyone has already voted nothing is sent and the throttle is untouched.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll to nudge voters for
+/// * `user_id` - ID of the user requesting the nudge
+/// * `is_admin` - Whether the requesting user is an admin
+///
+/// # Returns
+/// * `Ok(usize)` - Number of users nudged
+/// * `Err(sqlx::Error)` - Database error, poll not found (RowNotFound),
+///   permission denied ([`forbidden`]), or the poll is waiting for approval,
+///   closed, or was nudged too recently
+pub async fn nudge_non_voters(
+    pool: &SqlitePool,
+    poll_id: i64,
+    user_id: i64,
+    is_admin: bool,
+) -> Result<usize, sqlx::Error> {
+    let poll = get_poll_by_id(pool, poll_id).await?;
+
+    if !is_admin && poll.creator_id != user_id {
+        return Err(refused(poll.published));
+    }
+
+    // Nobody else can see the poll yet, so a reminder would lead nowhere
+    if !poll.published {
+        return Err(invalid_input("This poll is waiting for approval, so nobody can vote on it yet"));
+    }
+    let now = Utc::now();
+    if poll.expires_at <= now {
+        return Err(invalid_input("This poll has closed, so there is nobody left to nudge"));
+    }
+
+    let non_voters = get_non_voters(pool, poll_id).await?;
+    if non_voters.is_empty() {
+        return Ok(0);
+    }
+
+    // Claim the nudge atomically so concurrent requests can't both send one
+    let claimed = sqlx::query(
+        "UPDATE polls SET last_nudged_at = ?
+         WHERE id = ? AND (last_nudged_at IS NULL OR julianday(last_nudged_at) <= julianday(?))",
+    )
+    .bind(now)
+    .bind(poll_id)
+    .bind(now - chrono::Duration::hours(config::get().nudge_interval_hours))
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    if claimed == 0 {
+        return Err(invalid_input(&format!(
+            "Voters for this poll were already nudged in the last {} hours",
+            config::get().nudge_interval_hours
+        )));
+    }
+
+    let body = format!(
+        "{} is waiting for your vote on \"{}\"",
+        poll.creator_username, poll.title
+    );
+    let link = format!("/polls/{}", poll_id);
+    for voter in &non_voters {
+        notify::push(pool, voter.id, "nudge", &body, Some(&link)).await?;
+    }
+
+    audit::record(
+        pool,
+        Some(user_id),
+        "poll.nudge",
+        &format!("Nudged {} non-voters on poll {}", non_voters.len(), poll_id),
+    )
+    .await;
+
+    info!("User {} nudged {} non-voters on poll {}", user_id, non_voters.len(), poll_id);
+    Ok(non_voters.len())
+}
+
+/// Retrieves all users who voted for a specific poll option.
+///
+/// This function returns the list of users who cast votes for
+/// a particular option, ordered by when they voted.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `option_id` - ID of the poll option to get voters for
+///
+/// # Returns
+/// * `Ok(Vec<User>)` - Vector of users who voted for this option
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_voters_for_option(
+    pool: &SqlitePool,
+    option_id: i64,
+) -> Result<Vec<User>, sqlx::Error> {
+    sqlx::query_as::<_, User>(
+        "SELECT u.id, u.username, u.is_admin, u.created_at, u.password_hash, u.session_epoch, u.last_login_at, u.vote_weight, u.disabled, u.has_onboarded
+         FROM users u
+         JOIN votes v ON u.id = v.user_id
+         WHERE v.option_id = ?
+         ORDER BY v.created_at ASC",
+    )
+    .bind(option_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Retrieves all voters for a poll with their complete voting choices.
+///
+/// This function returns each unique voter along with all the option IDs
+/// they voted for in the specified poll. Used for detailed voter analysis.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll to get voters for
+///
+/// # Returns
+/// * `Ok(Vec<(User, Vec<i64>)>)` - Vector of tuples containing each voter and their option IDs
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_poll_voters(
+    pool: &SqlitePool,
+    poll_id: i64,
+) -> Result<Vec<(User, Vec<i64>)>, sqlx::Error> {
+    // Get all users who voted in this poll
+    let voters = sqlx::query_as::<_, User>(
+        "SELECT DISTINCT u.id, u.username, u.is_admin, u.created_at, u.password_hash, u.session_epoch, u.last_login_at, u.vote_weight, u.disabled, u.has_onboarded
+         FROM users u
+         JOIN votes v ON u.id = v.user_id
+         JOIN options o ON v.option_id = o.id
+         WHERE o.poll_id = ?
+         ORDER BY u.username",
+    )
+    .bind(poll_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut result = Vec::new();
+
+    for voter in voters {
+        // Get all option IDs this user voted for in this poll
+        let voted_options = sqlx::query_scalar::<_, i64>(
+            "SELECT o.id
+             FROM votes v
+             JOIN options o ON v.option_id = o.id
+             WHERE v.user_id = ? AND o.poll_id = ?
+             ORDER BY o.id",
+        )
+        .bind(voter.id)
+        .bind(poll_id)
+        .fetch_all(pool)
+        .await?;
+
+        result.push((voter, voted_options));
+    }
+
+    Ok(result)
+}
+
+/// Retrieves comprehensive voting details for a poll.
+///
+/// This function aggregates all voting information for a poll into
+/// a single structure containing the poll, all options with their voters,
+/// and summary statistics.
+///
+/// # Data Collected
+/// - Poll information with creator details
+/// - All options with individual vote details and voter information
+/// - Total vote count across all options, raw and weighted by vote weight
+/// - Count of unique voters who participated
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll to get detailed information for
+///
+/// # Returns
+/// * `Ok(PollVotingDetails)` - Complete voting details structure
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_poll_voting_details(
+    pool: &SqlitePool,
+    poll_id: i64,
+) -> Result<PollVotingDetails, sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+    load_voting_details(&mut conn, poll_id, None).await
+}
+
+/// Retrieves voting details for the voters page, summarized for large polls.
+///
+/// Polls with at most `VOTERS_SUMMARY_THRESHOLD` unique voters get the full
+/// detail of [`get_poll_voting_details`]. Larger polls keep the full counts
+/// but list only the first [`SUMMARY_VOTERS_PER_OPTION`] voters of each
+/// option, with `more_voters` saying how many were left out; the rest can
+/// be paged through with [`get_option_voters_page`].
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll to get voting information for
+///
+/// # Returns
+/// * `Ok(PollVotingDetails)` - Voting details, with `summarized` set when capped
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_poll_voters_view(
+    pool: &SqlitePool,
+    poll_id: i64,
+) -> Result<PollVotingDetails, sqlx::Error> {
+    let voters = count_poll_voters(pool, poll_id).await?;
+    let limit = (voters > config::get().voters_summary_threshold).then_some(SUMMARY_VOTERS_PER_OPTION);
+
+    let mut conn = pool.acquire().await?;
+    load_voting_details(&mut conn, poll_id, limit).await
+}
+
+/// Counts the unique users who have voted on a poll.
+async fn count_poll_voters<'e, E>(executor: E, poll_id: i64) -> Result<i64, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query_scalar(
+        "SELECT COUNT(DISTINCT v.user_id)
+         FROM votes v
+         JOIN options o ON v.option_id = o.id
+         WHERE o.poll_id = ?",
+    )
+    .bind(poll_id)
+    .fetch_one(executor)
+    .await
+}
+
+/// Loads a poll's voting details, listing at most `voters_per_option`
+/// voters for each option when given. Counts always cover every vote.
+///
+/// Every query runs on `conn`, so inside a transaction the details include
+/// its uncommitted changes.
+async fn load_voting_details(
+    conn: &mut SqliteConnection,
+    poll_id: i64,
+    voters_per_option: Option<i64>,
+) -> Result<PollVotingDetails, sqlx::Error> {
+    // Get the poll
+    let poll = get_poll_by_id(&mut *conn, poll_id).await?;
+
+    // Get all options for this poll
+    let options = get_poll_options(&mut *conn, poll_id).await?;
+
+    let mut options_with_voters = Vec::new();
+    let mut total_votes = 0;
+    let mut total_weighted_votes = 0;
+
+    for option in options {
+        // Get votes for this option with user information; a negative
+        // limit means no limit in SQLite
+        let votes_with_users = sqlx::query_as::<_, VoteWithUser>(
+            "SELECT v.id as vote_id, v.user_id, u.username, v.option_id, v.vote_comment, v.created_at,
+                    u.vote_weight
+             FROM votes v
+             JOIN users u ON v.user_id = u.id
+             WHERE v.option_id = ?
+             ORDER BY v.created_at ASC, v.id ASC
+             LIMIT ?",
+        )
+        .bind(option.id)
+        .bind(voters_per_option.unwrap_or(-1))
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let (vote_count, weighted_votes) = match voters_per_option {
+            None => (
+                votes_with_users.len() as i64,
+                votes_with_users.iter().map(|vote| vote.vote_weight).sum(),
+            ),
+            Some(_) => sqlx::query_as::<_, (i64, i64)>(
+                "SELECT COUNT(v.id), COALESCE(SUM(u.vote_weight), 0)
+                 FROM votes v
+                 JOIN users u ON v.user_id = u.id
+                 WHERE v.option_id = ?",
+            )
+            .bind(option.id)
+            .fetch_one(&mut *conn)
+            .await?,
+        };
+        total_votes += vote_count;
+        total_weighted_votes += weighted_votes;
+
+        let option_with_voters = OptionWithVoters {
+            id: option.id,
+            poll_id: option.poll_id,
+            text: option.text,
+            is_date: option.is_date,
+            date_time: option.date_time,
+            vote_count,
+            weighted_votes,
+            more_voters: vote_count - votes_with_users.len() as i64,
+            voters: votes_with_users,
+        };
+
+        options_with_voters.push(option_with_voters);
+    }
+
+    let total_voters = count_poll_voters(&mut *conn, poll_id).await?;
+    let (quorum_met, quorum_remaining) = quorum_status(poll.quorum, total_voters);
+
+    Ok(PollVotingDetails {
+        poll,
+        options_with_voters,
+        total_votes,
+        total_weighted_votes,
+        total_voters,
+        quorum_met,
+        quorum_remaining,
+        summarized: voters_per_option.is_some(),
+    })
+}
+
+/// Retrieves one page of the voters for a poll option, oldest vote first.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll the option belongs to
+/// * `option_id` - ID of the option
+/// * `page` - Page number, starting at 1
+///
+/// # Returns
+/// * `Ok(OptionVotersPage)` - Up to [`OPTION_VOTERS_PAGE_SIZE`] voters and the total
+/// * `Err(sqlx::Error::RowNotFound)` - If the option isn't part of the poll
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_option_voters_page(
+    pool: &SqlitePool,
+    poll_id: i64,
+    option_id: i64,
+    page: i64,
+) -> Result<OptionVotersPage, sqlx::Error> {
+    let page = page.max(1);
+
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(v.id)
+         FROM options o
+         LEFT JOIN votes v ON v.option_id = o.id
+         WHERE o.id = ? AND o.poll_id = ?
+         GROUP BY o.id",
+    )
+    .bind(option_id)
+    .bind(poll_id)
+    .fetch_one(pool)
+    .await?;
+
+    let voters = sqlx::query_as::<_, VoteWithUser>(
+        "SELECT v.id as vote_id, v.user_id, u.username, v.option_id, v.vote_comment, v.created_at,
+                u.vote_weight
+         FROM votes v
+         JOIN users u ON v.user_id = u.id
+         WHERE v.option_id = ?
+         ORDER BY v.created_at ASC, v.id ASC
+         LIMIT ? OFFSET ?",
+    )
+    .bind(option_id)
+    .bind(OPTION_VOTERS_PAGE_SIZE)
+    .bind((page - 1) * OPTION_VOTERS_PAGE_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(OptionVotersPage {
+        option_id,
+        page,
+        per_page: OPTION_VOTERS_PAGE_SIZE,
+        total,
+        voters,
+    })
+}
+
+/// Determines whether a poll has reached its participation quorum.
+///
+/// Polls without a quorum are always considered to have met it.
+///
+/// # Arguments
+/// * `quorum` - Required number of unique voters, if any
+/// * `total_voters` - Number of unique voters so far
+///
+/// # Returns
+/// A tuple of whether the quorum is met and how many more voters are needed
+pub fn quorum_status(quorum: Option<i64>, total_voters: i64) -> (bool, i64) {
+    let remaining = quorum.map_or(0, |quorum| (quorum - total_voters).max(0));
+    (remaining == 0, remaining)
+}
+
+/// Retrieves every vote on a poll as a flat list of ballots.
+///
+/// Unlike the aggregate results or the per-option voters view, this is the
+/// raw event stream: one entry per vote, ordered by when it was cast.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll
+///
+/// # Returns
+/// * `Ok(Vec<Ballot>)` - Votes ordered from oldest to newest
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_poll_ballots(pool: &SqlitePool, poll_id: i64) -> Result<Vec<Ballot>, sqlx::Error> {
+    sqlx::query_as::<_, Ballot>(
+        "SELECT v.user_id, u.username, v.option_id, o.text as option_text, v.created_at
+         FROM votes v
+         JOIN options o ON v.option_id = o.id
+         JOIN users u ON v.user_id = u.id
+         WHERE o.poll_id = ?
+         ORDER BY v.created_at ASC, v.id ASC",
+    )
+    .bind(poll_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Streams every vote on a poll as CSV for the voters export.
+///
+/// Columns are `option,username,voted_at`, ordered by option and then by
+/// vote time. Rows are written as they are read from the database rather
+/// than collected first, so large polls export in constant memory.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll to export
+///
+/// # Returns
+/// A stream of CSV rows, starting with the header row
+pub fn stream_poll_votes_csv(pool: SqlitePool, poll_id: i64) -> csv::CsvStream {
+    csv::spawn(move |tx| async move {
+        if tx.send("option,username,voted_at\n".to_string()).await.is_err() {
+            return;
+        }
+
+        let mut rows = sqlx::query_as::<_, (String, String, DateTime<Utc>)>(
+            "SELECT o.text, u.username, v.created_at
+             FROM votes v
+             JOIN options o ON v.option_id = o.id
+             JOIN users u ON v.user_id = u.id
+             WHERE o.poll_id = ?
+             ORDER BY o.id, v.created_at",
+        )
+        .bind(poll_id)
+        .fetch(&pool);
+
+        while let Some(row) = rows.next().await {
+            let (option, username, voted_at) = match row {
+                Ok(row) => row,
+                Err(err) => {
+                    error!("Failed to export votes for poll {}: {}", poll_id, err);
+                    return;
+                }
+            };
+
+            let line = format!(
+                "{},{},{}\n",
+                csv::escape_field(&option),
+                csv::escape_field(&username),
+                voted_at.to_rfc3339(),
+            );
+
+            // The client has gone away
+            if tx.send(line).await.is_err() {
+                return;
+            }
+        }
+    })
+}
+
+/// Computes a weak ETag for a poll's current results.
+///
+/// The tag is derived from a cheap aggregate over the poll's votes (count,
+/// highest vote ID, latest vote time, and voters' total vote weight) plus the
+/// option and comment counts and the poll's timing state, so it changes
+/// whenever a vote is cast or removed, a voter's weight changes, options or
+/// comments change, or the poll locks, expires, or is reopened.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll` - The poll to tag
+/// * `viewer_id` - ID of the viewing user, for responses that include their own votes
+///
+/// # Returns
+/// * `Ok(String)` - Weak ETag value, including the `W/` prefix and quotes
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_poll_etag(
+    pool: &SqlitePool,
+    poll: &PollWithCreator,
+    viewer_id: Option<i64>,
+) -> Result<String, sqlx::Error> {
+    let (vote_count, max_vote_id, last_vote_at, total_weight, option_count, comment_state) =
+        sqlx::query_as::<_, (i64, i64, Option<String>, i64, i64, Option<String>)>(
+            "SELECT COUNT(v.id), COALESCE(MAX(v.id), 0), MAX(v.created_at),
+             COALESCE(SUM(u.vote_weight), 0),
+             (SELECT COUNT(*) FROM options WHERE poll_id = ?),
+             (SELECT COUNT(*) || ':' || COALESCE(MAX(id), 0) FROM comments WHERE poll_id = ?)
+             FROM votes v
+             JOIN options o ON v.option_id = o.id
+             JOIN users u ON v.user_id = u.id
+             WHERE o.poll_id = ?",
+        )
+        .bind(poll.id)
+        .bind(poll.id)
+        .bind(poll.id)
+        .fetch_one(pool)
+        .await?;
+
+    let now = Utc::now();
+    let mut hasher = DefaultHasher::new();
+    vote_count.hash(&mut hasher);
+    max_vote_id.hash(&mut hasher);
+    last_vote_at.hash(&mut hasher);
+    total_weight.hash(&mut hasher);
+    option_count.hash(&mut hasher);
+    comment_state.hash(&mut hasher);
+    poll.expires_at.hash(&mut hasher);
+    (poll.expires_at <= now).hash(&mut hasher);
+    poll.votes_lock_at.is_some_and(|dt| dt <= now).hash(&mut hasher);
+    poll.finalized.hash(&mut hasher);
+    // The page counts down to the lock, so the tag must change as it ticks
+    poll.votes_lock_at
+        .filter(|dt| *dt > now)
+        .map(|dt| time_until(dt, now))
+        .hash(&mut hasher);
+    viewer_id.hash(&mut hasher);
+
+    Ok(format!("W/\"{}-{:x}\"", poll.id, hasher.finish()))
+}
+
+/// Retrieves the number of votes for each of a poll's options.
+///
+/// Counts come from a single grouped query, so this is cheap enough for
+/// clients that poll for live results. Raw counts and counts weighted by
+/// each voter's vote weight are returned side by side, along with the open
+/// slots on vote-capped options.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll to count votes for
+///
+/// # Returns
+/// * `Ok(VoteCounts)` - Counts for every option, including those with no votes
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_vote_counts(pool: &SqlitePool, poll_id: i64) -> Result<VoteCounts, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (i64, Option<i64>, i64, i64, Option<DateTime<Utc>>)>(
+        "SELECT o.id, o.max_votes, COUNT(v.id), COALESCE(SUM(u.vote_weight), 0), MAX(v.created_at)
+         FROM options o
+         LEFT JOIN votes v ON v.option_id = o.id
+         LEFT JOIN users u ON v.user_id = u.id
+         WHERE o.poll_id = ?
+         GROUP BY o.id",
+    )
+    .bind(poll_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut vote_counts = VoteCounts {
+        counts: Default::default(),
+        total: 0,
+        weighted: Default::default(),
+        weighted_total: 0,
+        remaining: Default::default(),
+        full: Default::default(),
+        last_vote_at: None,
+    };
+    for (option_id, max_votes, count, weighted, last_vote_at) in rows {
+        let (remaining, full) = option_capacity(max_votes, count);
+        vote_counts.remaining.insert(option_id, remaining);
+        vote_counts.full.insert(option_id, full);
+        vote_counts.counts.insert(option_id, count);
+        vote_counts.total += count;
+        vote_counts.weighted.insert(option_id, weighted);
+        vote_counts.weighted_total += weighted;
+        vote_counts.last_vote_at = vote_counts.last_vote_at.max(last_vote_at);
+    }
+
+    Ok(vote_counts)
+}
+
+/// Builds a poll's cumulative vote count over time.
+///
+/// Vote times come from one ordered query and are folded into buckets
+/// here. Buckets run without gaps from the one holding the first vote to
+/// the one holding the last, so quiet periods show as flat stretches.
+/// Only current votes are counted: a vote that was taken back leaves no
+/// trace.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `poll_id` - ID of the poll
+/// * `bucket` - Size of each bucket
+///
+/// # Returns
+/// * `Ok(Vec<TimelinePoint>)` - The buckets, oldest first (empty if nobody voted)
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_poll_timeline(
+    pool: &SqlitePool,
+    poll_id: i64,
+    bucket: TimelineBucket,
+) -> Result<Vec<TimelinePoint>, sqlx::Error> {
+    let vote_times = sqlx::query_scalar::<_, DateTime<Utc>>(
+        "SELECT v.created_at FROM votes v
+         JOIN options o ON v.option_id = o.id
+         WHERE o.poll_id = ?
+         ORDER BY v.created_at",
+    )
+    .bind(poll_id)
+    .fetch_all(pool)
+    .await?;
+
+    let step = bucket.duration();
+    let mut points: Vec<TimelinePoint> = Vec::new();
+    let mut total = 0;
+    for created_at in vote_times {
+        let start = created_at.duration_trunc(step).unwrap_or(created_at);
+
+        // Carry the count through buckets without votes
+        while let Some(last) = points.last().map(|point| point.timestamp) {
+            if last >= start {
+                break;
+            }
+            points.push(TimelinePoint {
+                timestamp: last + step,
+                cumulative_votes: total,
+            });
+        }
+
+        total += 1;
+        match points.last_mut() {
+            Some(point) if point.timestamp == start => point.cumulative_votes = total,
+            _ => points.push(TimelinePoint {
+                timestamp: start,
+                cumulative_votes: total,
+            }),
+        }
+    }
+
+    Ok(points)
+}
+
+/// Computes a weak ETag for a poll's vote counts.
+///
+/// The tag is derived from the latest vote time and the counts themselves,
+/// since removing an older vote leaves the latest time unchanged.
+///
+/// # Arguments
+/// * `poll_id` - ID of the poll the counts belong to
+/// * `vote_counts` - The counts to tag
+///
+/// # Returns
+/// Weak ETag value, including the `W/` prefix and quotes
+pub fn get_vote_counts_etag(poll_id: i64, vote_counts: &VoteCounts) -> String {
+    let mut hasher = DefaultHasher::new();
+    vote_counts.last_vote_at.hash(&mut hasher);
+    vote_counts.counts.hash(&mut hasher);
+    vote_counts.weighted.hash(&mut hasher);
+    vote_counts.remaining.hash(&mut hasher);
+
+    format!("W/\"{}-counts-{:x}\"", poll_id, hasher.finish())
+}
+
+/// Extracts a poll's basic information, leaving out options and votes.
+///
+/// # Arguments
+/// * `poll` - Poll information with creator details
+pub fn get_poll_meta(poll: &PollWithCreator) -> PollMeta {
+    PollMeta {
+        id: poll.id,
+        title: poll.title.clone(),
+        description: poll.description.clone(),
+        creator_username: poll.creator_username.clone(),
+        created_at: poll.created_at,
+        expires_at: poll.expires_at,
+        is_expired: poll.expires_at <= Utc::now(),
+    }
+}
+
+/// Computes a weak ETag for a poll's basic information.
+///
+/// Titles and descriptions can't be edited, so the tag only needs the
+/// creation and expiry times plus whether the poll has expired yet.
+///
+/// # Arguments
+/// * `meta` - The poll information to tag
+///
+/// # Returns
+/// Weak ETag value, including the `W/` prefix and quotes
+pub fn get_poll_meta_etag(meta: &PollMeta) -> String {
+    let mut hasher = DefaultHasher::new();
+    meta.created_at.hash(&mut hasher);
+    meta.expires_at.hash(&mut hasher);
+    meta.is_expired.hash(&mut hasher);
+
+    format!("W/\"{}-meta-{:x}\"", meta.id, hasher.finish())
+}
+
+/// Describes how long remains until `at`, such as "2 days" or "45 minutes".
+///
+/// Uses the largest whole unit, rounding down, and never reports less than
+/// one minute.
+///
+/// # Arguments
+/// * `at` - The future time to count down to
+/// * `now` - The current time
+fn time_until(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let remaining = at - now;
+    let (count, unit) = if remaining.num_days() >= 1 {
+        (remaining.num_days(), "day")
+    } else if remaining.num_hours() >= 1 {
+        (remaining.num_hours(), "hour")
+    } else {
+        (remaining.num_minutes().max(1), "minute")
+    };
+
+    format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" })
+}
+
+/// Computes an option's share of the total votes as a percentage.
+///
+/// The result is rounded to one decimal place. A poll with no votes yields
+/// `0.0` for every option rather than dividing by zero.
+///
+/// # Arguments
+/// * `vote_count` - Number of votes for the option
+/// * `total_votes` - Number of votes across all options
+///
+/// # Returns
+/// The percentage in the range `0.0..=100.0`
+pub fn vote_percentage(vote_count: i64, total_votes: i64) -> f64 {
+    if total_votes <= 0 {
+        return 0.0;
+    }
+
+    (vote_count as f64 / total_votes as f64 * 1000.0).round() / 10.0
+}
+
+/// Works out how much room a vote-capped option has left.
+///
+/// # Arguments
+/// * `max_votes` - The option's vote cap, if any
+/// * `vote_count` - Votes the option currently has
+///
+/// # Returns
+/// The open slots (never below 0) and whether the option is full, or
+/// `None` for both when the option has no cap
+pub fn option_capacity(max_votes: Option<i64>, vote_count: i64) -> (Option<i64>, Option<bool>) {
+    match max_votes {
+        Some(max) => (Some((max - vote_count).max(0)), Some(vote_count >= max)),
+        None => (None, None),
+    }
+}
+
+/// Decides whether an option is hidden behind the "show more" cutoff.
+///
+/// Options the user voted for stay visible regardless of position.
+///
+/// # Arguments
+/// * `index` - Position of the option in the poll
+/// * `is_voted` - Whether the user voted for the option
+/// * `max_visible` - Options shown before the cutoff, if any (`MAX_VISIBLE_OPTIONS`)
+fn is_collapsed(index: usize, is_voted: bool, max_visible: Option<usize>) -> bool {
+    !is_voted && max_visible.is_some_and(|max| index >= max)
+}
+
+/// Formats poll data into JSON structure for template rendering.
+///
+/// This function converts poll and voting data into a JSON structure
+/// suitable for use in Tera templates, including vote counts, user voting
+/// status, and expiration information.
+///
+/// # Template Data Included
+/// - Poll basic information (title, description, creator, dates)
+/// - Expiration status (is_expired boolean)
+/// - Vote lock time and status (is_locked boolean), plus `votes_lock_in`
+///   (e.g. "3 hours") while the lock is still ahead; omitted otherwise
+/// - All options with raw and weighted vote counts, percentages of the
+///   weighted total, and user voting status; capped options also get
+///   `remaining` slots and `full` (both null when uncapped)
+/// - Total vote count across all options, raw and weighted, and whether
+///   any weighting applies (`weighted`)
+///
+/// # Arguments
+/// * `poll` - Poll information with creator details
+/// * `options` - Array of poll options with vote counts
+/// * `user_votes` - Array of option IDs the current user has voted for
+///
+/// # Returns
+/// A JSON value containing all formatted poll data for template use
+pub fn format_poll_for_template(
+    poll: &PollWithCreator,
+    options: &[PollOption],
+    user_votes: &[i64],
+) -> serde_json::Value {
+    let total_votes: i64 = options.iter().map(|o| o.vote_count).sum();
+    let total_weighted_votes: i64 = options.iter().map(|o| o.weighted_votes).sum();
+
+    let max_visible = config::get().max_visible_options;
+    let mut hidden_count = 0;
+
+    let options_json: Vec<serde_json::Value> = options
+        .iter()
+        .enumerate()
+        .map(|(index, option)| {
+            let is_voted = user_votes.contains(&option.id);
+            let (remaining, full) = option_capacity(option.max_votes, option.vote_count);
+
+            let collapsed = is_collapsed(index, is_voted, max_visible);
+            if collapsed {
+                hidden_count += 1;
+            }
+
+            serde_json::json!({
+                "id": option.id,
+                "text": option.text,
+                "is_date": option.is_date,
+                "date_time": option.date_time,
+                "image_url": option.image_url,
+                "max_votes": option.max_votes,
+                "suggested_by_username": option.suggested_by_username,
+                "remaining": remaining,
+                "full": full,
+                "vote_count": option.vote_count,
+                "weighted_votes": option.weighted_votes,
+                "percentage": vote_percentage(option.weighted_votes, total_weighted_votes),
+                "is_voted": is_voted,
+                "collapsed": collapsed,
+            })
+        })
+        .collect();
+
+    let now = Utc::now();
+    let mut poll_json = serde_json::json!({
+        "id": poll.id,
+        "title": poll.title,
+        "description": poll.description,
+        "creator_id": poll.creator_id,
+        "creator_username": poll.creator_username,
+        "created_at": poll.created_at.to_rfc3339(),
+        "expires_at": poll.expires_at.to_rfc3339(),
+        "is_expired": poll.expires_at <= now,
+        "votes_lock_at": poll.votes_lock_at.map(|dt| dt.to_rfc3339()),
+        "open_voters": poll.open_voters,
+        "require_vote_comment": poll.require_vote_comment,
+        "quorum": poll.quorum,
+        "auto_close_at_quorum": poll.auto_close_at_quorum,
+        "poll_kind": poll.poll_kind,
+        "archived": poll.archived,
+        "creator_can_vote": poll.creator_can_vote,
+        "multi_select": poll.multi_select,
+        "finalized": poll.finalized,
+        "allow_write_in": poll.allow_write_in,
+        "published": poll.published,
+        "user_has_voted": !user_votes.is_empty(),
+        "is_locked": poll.finalized || poll.votes_lock_at.is_some_and(|dt| dt <= now),
+        "options": options_json,
+        "hidden_count": hidden_count,
+        "total_votes": total_votes,
+        "total_weighted_votes": total_weighted_votes,
+        "weighted": total_weighted_votes != total_votes,
+    });
+
+    if let Some(lock) = poll.votes_lock_at.filter(|dt| *dt > now) {
+        poll_json["votes_lock_in"] = serde_json::json!(time_until(lock, now));
+    }
+
+    poll_json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    /// Inserts a user and returns their ID.
+    async fn add_user(pool: &SqlitePool, username: &str, is_admin: bool) -> i64 {
+        sqlx::query("INSERT INTO users (username, password_hash, is_admin) VALUES (?, '!', ?)")
+            .bind(username)
+            .bind(is_admin)
+            .execute(pool)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    /// A form for a poll closing tomorrow with the given newline-separated options.
+    fn poll_form(title: &str, options: &str) -> NewPollForm {
+        NewPollForm {
+            title: title.to_string(),
+            description: None,
+            expires_at: (Utc::now() + Duration::days(1)).format("%Y-%m-%dT%H:%M").to_string(),
+            votes_lock_at: None,
+            quorum: None,
+            open_voters: false,
+            require_vote_comment: false,
+            auto_close_at_quorum: false,
+            poll_kind: None,
+            creator_can_vote: None,
+            multi_select: None,
+            allow_write_in: false,
+            options: options.to_string(),
+            options_separator: Some("newline".to_string()),
+            copy_options_from: None,
+            confirm_duplicate: false,
+        }
+    }
+
+    /// Returns the ID of the poll's option with the given text.
+    async fn option_id(pool: &SqlitePool, poll_id: i64, text: &str) -> i64 {
+        get_poll_options(pool, poll_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|option| option.text == text)
+            .unwrap()
+            .id
+    }
+
+    /// Returns the message of an error built by [`invalid_input`].
+    fn message(err: sqlx::Error) -> String {
+        match err {
+            sqlx::Error::ColumnDecode { source, .. } => source.to_string(),
+            other => panic!("expected invalid input, got {}", other),
+        }
+    }
+
+    #[test]
+    fn vote_percentage_rounds_to_one_decimal() {
+        assert_eq!(vote_percentage(1, 3), 33.3);
+        assert_eq!(vote_percentage(2, 3), 66.7);
+        assert_eq!(vote_percentage(1, 4), 25.0);
+        assert_eq!(vote_percentage(4, 4), 100.0);
+    }
+
+    #[test]
+    fn vote_percentage_is_zero_without_votes() {
+        assert_eq!(vote_percentage(0, 0), 0.0);
+        assert_eq!(vote_percentage(0, 5), 0.0);
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn single_choice_vote_change_names_both_options(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let voter = add_user(&pool, "voter", false).await;
+        let mut form = poll_form("Next game", "Catan\nAzul");
+        form.multi_select = Some(false);
+        let poll_id = create_poll(&pool, &form, creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+        let azul = option_id(&pool, poll_id, "Azul").await;
+
+        assert_eq!(vote_on_poll(&pool, poll_id, catan, voter, None).await?, VoteOutcome::FirstVote);
+        assert_eq!(
+            vote_on_poll(&pool, poll_id, azul, voter, None).await?,
+            VoteOutcome::Changed {
+                from: "Catan".to_string(),
+                to: "Azul".to_string(),
+            }
+        );
+        assert_eq!(get_user_votes(&pool, poll_id, voter).await?, vec![azul]);
+        assert_eq!(vote_on_poll(&pool, poll_id, azul, voter, None).await?, VoteOutcome::Removed);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn multi_select_vote_is_added(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let voter = add_user(&pool, "voter", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul"), creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+        let azul = option_id(&pool, poll_id, "Azul").await;
+
+        assert_eq!(vote_on_poll(&pool, poll_id, catan, voter, None).await?, VoteOutcome::FirstVote);
+        assert_eq!(vote_on_poll(&pool, poll_id, azul, voter, None).await?, VoteOutcome::Added);
+        assert_eq!(get_user_votes(&pool, poll_id, voter).await?.len(), 2);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn template_import_needs_a_future_expiry(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul"), creator).await?;
+        let document = serde_json::to_string(&get_poll_template(&pool, poll_id).await?).unwrap();
+
+        let yesterday = (Utc::now() - Duration::days(1)).format("%Y-%m-%dT%H:%M").to_string();
+        let err = import_poll_template(&pool, &document, &yesterday, creator).await.unwrap_err();
+        assert_eq!(message(err), "Expiration time must be in the future");
+
+        let tomorrow = (Utc::now() + Duration::days(1)).format("%Y-%m-%dT%H:%M").to_string();
+        let imported = import_poll_template(&pool, &document, &tomorrow, creator).await?;
+        assert_eq!(get_poll_options(&pool, imported).await?.len(), 2);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn finalized_poll_snapshot_is_frozen(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let voter = add_user(&pool, "voter", false).await;
+        let late = add_user(&pool, "late", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul"), creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+        let azul = option_id(&pool, poll_id, "Azul").await;
+        vote_on_poll(&pool, poll_id, catan, voter, None).await?;
+
+        finalize_poll(&pool, poll_id, creator, false).await?;
+        let err = vote_on_poll(&pool, poll_id, azul, late, None).await.unwrap_err();
+        assert_eq!(message(err), "Votes for this poll are locked in and can no longer be changed.");
+
+        let snapshot = get_results_snapshot(&pool, poll_id).await?;
+        let details: PollVotingDetails = serde_json::from_str(&snapshot.results).unwrap();
+        assert!(details.poll.finalized);
+        assert_eq!(details.total_votes, 1);
+        assert_eq!(details.total_voters, 1);
+        assert_eq!(snapshot.finalized_by, Some(creator));
+
+        let err = finalize_poll(&pool, poll_id, creator, false).await.unwrap_err();
+        assert_eq!(message(err), "This poll has already been finalized.");
+        Ok(())
+    }
+
+    /// Stores a poll's expiry as SQLite writes timestamps, without the `T`
+    /// or offset that bound values carry.
+    async fn set_expiry(pool: &SqlitePool, poll_id: i64, expires_at: DateTime<Utc>) {
+        sqlx::query("UPDATE polls SET expires_at = ? WHERE id = ?")
+            .bind(expires_at.format("%Y-%m-%d %H:%M:%S%.f").to_string())
+            .bind(poll_id)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn expiry_boundary_agrees_between_listings_and_votes(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let voter = add_user(&pool, "voter", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul"), creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+        let listed = |polls: Vec<PollWithCreator>| polls.iter().any(|poll| poll.id == poll_id);
+
+        // A minute before expiry the poll is open everywhere
+        set_expiry(&pool, poll_id, Utc::now() + Duration::minutes(1)).await;
+        assert!(listed(get_active_polls(&pool).await?));
+        assert!(!listed(get_expired_polls(&pool).await?));
+        assert_eq!(vote_on_poll(&pool, poll_id, catan, voter, None).await?, VoteOutcome::FirstVote);
+
+        // At exactly expires_at it is expired everywhere
+        set_expiry(&pool, poll_id, Utc::now()).await;
+        assert!(!listed(get_active_polls(&pool).await?));
+        assert!(listed(get_expired_polls(&pool).await?));
+        let err = vote_on_poll(&pool, poll_id, catan, voter, None).await.unwrap_err();
+        assert_eq!(message(err), "Cannot vote on expired poll.");
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn option_text_is_unique_ignoring_case(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+
+        let err = create_poll(&pool, &poll_form("Next game", "Catan\ncatan"), creator).await.unwrap_err();
+        assert_eq!(message(err), "Option \"catan\" is already in this poll");
+
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul"), creator).await?;
+        let form = NewOptionsForm {
+            options: "Wingspan, catan".to_string(),
+            also_extend: false,
+            expires_at: None,
+        };
+        let err = add_poll_options(&pool, poll_id, &form, creator).await.unwrap_err();
+        assert_eq!(message(err), "Option \"catan\" is already in this poll");
+        assert_eq!(get_poll_options(&pool, poll_id).await?.len(), 2);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn votes_csv_streams_one_row_per_chunk(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul"), creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+
+        // More rows than the export channel buffers, so the producer has to
+        // wait for the reader
+        for i in 0..200 {
+            let voter = add_user(&pool, &format!("voter{}", i), false).await;
+            sqlx::query("INSERT INTO votes (user_id, option_id) VALUES (?, ?)")
+                .bind(voter)
+                .bind(catan)
+                .execute(&pool)
+                .await?;
+        }
+
+        let chunks: Vec<String> = stream_poll_votes_csv(pool.clone(), poll_id).collect().await;
+        assert_eq!(chunks.len(), 201);
+        assert_eq!(chunks[0], "option,username,voted_at\n");
+        for chunk in &chunks[1..] {
+            assert!(chunk.starts_with("Catan,voter"));
+            assert_eq!(chunk.matches('\n').count(), 1);
+        }
+        Ok(())
+    }
+
+    /// Returns each option's rendered percentage, in option order.
+    async fn rendered_percentages(pool: &SqlitePool, poll_id: i64) -> Vec<f64> {
+        let poll = get_poll_by_id(pool, poll_id).await.unwrap();
+        let options = get_poll_options(pool, poll_id).await.unwrap();
+        format_poll_for_template(&poll, &options, &[])["options"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|option| option["percentage"].as_f64().unwrap())
+            .collect()
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn rendered_percentages_follow_weighted_votes(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul\nWingspan"), creator).await?;
+        assert_eq!(rendered_percentages(&pool, poll_id).await, [0.0, 0.0, 0.0]);
+
+        let catan = option_id(&pool, poll_id, "Catan").await;
+        let azul = option_id(&pool, poll_id, "Azul").await;
+        let mut voters = Vec::new();
+        for (name, option) in [("ann", catan), ("bob", catan), ("cat", azul)] {
+            let voter = add_user(&pool, name, false).await;
+            vote_on_poll(&pool, poll_id, option, voter, None).await?;
+            voters.push(voter);
+        }
+        // 2 of 3 votes is 66.7%, 1 of 3 is 33.3%
+        assert_eq!(rendered_percentages(&pool, poll_id).await, [66.7, 33.3, 0.0]);
+
+        // With a weight of 2, cat's single vote matches the other two
+        sqlx::query("UPDATE users SET vote_weight = 2 WHERE id = ?")
+            .bind(voters[2])
+            .execute(&pool)
+            .await?;
+        assert_eq!(rendered_percentages(&pool, poll_id).await, [50.0, 50.0, 0.0]);
+        Ok(())
+    }
+
+    /// Marks a poll as waiting for approval.
+    async fn unpublish(pool: &SqlitePool, poll_id: i64) {
+        sqlx::query("UPDATE polls SET published = 0 WHERE id = ?")
+            .bind(poll_id)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn runoff_of_unpublished_poll_waits_for_approval(pool: SqlitePool) -> sqlx::Result<()> {
+        let admin = add_user(&pool, "admin", true).await;
+        let creator = add_user(&pool, "creator", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul\nWingspan"), creator).await?;
+        let tomorrow = (Utc::now() + Duration::days(1)).format("%Y-%m-%dT%H:%M").to_string();
+
+        let runoff = create_runoff(&pool, poll_id, 2, &tomorrow, admin, true).await?;
+        assert!(get_poll_by_id(&pool, runoff).await?.published);
+
+        unpublish(&pool, poll_id).await;
+        let runoff = create_runoff(&pool, poll_id, 2, &tomorrow, admin, true).await?;
+        assert!(!get_poll_by_id(&pool, runoff).await?.published);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn unpublished_poll_is_hidden_from_other_users(pool: SqlitePool) -> sqlx::Result<()> {
+        let admin = add_user(&pool, "admin", true).await;
+        let creator = add_user(&pool, "creator", false).await;
+        let other = add_user(&pool, "other", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul"), creator).await?;
+        unpublish(&pool, poll_id).await;
+
+        // Copying its options
+        let mut form = poll_form("Copy", "Wingspan");
+        form.copy_options_from = Some(poll_id);
+        let err = create_poll(&pool, &form, other).await.unwrap_err();
+        assert_eq!(message(err), "The poll to copy options from was not found");
+        let copy = create_poll(&pool, &form, admin).await?;
+        assert_eq!(get_poll_options(&pool, copy).await?.len(), 3);
+
+        // Acting on it as someone else
+        let err = nudge_non_voters(&pool, poll_id, other, false).await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::RowNotFound), "{}", err);
+        let tomorrow = (Utc::now() + Duration::days(1)).format("%Y-%m-%dT%H:%M").to_string();
+        let err = create_runoff(&pool, poll_id, 2, &tomorrow, other, false).await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::RowNotFound), "{}", err);
+
+        // Its creator can't nudge anyone until it's approved
+        let err = nudge_non_voters(&pool, poll_id, creator, false).await.unwrap_err();
+        assert_eq!(message(err), "This poll is waiting for approval, so nobody can vote on it yet");
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn votes_lock_at_the_retraction_deadline(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let voter = add_user(&pool, "voter", false).await;
+
+        let mut form = poll_form("Next game", "Catan\nAzul");
+        form.votes_lock_at = Some((Utc::now() + Duration::days(2)).format("%Y-%m-%dT%H:%M").to_string());
+        let err = create_poll(&pool, &form, creator).await.unwrap_err();
+        assert_eq!(message(err), "Vote lock time must not be later than the expiration time");
+
+        form.votes_lock_at = Some((Utc::now() + Duration::hours(1)).format("%Y-%m-%dT%H:%M").to_string());
+        let poll_id = create_poll(&pool, &form, creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+        let azul = option_id(&pool, poll_id, "Azul").await;
+        vote_on_poll(&pool, poll_id, catan, voter, None).await?;
+
+        sqlx::query("UPDATE polls SET votes_lock_at = ? WHERE id = ?")
+            .bind(Utc::now() - Duration::minutes(1))
+            .bind(poll_id)
+            .execute(&pool)
+            .await?;
+        for option in [catan, azul] {
+            let err = vote_on_poll(&pool, poll_id, option, voter, None).await.unwrap_err();
+            assert_eq!(message(err), "Votes for this poll are locked in and can no longer be changed.");
+        }
+        assert_eq!(get_user_votes(&pool, poll_id, voter).await?, vec![catan]);
+        Ok(())
+    }
+
+    #[test]
+    fn quorum_status_counts_the_voters_still_needed() {
+        assert_eq!(quorum_status(None, 0), (true, 0));
+        assert_eq!(quorum_status(Some(3), 1), (false, 2));
+        assert_eq!(quorum_status(Some(3), 3), (true, 0));
+        assert_eq!(quorum_status(Some(3), 5), (true, 0));
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn quorum_counts_unique_voters(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let ann = add_user(&pool, "ann", false).await;
+        let bob = add_user(&pool, "bob", false).await;
+        let mut form = poll_form("Next game", "Catan\nAzul");
+        form.quorum = Some(3);
+        let poll_id = create_poll(&pool, &form, creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+        let azul = option_id(&pool, poll_id, "Azul").await;
+
+        // Two votes from one voter count once
+        vote_on_poll(&pool, poll_id, catan, ann, None).await?;
+        vote_on_poll(&pool, poll_id, azul, ann, None).await?;
+        let details = get_poll_voting_details(&pool, poll_id).await?;
+        assert_eq!((details.total_voters, details.quorum_met, details.quorum_remaining), (1, false, 2));
+
+        vote_on_poll(&pool, poll_id, catan, bob, None).await?;
+        let details = get_poll_voting_details(&pool, poll_id).await?;
+        assert_eq!((details.total_voters, details.quorum_met, details.quorum_remaining), (2, false, 1));
+
+        form.quorum = Some(-1);
+        let err = create_poll(&pool, &form, creator).await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::ColumnDecode { .. }));
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn only_expired_polls_can_be_reopened(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let other = add_user(&pool, "other", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul"), creator).await?;
+        let next_week = (Utc::now() + Duration::days(7)).format("%Y-%m-%dT%H:%M").to_string();
+        let yesterday = (Utc::now() - Duration::days(1)).format("%Y-%m-%dT%H:%M").to_string();
+
+        let err = reopen_poll(&pool, poll_id, &next_week, creator, false).await.unwrap_err();
+        assert_eq!(message(err), "Poll is still active and cannot be reopened");
+
+        set_expiry(&pool, poll_id, Utc::now() - Duration::hours(1)).await;
+        let err = reopen_poll(&pool, poll_id, &yesterday, creator, false).await.unwrap_err();
+        assert_eq!(message(err), "New expiration time must be in the future");
+        let err = reopen_poll(&pool, poll_id, &next_week, other, false).await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::ColumnDecode { index, .. } if index == "forbidden"));
+
+        reopen_poll(&pool, poll_id, &next_week, creator, false).await?;
+        let poll = get_poll_by_id(&pool, poll_id).await?;
+        assert_eq!(poll.expires_at.format("%Y-%m-%dT%H:%M").to_string(), next_week);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn calendar_lists_voted_dates_of_open_polls_in_order(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let voter = add_user(&pool, "voter", false).await;
+        let later = (Utc::now() + Duration::days(10)).format("%Y-%m-%dT19:00").to_string();
+        let sooner = (Utc::now() + Duration::days(5)).format("%Y-%m-%dT19:00").to_string();
+        let options = format!("{}\n{}\nCatan", later, sooner);
+
+        let open = create_poll(&pool, &poll_form("When?", &options), creator).await?;
+        let closed = create_poll(&pool, &poll_form("When else?", &options), creator).await?;
+        for poll_id in [open, closed] {
+            for text in [later.as_str(), sooner.as_str(), "Catan"] {
+                let option = option_id(&pool, poll_id, text).await;
+                vote_on_poll(&pool, poll_id, option, voter, None).await?;
+            }
+        }
+        set_expiry(&pool, closed, Utc::now() - Duration::minutes(1)).await;
+
+        let calendar = get_user_calendar(&pool, voter).await?;
+        let entries: Vec<(i64, &str)> = calendar
+            .iter()
+            .map(|entry| (entry.poll_id, entry.text.as_str()))
+            .collect();
+        assert_eq!(entries, [(open, sooner.as_str()), (open, later.as_str())]);
+        assert!(get_user_calendar(&pool, creator).await?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn options_past_the_cutoff_collapse_unless_voted() {
+        assert!(!is_collapsed(5, false, None));
+        assert!(!is_collapsed(2, false, Some(3)));
+        assert!(is_collapsed(3, false, Some(3)));
+        assert!(!is_collapsed(3, true, Some(3)));
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn ballots_list_every_vote_oldest_first(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let ann = add_user(&pool, "ann", false).await;
+        let bob = add_user(&pool, "bob", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul"), creator).await?;
+        let other_poll = create_poll(&pool, &poll_form("Snacks", "Chips\nPretzels"), creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+        let azul = option_id(&pool, poll_id, "Azul").await;
+        let chips = option_id(&pool, other_poll, "Chips").await;
+
+        vote_on_poll(&pool, poll_id, azul, bob, None).await?;
+        vote_on_poll(&pool, poll_id, catan, ann, None).await?;
+        vote_on_poll(&pool, poll_id, azul, ann, None).await?;
+        vote_on_poll(&pool, other_poll, chips, ann, None).await?;
+
+        let ballots: Vec<(String, String)> = get_poll_ballots(&pool, poll_id)
+            .await?
+            .into_iter()
+            .map(|ballot| (ballot.username, ballot.option_text))
+            .collect();
+        let expected = [("bob", "Azul"), ("ann", "Catan"), ("ann", "Azul")];
+        assert_eq!(ballots, expected.map(|(user, option)| (user.to_string(), option.to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn options_take_an_http_image_url() {
+        let options = parse_options("Catan | https://example.com/catan.png, Azul", ',').unwrap();
+        assert_eq!(options[0].text, "Catan");
+        assert_eq!(options[0].image_url, Some("https://example.com/catan.png"));
+        assert_eq!(options[1].image_url, None);
+
+        for url in ["javascript:alert(1)", "ftp://example.com/a.png", "https://example.com/a b.png"] {
+            let err = parse_options(&format!("Azul | {}", url), ',').err().unwrap();
+            assert_eq!(message(err), "Option \"Azul\" has an invalid image URL (must be http or https)");
+        }
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn runoff_copies_the_top_options_without_votes(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let other = add_user(&pool, "other", false).await;
+        let form = poll_form("Next game", "Catan\nAzul\nWingspan\nRoot");
+        let poll_id = create_poll(&pool, &form, creator).await?;
+        let tomorrow = (Utc::now() + Duration::days(1)).format("%Y-%m-%dT%H:%M").to_string();
+        for (name, text) in [("ann", "Wingspan"), ("bob", "Wingspan"), ("cat", "Root")] {
+            let voter = add_user(&pool, name, false).await;
+            let option = option_id(&pool, poll_id, text).await;
+            vote_on_poll(&pool, poll_id, option, voter, None).await?;
+        }
+
+        // Catan and Azul tie at zero, so the earlier option wins the third place
+        let runoff = create_runoff(&pool, poll_id, 3, &tomorrow, creator, false).await?;
+        let options: Vec<(String, i64)> = get_poll_options(&pool, runoff)
+            .await?
+            .into_iter()
+            .map(|option| (option.text, option.vote_count))
+            .collect();
+        assert_eq!(
+            options,
+            [("Wingspan".to_string(), 0), ("Root".to_string(), 0), ("Catan".to_string(), 0)]
+        );
+
+        let err = create_runoff(&pool, poll_id, 1, &tomorrow, creator, false).await.unwrap_err();
+        assert_eq!(message(err), "A runoff needs at least 2 options");
+        let err = create_runoff(&pool, poll_id, 5, &tomorrow, creator, false).await.unwrap_err();
+        assert_eq!(message(err), "The poll only has 4 options");
+        let err = create_runoff(&pool, poll_id, 2, &tomorrow, other, false).await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::ColumnDecode { index, .. } if index == "forbidden"));
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn required_comment_is_checked_on_new_votes(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let voter = add_user(&pool, "voter", false).await;
+        let mut form = poll_form("Next game", "Catan\nAzul");
+        form.require_vote_comment = true;
+        let poll_id = create_poll(&pool, &form, creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+
+        for comment in [None, Some("   ")] {
+            let err = vote_on_poll(&pool, poll_id, catan, voter, comment).await.unwrap_err();
+            assert_eq!(message(err), "Please add a comment explaining your vote");
+        }
+        let long = "a".repeat(501);
+        let err = vote_on_poll(&pool, poll_id, catan, voter, Some(&long)).await.unwrap_err();
+        assert_eq!(message(err), "Comments can be at most 500 characters");
+
+        vote_on_poll(&pool, poll_id, catan, voter, Some("  Quick to teach ")).await?;
+        let comment: Option<String> = sqlx::query_scalar("SELECT vote_comment FROM votes WHERE user_id = ?")
+            .bind(voter)
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(comment.as_deref(), Some("Quick to teach"));
+
+        // Taking a vote back needs no comment
+        assert_eq!(vote_on_poll(&pool, poll_id, catan, voter, None).await?, VoteOutcome::Removed);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn poll_closes_when_the_quorum_is_reached(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let first = add_user(&pool, "first", false).await;
+        let second = add_user(&pool, "second", false).await;
+        let third = add_user(&pool, "third", false).await;
+        let mut form = poll_form("Next game", "Catan\nAzul");
+        form.quorum = Some(2);
+        form.auto_close_at_quorum = true;
+        let poll_id = create_poll(&pool, &form, creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+
+        assert_eq!(vote_on_poll(&pool, poll_id, catan, first, None).await?, VoteOutcome::FirstVote);
+        assert_eq!(
+            vote_on_poll(&pool, poll_id, catan, second, None).await?,
+            VoteOutcome::ClosedAtQuorum
+        );
+
+        let open: bool = sqlx::query_scalar(
+            "SELECT julianday(expires_at) > julianday('now') FROM polls WHERE id = ?",
+        )
+        .bind(poll_id)
+        .fetch_one(&pool)
+        .await?;
+        assert!(!open);
+        assert!(vote_on_poll(&pool, poll_id, catan, third, None).await.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn closing_at_quorum_needs_a_quorum() {
+        assert_eq!(
+            message(check_quorum(None, true).unwrap_err()),
+            "Set a quorum to close the poll automatically when it is reached"
+        );
+        assert!(check_quorum(Some(0), true).is_err());
+        assert!(check_quorum(Some(-1), false).is_err());
+        assert!(check_quorum(Some(3), true).is_ok());
+        assert!(check_quorum(None, false).is_ok());
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn nudge_reaches_only_active_non_voters_once(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let voter = add_user(&pool, "voter", false).await;
+        let waiting = add_user(&pool, "waiting", false).await;
+        let disabled = add_user(&pool, "disabled", false).await;
+        sqlx::query("UPDATE users SET disabled = 1 WHERE id = ?")
+            .bind(disabled)
+            .execute(&pool)
+            .await?;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul"), creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+        vote_on_poll(&pool, poll_id, catan, voter, None).await?;
+
+        let err = nudge_non_voters(&pool, poll_id, waiting, false).await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::ColumnDecode { index, .. } if index == "forbidden"));
+
+        assert_eq!(nudge_non_voters(&pool, poll_id, creator, false).await?, 2);
+        let nudged: Vec<i64> = sqlx::query_scalar(
+            "SELECT user_id FROM notifications WHERE kind = 'nudge' ORDER BY user_id",
+        )
+        .fetch_all(&pool)
+        .await?;
+        assert_eq!(nudged, vec![creator, waiting]);
+
+        // A second nudge inside the interval is refused and sends nothing
+        assert!(message(nudge_non_voters(&pool, poll_id, creator, false).await.unwrap_err())
+            .contains("already nudged"));
+        let total: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM notifications WHERE kind = 'nudge'")
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(total, 2);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn full_option_only_accepts_unvotes(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let first = add_user(&pool, "first", false).await;
+        let second = add_user(&pool, "second", false).await;
+        let poll_id =
+            create_poll(&pool, &poll_form("Game night", "Catan|max=1\nAzul"), creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+
+        vote_on_poll(&pool, poll_id, catan, first, None).await?;
+        let err = vote_on_poll(&pool, poll_id, catan, second, None).await.unwrap_err();
+        assert_eq!(message(err), "This option is full");
+
+        assert_eq!(vote_on_poll(&pool, poll_id, catan, first, None).await?, VoteOutcome::Removed);
+        assert_eq!(
+            vote_on_poll(&pool, poll_id, catan, second, None).await?,
+            VoteOutcome::FirstVote
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn vote_cap_must_be_a_positive_whole_number() {
+        let options = parse_options("Catan|max=6\nAzul", '\n').unwrap();
+        assert_eq!(options[0].max_votes, Some(6));
+        assert_eq!(options[1].max_votes, None);
+
+        for raw in ["Catan|max=0", "Catan|max=-2", "Catan|max=lots"] {
+            let err = parse_options(raw, '\n').err().unwrap();
+            assert!(message(err).contains("invalid vote cap"), "{}", raw);
+        }
+    }
+
+    #[test]
+    fn capacity_is_only_reported_for_capped_options() {
+        assert_eq!(option_capacity(None, 3), (None, None));
+        assert_eq!(option_capacity(Some(6), 4), (Some(2), Some(false)));
+        assert_eq!(option_capacity(Some(6), 6), (Some(0), Some(true)));
+        // A cap lowered below the existing votes never shows negative slots
+        assert_eq!(option_capacity(Some(2), 5), (Some(0), Some(true)));
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn vote_counts_include_open_slots(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let voter = add_user(&pool, "voter", false).await;
+        let poll_id =
+            create_poll(&pool, &poll_form("Game night", "Catan|max=1\nRoot|max=3\nAzul"), creator)
+                .await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+        let root = option_id(&pool, poll_id, "Root").await;
+        let azul = option_id(&pool, poll_id, "Azul").await;
+        vote_on_poll(&pool, poll_id, catan, voter, None).await?;
+        vote_on_poll(&pool, poll_id, root, voter, None).await?;
+
+        let counts = get_vote_counts(&pool, poll_id).await?;
+        assert_eq!(counts.remaining[&catan], Some(0));
+        assert_eq!(counts.full[&catan], Some(true));
+        assert_eq!(counts.remaining[&root], Some(2));
+        assert_eq!(counts.full[&root], Some(false));
+        assert_eq!((counts.remaining[&azul], counts.full[&azul]), (None, None));
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn poll_kind_restricts_options(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+
+        let mut form = poll_form("When?", "2030-03-01T19:00\nSoon");
+        form.poll_kind = Some("dates".to_string());
+        let err = create_poll(&pool, &form, creator).await.unwrap_err();
+        assert_eq!(
+            message(err),
+            "Option \"Soon\" is not a date (YYYY-MM-DDTHH:MM), but this poll only accepts dates"
+        );
+
+        let mut form = poll_form("What?", "Catan\n2030-03-01T19:00");
+        form.poll_kind = Some("text".to_string());
+        let err = create_poll(&pool, &form, creator).await.unwrap_err();
+        assert!(message(err).starts_with("Option \"2030-03-01T19:00\" is a date"));
+
+        form.poll_kind = Some("bingo".to_string());
+        let err = create_poll(&pool, &form, creator).await.unwrap_err();
+        assert_eq!(message(err), "Unknown poll kind \"bingo\"");
+
+        // Mixed is the default and takes both, and later options must match the kind
+        form.poll_kind = None;
+        create_poll(&pool, &form, creator).await?;
+        let mut form = poll_form("When?", "2030-03-01T19:00");
+        form.poll_kind = Some("dates".to_string());
+        let poll_id = create_poll(&pool, &form, creator).await?;
+        let new_options = |options: &str| NewOptionsForm {
+            options: options.to_string(),
+            also_extend: false,
+            expires_at: None,
+        };
+        assert!(add_poll_options(&pool, poll_id, &new_options("Later"), creator).await.is_err());
+        add_poll_options(&pool, poll_id, &new_options("2030-03-02T19:00"), creator).await?;
+        assert_eq!(get_poll_options(&pool, poll_id).await?.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn time_until_uses_the_largest_whole_unit() {
+        let now = Utc::now();
+        assert_eq!(time_until(now + Duration::days(3) + Duration::hours(5), now), "3 days");
+        assert_eq!(time_until(now + Duration::hours(25), now), "1 day");
+        assert_eq!(time_until(now + Duration::minutes(150), now), "2 hours");
+        assert_eq!(time_until(now + Duration::minutes(45), now), "45 minutes");
+        assert_eq!(time_until(now + Duration::seconds(10), now), "1 minute");
+    }
+
+    /// Returns the template data for a poll, as seen by a user who hasn't voted.
+    async fn rendered_poll(pool: &SqlitePool, poll_id: i64) -> serde_json::Value {
+        let poll = get_poll_by_id(pool, poll_id).await.unwrap();
+        let options = get_poll_options(pool, poll_id).await.unwrap();
+        format_poll_for_template(&poll, &options, &[])
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn lock_countdown_is_shown_until_the_lock(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let mut form = poll_form("Next game", "Catan\nAzul");
+        let lock_at = Utc::now() + Duration::hours(3);
+        form.votes_lock_at = Some(lock_at.format("%Y-%m-%dT%H:%M").to_string());
+        let poll_id = create_poll(&pool, &form, creator).await?;
+
+        let open = rendered_poll(&pool, poll_id).await;
+        assert_eq!(open["is_locked"], false);
+        assert!(open["votes_lock_in"].as_str().unwrap().ends_with("hours"));
+
+        sqlx::query("UPDATE polls SET votes_lock_at = ? WHERE id = ?")
+            .bind(Utc::now() - Duration::minutes(1))
+            .bind(poll_id)
+            .execute(&pool)
+            .await?;
+        let locked = rendered_poll(&pool, poll_id).await;
+        assert_eq!(locked["is_locked"], true);
+        assert!(locked.get("votes_lock_in").is_none());
+        Ok(())
+    }
+
+    /// Returns the text of each of a poll's options, in order.
+    async fn option_texts(pool: &SqlitePool, poll_id: i64) -> Vec<String> {
+        get_poll_options(pool, poll_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|option| option.text)
+            .collect()
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn options_split_on_the_chosen_separator(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+
+        let mut form = poll_form("Snacks", " Chips, salsa \n\nPretzels\n");
+        let poll_id = create_poll(&pool, &form, creator).await?;
+        assert_eq!(option_texts(&pool, poll_id).await, ["Chips, salsa", "Pretzels"]);
+
+        // Comma is the default separator
+        form.title = "Games".to_string();
+        form.options = "Catan, Azul,,".to_string();
+        form.options_separator = None;
+        let poll_id = create_poll(&pool, &form, creator).await?;
+        assert_eq!(option_texts(&pool, poll_id).await, ["Catan", "Azul"]);
+
+        form.options_separator = Some("semicolon".to_string());
+        let err = create_poll(&pool, &form, creator).await.unwrap_err();
+        assert_eq!(message(err), "Unknown options separator \"semicolon\"");
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn archived_polls_leave_the_listings(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let other = add_user(&pool, "other", false).await;
+        let open = create_poll(&pool, &poll_form("Open", "Catan\nAzul"), creator).await?;
+        let closed = create_poll(&pool, &poll_form("Closed", "Catan\nAzul"), creator).await?;
+        set_expiry(&pool, closed, Utc::now() - Duration::days(1)).await;
+        let ids = |polls: Vec<PollWithCreator>| {
+            polls.into_iter().map(|poll| poll.id).collect::<Vec<_>>()
+        };
+
+        let err = set_poll_archived(&pool, open, true, other, false).await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::ColumnDecode { index, .. } if index == "forbidden"));
+
+        set_poll_archived(&pool, open, true, creator, false).await?;
+        set_poll_archived(&pool, closed, true, creator, false).await?;
+        assert!(get_active_polls(&pool).await?.is_empty());
+        assert!(get_expired_polls(&pool).await?.is_empty());
+        assert_eq!(ids(get_archived_polls(&pool).await?).len(), 2);
+        // Still reachable by direct link
+        assert!(get_poll_by_id(&pool, open).await?.archived);
+
+        set_poll_archived(&pool, open, false, creator, false).await?;
+        assert_eq!(ids(get_active_polls(&pool).await?), [open]);
+        assert_eq!(ids(get_archived_polls(&pool).await?), [closed]);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn copied_options_replace_typed_repeats(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let source = create_poll(
+            &pool,
+            &poll_form("Last week", "Catan|https://example.com/catan.jpg|max=4\nAzul"),
+            creator,
+        )
+        .await?;
+
+        let mut form = poll_form("This week", "azul\nWingspan");
+        form.copy_options_from = Some(source);
+        let poll_id = create_poll(&pool, &form, creator).await?;
+        assert_eq!(option_texts(&pool, poll_id).await, ["Catan", "Azul", "Wingspan"]);
+        let catan = &get_poll_options(&pool, poll_id).await?[0];
+        assert_eq!(catan.image_url.as_deref(), Some("https://example.com/catan.jpg"));
+        assert_eq!(catan.max_votes, Some(4));
+
+        form.title = "Next week".to_string();
+        form.copy_options_from = Some(source + 100);
+        let err = create_poll(&pool, &form, creator).await.unwrap_err();
+        assert_eq!(message(err), "The poll to copy options from was not found");
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn creator_opted_out_can_neither_vote_nor_unvote(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", true).await;
+        let voter = add_user(&pool, "voter", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul"), creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+        vote_on_poll(&pool, poll_id, catan, creator, None).await?;
+
+        // Being an admin doesn't exempt the creator
+        sqlx::query("UPDATE polls SET creator_can_vote = 0 WHERE id = ?")
+            .bind(poll_id)
+            .execute(&pool)
+            .await?;
+        let expected = "The creator of this poll can't vote on it.";
+        let azul = option_id(&pool, poll_id, "Azul").await;
+        let err = vote_on_poll(&pool, poll_id, azul, creator, None).await.unwrap_err();
+        assert_eq!(message(err), expected);
+        let err = vote_on_poll(&pool, poll_id, catan, creator, None).await.unwrap_err();
+        assert_eq!(message(err), expected);
+
+        assert_eq!(vote_on_poll(&pool, poll_id, azul, voter, None).await?, VoteOutcome::FirstVote);
+
+        let mut form = poll_form("Snacks", "Chips\nPretzels");
+        form.creator_can_vote = Some(false);
+        let poll_id = create_poll(&pool, &form, creator).await?;
+        assert!(!get_poll_by_id(&pool, poll_id).await?.creator_can_vote);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn runoff_ranks_options_by_weighted_votes(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let form = poll_form("Next game", "Catan\nWingspan\nRoot");
+        let poll_id = create_poll(&pool, &form, creator).await?;
+        for (name, text) in [("ann", "Wingspan"), ("bob", "Wingspan"), ("cat", "Root")] {
+            let voter = add_user(&pool, name, false).await;
+            let option = option_id(&pool, poll_id, text).await;
+            vote_on_poll(&pool, poll_id, option, voter, None).await?;
+        }
+        sqlx::query("UPDATE users SET vote_weight = 3 WHERE username = 'cat'")
+            .execute(&pool)
+            .await?;
+
+        // Root's single vote counts 3 times, beating Wingspan's two
+        let tomorrow = (Utc::now() + Duration::days(1)).format("%Y-%m-%dT%H:%M").to_string();
+        let runoff = create_runoff(&pool, poll_id, 2, &tomorrow, creator, false).await?;
+        assert_eq!(option_texts(&pool, runoff).await, ["Root", "Wingspan"]);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn option_comments_are_threaded_under_their_option(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let other = add_user(&pool, "other", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul"), creator).await?;
+        let other_poll = create_poll(&pool, &poll_form("Snacks", "Chips"), creator).await?;
+        let azul = option_id(&pool, poll_id, "Azul").await;
+        let chips = option_id(&pool, other_poll, "Chips").await;
+
+        add_comment(&pool, poll_id, Some(azul), other, "  Quick to teach  ").await?;
+        add_comment(&pool, poll_id, None, creator, "Pick by Friday").await?;
+
+        let err = add_comment(&pool, poll_id, Some(chips), other, "Wrong poll").await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::RowNotFound), "{}", err);
+        let err = add_comment(&pool, poll_id, None, other, "   ").await.unwrap_err();
+        assert_eq!(message(err), "Comments can't be empty.");
+        let long = "x".repeat(MAX_COMMENT_CHARS + 1);
+        assert!(add_comment(&pool, poll_id, None, other, &long).await.is_err());
+
+        let mut poll_json = rendered_poll(&pool, poll_id).await;
+        attach_comments(&mut poll_json, &get_poll_comments(&pool, poll_id).await?);
+        assert_eq!(poll_json["options"][0]["comments"], serde_json::json!([]));
+        assert_eq!(poll_json["options"][1]["comments"][0]["body"], "Quick to teach");
+        assert_eq!(poll_json["options"][1]["comments"][0]["username"], "other");
+        assert_eq!(poll_json["comments"].as_array().unwrap().len(), 1);
+        assert_eq!(poll_json["comments"][0]["body"], "Pick by Friday");
+
+        // Only the other user's comment notifies the creator
+        let notified: Vec<i64> =
+            sqlx::query_scalar("SELECT user_id FROM notifications WHERE kind = 'comment'")
+                .fetch_all(&pool)
+                .await?;
+        assert_eq!(notified, [creator]);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn adding_options_reopens_only_when_extending(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan"), creator).await?;
+        set_expiry(&pool, poll_id, Utc::now() - Duration::days(1)).await;
+        let is_open = || async {
+            let poll = get_poll_by_id(&pool, poll_id).await.unwrap();
+            !get_poll_meta(&poll).is_expired
+        };
+        let form = |options: &str, also_extend: bool, expires_at: DateTime<Utc>| NewOptionsForm {
+            options: options.to_string(),
+            also_extend,
+            expires_at: Some(expires_at.format("%Y-%m-%dT%H:%M").to_string()),
+        };
+        let next_week = Utc::now() + Duration::days(7);
+
+        add_poll_options(&pool, poll_id, &form("Azul", false, next_week), creator).await?;
+        assert!(!is_open().await);
+
+        let yesterday = Utc::now() - Duration::days(1);
+        let err = add_poll_options(&pool, poll_id, &form("Root", true, yesterday), creator)
+            .await
+            .unwrap_err();
+        assert_eq!(message(err), "New expiration time must be in the future");
+
+        // A rejected option leaves the poll closed as well
+        assert!(add_poll_options(&pool, poll_id, &form("catan", true, next_week), creator)
+            .await
+            .is_err());
+        assert!(!is_open().await);
+        assert_eq!(option_texts(&pool, poll_id).await, ["Catan", "Azul"]);
+
+        add_poll_options(&pool, poll_id, &form("Wingspan", true, next_week), creator).await?;
+        assert!(is_open().await);
+        assert_eq!(option_texts(&pool, poll_id).await, ["Catan", "Azul", "Wingspan"]);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn creator_is_told_of_comments_and_quorum(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let mut form = poll_form("Next game", "Catan\nAzul");
+        form.quorum = Some(2);
+        let poll_id = create_poll(&pool, &form, creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+        let azul = option_id(&pool, poll_id, "Azul").await;
+
+        // The creator's own vote brings no notification
+        vote_on_poll(&pool, poll_id, catan, creator, Some("Mine")).await?;
+        for name in ["ann", "bob"] {
+            let voter = add_user(&pool, name, false).await;
+            vote_on_poll(&pool, poll_id, azul, voter, Some("Short game")).await?;
+        }
+
+        let notifications = notify::get_recent(&pool, creator, 10).await?;
+        let mut received: Vec<(&str, &str)> = notifications
+            .iter()
+            .map(|n| (n.kind.as_str(), n.body.as_str()))
+            .collect();
+        received.reverse();
+        assert_eq!(
+            received,
+            [
+                ("vote_comment", "ann commented on their vote in \"Next game\": Short game"),
+                ("quorum", "Your poll \"Next game\" reached its quorum of 2 voters"),
+                ("vote_comment", "bob commented on their vote in \"Next game\": Short game"),
+            ]
+        );
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn deleted_poll_can_be_restored_once(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let other = add_user(&pool, "other", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul"), creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+        vote_on_poll(&pool, poll_id, catan, other, None).await?;
+        add_comment(&pool, poll_id, Some(catan), other, "Classic").await?;
+
+        delete_poll(&pool, poll_id, creator, false).await?;
+        assert!(matches!(get_poll_by_id(&pool, poll_id).await, Err(sqlx::Error::RowNotFound)));
+        let deleted = tombstone::deleted_by(creator);
+        assert_eq!(deleted.iter().map(|poll| poll.id).collect::<Vec<_>>(), [poll_id]);
+
+        // Someone else's attempt leaves the undo available
+        let err = undo_delete_poll(&pool, poll_id, other, false).await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::ColumnDecode { index, .. } if index == "forbidden"));
+
+        undo_delete_poll(&pool, poll_id, creator, false).await?;
+        assert_eq!(get_poll_by_id(&pool, poll_id).await?.title, "Next game");
+        assert_eq!(option_id(&pool, poll_id, "Catan").await, catan);
+        assert_eq!(get_poll_options(&pool, poll_id).await?[0].vote_count, 1);
+        assert_eq!(get_poll_comments(&pool, poll_id).await?[0].body, "Classic");
+
+        let err = undo_delete_poll(&pool, poll_id, creator, false).await.unwrap_err();
+        assert!(message(err).starts_with("This poll can no longer be restored."));
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn write_in_adds_the_option_with_its_vote(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let voter = add_user(&pool, "voter", false).await;
+        let closed = create_poll(&pool, &poll_form("Snacks", "Chips"), creator).await?;
+        let err = add_write_in(&pool, closed, "Pretzels", voter).await.unwrap_err();
+        assert_eq!(message(err), "This poll doesn't accept write-ins.");
+
+        let mut form = poll_form("Next game", "Catan");
+        form.allow_write_in = true;
+        form.multi_select = Some(true);
+        let poll_id = create_poll(&pool, &form, creator).await?;
+
+        assert_eq!(add_write_in(&pool, poll_id, " Azul ", voter).await?, VoteOutcome::FirstVote);
+        let azul = get_poll_options(&pool, poll_id).await?.pop().unwrap();
+        assert_eq!((azul.text.as_str(), azul.vote_count), ("Azul", 1));
+        let suggested_by: Option<i64> =
+            sqlx::query_scalar("SELECT suggested_by FROM options WHERE id = ?")
+                .bind(azul.id)
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(suggested_by, Some(voter));
+
+        let err = add_write_in(&pool, poll_id, "catan", voter).await.unwrap_err();
+        assert_eq!(message(err), "That option is already in the poll. Vote for it instead.");
+        let err = add_write_in(&pool, poll_id, "Root\nGo", voter).await.unwrap_err();
+        assert_eq!(message(err), "Suggest one option at a time");
+
+        for text in ["Root", "Go"] {
+            add_write_in(&pool, poll_id, text, voter).await?;
+        }
+        let err = add_write_in(&pool, poll_id, "Dixit", voter).await.unwrap_err();
+        assert_eq!(
+            message(err),
+            format!("You can suggest at most {} options per poll", MAX_WRITE_INS_PER_USER)
+        );
+        assert_eq!(option_texts(&pool, poll_id).await, ["Catan", "Azul", "Root", "Go"]);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn range_includes_both_ends_and_optionally_text(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let options = "2030-03-01T19:00\n2030-03-02T19:00\n2030-03-03T19:00\nWhenever";
+        let poll_id = create_poll(&pool, &poll_form("When?", options), creator).await?;
+        let in_range = |from: Option<&'static str>, to: Option<&'static str>, include_text| {
+            let pool = pool.clone();
+            async move {
+                get_options_in_range(&pool, poll_id, from, to, include_text)
+                    .await
+                    .map(|options| options.into_iter().map(|o| o.text).collect::<Vec<_>>())
+            }
+        };
+
+        let from = Some("2030-03-01T19:00");
+        let to = Some("2030-03-02T19:00:00Z");
+        assert_eq!(in_range(from, to, false).await?, ["2030-03-01T19:00", "2030-03-02T19:00"]);
+        assert_eq!(
+            in_range(Some("2030-03-03T00:00"), None, true).await?,
+            ["2030-03-03T19:00", "Whenever"]
+        );
+        assert_eq!(in_range(None, None, false).await?.len(), 4);
+
+        let err = in_range(to, from, false).await.unwrap_err();
+        assert_eq!(message(err), "The start of the range must not be after its end");
+        let err = in_range(Some("March"), None, false).await.unwrap_err();
+        assert_eq!(message(err), "Invalid from date \"March\"; use YYYY-MM-DDTHH:MM");
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn polls_are_capped_at_the_configured_option_count(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let max = config::get().max_options_per_poll;
+        let games = |range: std::ops::RangeInclusive<i64>| {
+            range.map(|n| format!("Game {}", n)).collect::<Vec<_>>()
+        };
+
+        let form = poll_form("Too many", &games(1..=max + 1).join("\n"));
+        let err = create_poll(&pool, &form, creator).await.unwrap_err();
+        assert!(message(err).starts_with(&format!("A poll can have at most {} options", max)));
+
+        let form = poll_form("Just enough", &games(1..=max - 1).join("\n"));
+        let poll_id = create_poll(&pool, &form, creator).await?;
+        let add = |options: Vec<String>| NewOptionsForm {
+            options: options.join(","),
+            also_extend: false,
+            expires_at: None,
+        };
+        let err = add_poll_options(&pool, poll_id, &add(games(max..=max + 1)), creator)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            message(err),
+            format!(
+                "A poll can have at most {} options (this poll has {}, and 2 more were given)",
+                max,
+                max - 1
+            )
+        );
+
+        add_poll_options(&pool, poll_id, &add(games(max..=max)), creator).await?;
+        assert_eq!(option_texts(&pool, poll_id).await.len() as i64, max);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn duplicate_titles_need_confirmation(pool: SqlitePool) -> sqlx::Result<()> {
+        let alice = add_user(&pool, "alice", false).await;
+        let bob = add_user(&pool, "bob", false).await;
+        let first = create_poll(&pool, &poll_form("Game night", "Catan"), alice).await?;
+
+        let repeat = poll_form("  game NIGHT ", "Azul");
+        let err = create_poll(&pool, &repeat, alice).await.unwrap_err();
+        assert!(matches!(&err, sqlx::Error::ColumnDecode { index, .. } if index == "duplicate"));
+        assert_eq!(message(err), "You already have an open poll titled \"Game night\"");
+        create_poll(&pool, &repeat, bob).await?;
+
+        let confirmed = NewPollForm {
+            confirm_duplicate: true,
+            ..poll_form("  game NIGHT ", "Azul")
+        };
+        create_poll(&pool, &confirmed, alice).await?;
+
+        // Expired polls never match, so only the confirmed repeat still does
+        set_expiry(&pool, first, Utc::now() - Duration::days(1)).await;
+        let found = find_duplicate_poll(&pool, alice, "Game Night").await?;
+        assert_eq!(found.map(|(_, title)| title), Some("  game NIGHT ".to_string()));
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn large_polls_list_only_the_first_voters(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul"), creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+        let voters = config::get().voters_summary_threshold + 1;
+        sqlx::query(
+            "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < ?)
+             INSERT INTO users (username, password_hash) SELECT 'voter' || i, '!' FROM n",
+        )
+        .bind(voters)
+        .execute(&pool)
+        .await?;
+        sqlx::query(
+            "INSERT INTO votes (user_id, option_id)
+             SELECT id, ? FROM users WHERE username LIKE 'voter%'",
+        )
+        .bind(catan)
+        .execute(&pool)
+        .await?;
+
+        let details = get_poll_voters_view(&pool, poll_id).await?;
+        assert!(details.summarized);
+        assert_eq!(details.total_voters, voters);
+        let option = details.options_with_voters.iter().find(|o| o.id == catan).unwrap();
+        assert_eq!(option.vote_count, voters);
+        assert_eq!(option.voters.len() as i64, SUMMARY_VOTERS_PER_OPTION);
+        assert_eq!(option.more_voters, voters - SUMMARY_VOTERS_PER_OPTION);
+        assert!(!get_poll_voting_details(&pool, poll_id).await?.summarized);
+
+        let first = get_option_voters_page(&pool, poll_id, catan, 0).await?;
+        assert_eq!((first.page, first.total), (1, voters));
+        assert_eq!(first.voters.len() as i64, OPTION_VOTERS_PAGE_SIZE);
+        assert_eq!(first.voters[0].username, "voter1");
+        let last_page = (voters - 1) / OPTION_VOTERS_PAGE_SIZE + 1;
+        let last = get_option_voters_page(&pool, poll_id, catan, last_page).await?;
+        assert_eq!(last.voters.len() as i64, voters - (last_page - 1) * OPTION_VOTERS_PAGE_SIZE);
+        assert!(matches!(
+            get_option_voters_page(&pool, poll_id + 1, catan, 1).await,
+            Err(sqlx::Error::RowNotFound)
+        ));
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn timeline_fills_quiet_buckets(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan"), creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+        assert!(get_poll_timeline(&pool, poll_id, TimelineBucket::Hourly).await?.is_empty());
+
+        let vote_times = [
+            "2024-05-01T10:15:00Z",
+            "2024-05-01T10:40:00Z",
+            "2024-05-01T13:05:00Z",
+            "2024-05-02T09:00:00Z",
+        ];
+        let at = |timestamp: &str| timestamp.parse::<DateTime<Utc>>().unwrap();
+        for (n, created_at) in vote_times.iter().enumerate() {
+            let voter = add_user(&pool, &format!("voter{}", n), false).await;
+            sqlx::query("INSERT INTO votes (user_id, option_id, created_at) VALUES (?, ?, ?)")
+                .bind(voter)
+                .bind(catan)
+                .bind(at(created_at))
+                .execute(&pool)
+                .await?;
+        }
+        let counts = |points: &[TimelinePoint]| {
+            points.iter().map(|point| point.cumulative_votes).collect::<Vec<_>>()
+        };
+
+        // Hourly buckets run without gaps from 10:00 on day one to 09:00 on day two
+        let hourly = get_poll_timeline(&pool, poll_id, TimelineBucket::Hourly).await?;
+        assert_eq!(hourly.len(), 24);
+        assert_eq!(hourly[0].timestamp, at("2024-05-01T10:00:00Z"));
+        assert_eq!(hourly[23].timestamp, at("2024-05-02T09:00:00Z"));
+        assert_eq!(counts(&hourly[..5]), [2, 2, 2, 3, 3]);
+        assert_eq!(hourly[23].cumulative_votes, 4);
+
+        let daily = get_poll_timeline(&pool, poll_id, TimelineBucket::Daily).await?;
+        assert_eq!(daily[0].timestamp, at("2024-05-01T00:00:00Z"));
+        assert_eq!(counts(&daily), [3, 4]);
+        Ok(())
+    }
+
+    #[test]
+    fn timeline_bucket_accepts_short_names() {
+        assert_eq!(TimelineBucket::from_param(" Hour"), Some(TimelineBucket::Hourly));
+        assert_eq!(TimelineBucket::from_param("daily"), Some(TimelineBucket::Daily));
+        assert_eq!(TimelineBucket::from_param("weekly"), None);
+    }
+
+    /// Returns the bodies of the `results` notifications sent so far.
+    async fn results_notifications(pool: &SqlitePool) -> Vec<String> {
+        sqlx::query_scalar("SELECT body FROM notifications WHERE kind = 'results' ORDER BY user_id")
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn closing_announces_the_winner_to_each_voter(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let first = add_user(&pool, "first", false).await;
+        let second = add_user(&pool, "second", false).await;
+        let poll_id = create_poll(&pool, &poll_form("Next game", "Catan\nAzul"), creator).await?;
+        let catan = option_id(&pool, poll_id, "Catan").await;
+        let azul = option_id(&pool, poll_id, "Azul").await;
+        for (voter, option) in [(first, catan), (first, azul), (second, catan)] {
+            vote_on_poll(&pool, poll_id, option, voter, None).await?;
+        }
+        sqlx::query("UPDATE polls SET votes_lock_at = ? WHERE id = ?")
+            .bind(Utc::now() + Duration::hours(12))
+            .bind(poll_id)
+            .execute(&pool)
+            .await?;
+
+        let err = close_and_announce(&pool, poll_id, second, false).await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::ColumnDecode { index, .. } if index == "forbidden"));
+        assert_eq!(close_and_announce(&pool, poll_id, creator, false).await?, 2);
+
+        let poll = get_poll_by_id(&pool, poll_id).await?;
+        assert!(poll.expires_at <= Utc::now());
+        assert!(poll.votes_lock_at.unwrap() <= Utc::now());
+        let err = close_and_announce(&pool, poll_id, creator, false).await.unwrap_err();
+        assert_eq!(message(err), "This poll has already closed");
+
+        // The announcements go out in the background, one per voter
+        let mut sent = results_notifications(&pool).await;
+        for _ in 0..50 {
+            if sent.len() == 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            sent = results_notifications(&pool).await;
+        }
+        let winner = "Voting on \"Next game\" has closed: \"Catan\" won with 2 votes";
+        assert_eq!(sent, [winner, winner]);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn announcement_names_every_tied_option(pool: SqlitePool) -> sqlx::Result<()> {
+        let creator = add_user(&pool, "creator", false).await;
+        let voter = add_user(&pool, "voter", false).await;
+        let form = poll_form("Next game", "Catan\nAzul\nRoot");
+        let poll_id = create_poll(&pool, &form, creator).await?;
+        for text in ["Catan", "Azul"] {
+            vote_on_poll(&pool, poll_id, option_id(&pool, poll_id, text).await, voter, None).await?;
+        }
+
+        announce_results(&pool, poll_id, &[voter]).await;
+        assert_eq!(
+            results_notifications(&pool).await,
+            ["Voting on \"Next game\" has closed: \"Catan\", \"Azul\" tied with 1 vote each"]
+        );
+        Ok(())
+    }
 }
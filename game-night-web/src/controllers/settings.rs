@@ -0,0 +1,316 @@
+//! # Settings Controller Module
+//!
+//! This module manages runtime settings that administrators can change
+//! without restarting the application. Settings are persisted in the
+//! `settings` table and cached in memory, with the cache invalidated
+//! whenever a setting is changed through this module.
+//!
+//! ## Caching
+//! Each cached setting carries a generation counter that every change
+//! bumps. A reader that missed the cache only stores what it loaded if the
+//! generation is unchanged, so a value read from the database just before
+//! a change can't overwrite the invalidation.
+//!
+//! ## Key Functions
+//! - Reading whether new poll creation is enabled
+//! - Toggling poll creation on or off
+//! - Reading, setting, and clearing the site announcement
+
+use lazy_static::lazy_static;
+use sqlx::SqlitePool;
+use std::sync::RwLock;
+
+use crate::controllers::audit;
+use crate::models::SiteAnnouncement;
+
+/// Settings key controlling whether new polls may be created.
+const POLL_CREATION_ENABLED: &str = "poll_creation_enabled";
+
+/// Settings key holding the site announcement text.
+const SITE_ANNOUNCEMENT: &str = "site_announcement";
+
+/// Settings key controlling whether the site announcement is shown.
+const SITE_ANNOUNCEMENT_ACTIVE: &str = "site_announcement_active";
+
+/// In-memory copy of a setting, invalidated when the setting changes.
+struct SettingCache<T> {
+    /// The cached value and the generation it belongs to
+    state: RwLock<(Option<T>, u64)>,
+}
+
+impl<T: Clone> SettingCache<T> {
+    /// Creates an empty cache.
+    fn new() -> Self {
+        SettingCache {
+            state: RwLock::new((None, 0)),
+        }
+    }
+
+    /// Returns the cached value, or the current generation to pass to
+    /// [`SettingCache::fill`] once the value has been loaded.
+    fn get(&self) -> Result<T, u64> {
+        let (value, generation) = &*self.state.read().unwrap();
+        value.clone().ok_or(*generation)
+    }
+
+    /// Stores a value loaded from the database, unless the setting changed
+    /// since `generation` was read.
+    fn fill(&self, generation: u64, value: T) {
+        let mut state = self.state.write().unwrap();
+        if state.1 == generation {
+            state.0 = Some(value);
+        }
+    }
+
+    /// Drops the cached value after the setting changed.
+    fn invalidate(&self) {
+        let mut state = self.state.write().unwrap();
+        state.0 = None;
+        state.1 += 1;
+    }
+}
+
+lazy_static! {
+    /// Cached value of the poll creation flag.
+    static ref POLL_CREATION_CACHE: SettingCache<bool> = SettingCache::new();
+    /// Cached site announcement.
+    static ref ANNOUNCEMENT_CACHE: SettingCache<SiteAnnouncement> = SettingCache::new();
+}
+
+/// Inserts or replaces a single setting.
+///
+/// # Arguments
+/// * `executor` - Connection or transaction to write with
+/// * `key` - Settings key
+/// * `value` - New value
+async fn upsert_setting<'e, E>(executor: E, key: &str, value: &str) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        "INSERT INTO settings (key, value, updated_at) VALUES (?, ?, CURRENT_TIMESTAMP)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+    )
+    .bind(key)
+    .bind(value)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Returns whether new poll creation is currently enabled.
+///
+/// The value is read from the database on first use and cached afterwards.
+/// A missing setting is treated as enabled.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(bool)` - Whether users may create new polls
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn poll_creation_enabled(pool: &SqlitePool) -> Result<bool, sqlx::Error> {
+    let generation = match POLL_CREATION_CACHE.get() {
+        Ok(enabled) => return Ok(enabled),
+        Err(generation) => generation,
+    };
+
+    let value: Option<String> = sqlx::query_scalar("SELECT value FROM settings WHERE key = ?")
+        .bind(POLL_CREATION_ENABLED)
+        .fetch_optional(pool)
+        .await?;
+    let enabled = value.is_none_or(|value| value == "true");
+
+    POLL_CREATION_CACHE.fill(generation, enabled);
+    Ok(enabled)
+}
+
+/// Enables or disables new poll creation (admin functionality).
+///
+/// Voting on and viewing existing polls are unaffected.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `enabled` - Whether poll creation should be allowed
+/// * `admin_id` - ID of the admin making the change
+///
+/// # Returns
+/// * `Ok(())` - Setting saved and cache invalidated
+/// * `Err(sqlx::Error)` - Database error if the update fails
+pub async fn set_poll_creation_enabled(
+    pool: &SqlitePool,
+    enabled: bool,
+    admin_id: i64,
+) -> Result<(), sqlx::Error> {
+    upsert_setting(pool, POLL_CREATION_ENABLED, &enabled.to_string()).await?;
+
+    POLL_CREATION_CACHE.invalidate();
+
+    let action = if enabled { "enabled" } else { "disabled" };
+    audit::record(
+        pool,
+        Some(admin_id),
+        "settings.poll_creation",
+        &format!("Poll creation {}", action),
+    )
+    .await;
+
+    log::info!("Poll creation {} by admin {}", action, admin_id);
+    Ok(())
+}
+
+/// Returns the current site announcement.
+///
+/// The value is read from the database on first use and cached afterwards.
+/// A missing announcement is treated as empty and inactive.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(SiteAnnouncement)` - The announcement text and whether it is shown
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn site_announcement(pool: &SqlitePool) -> Result<SiteAnnouncement, sqlx::Error> {
+    let generation = match ANNOUNCEMENT_CACHE.get() {
+        Ok(announcement) => return Ok(announcement),
+        Err(generation) => generation,
+    };
+
+    let rows = sqlx::query_as::<_, (String, String)>(
+        "SELECT key, value FROM settings WHERE key IN (?, ?)",
+    )
+    .bind(SITE_ANNOUNCEMENT)
+    .bind(SITE_ANNOUNCEMENT_ACTIVE)
+    .fetch_all(pool)
+    .await?;
+
+    let mut announcement = SiteAnnouncement::default();
+    for (key, value) in rows {
+        match key.as_str() {
+            SITE_ANNOUNCEMENT => announcement.message = value,
+            SITE_ANNOUNCEMENT_ACTIVE => announcement.active = value == "true",
+            _ => {}
+        }
+    }
+
+    ANNOUNCEMENT_CACHE.fill(generation, announcement.clone());
+    Ok(announcement)
+}
+
+/// Sets or clears the site announcement (admin functionality).
+///
+/// An empty message clears the announcement, regardless of `active`.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `message` - Announcement text
+/// * `active` - Whether the announcement should be shown
+/// * `admin_id` - ID of the admin making the change
+///
+/// # Returns
+/// * `Ok(())` - Announcement saved and cache invalidated
+/// * `Err(sqlx::Error)` - Database error if the update fails
+pub async fn set_site_announcement(
+    pool: &SqlitePool,
+    message: &str,
+    active: bool,
+    admin_id: i64,
+) -> Result<(), sqlx::Error> {
+    let message = message.trim();
+    let active = active && !message.is_empty();
+
+    let mut tx = pool.begin().await?;
+    upsert_setting(&mut *tx, SITE_ANNOUNCEMENT, message).await?;
+    upsert_setting(&mut *tx, SITE_ANNOUNCEMENT_ACTIVE, &active.to_string()).await?;
+    tx.commit().await?;
+
+    ANNOUNCEMENT_CACHE.invalidate();
+
+    let details = if message.is_empty() {
+        "Announcement cleared".to_string()
+    } else if active {
+        format!("Announcement shown: {}", message)
+    } else {
+        format!("Announcement saved but hidden: {}", message)
+    };
+    audit::record(pool, Some(admin_id), "settings.announcement", &details).await;
+
+    log::info!("{} by admin {}", details, admin_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_returns_filled_value() {
+        let cache = SettingCache::new();
+        let generation = cache.get().unwrap_err();
+
+        cache.fill(generation, true);
+
+        assert_eq!(cache.get(), Ok(true));
+    }
+
+    #[test]
+    fn invalidate_drops_cached_value() {
+        let cache = SettingCache::new();
+        cache.fill(cache.get().unwrap_err(), true);
+
+        cache.invalidate();
+
+        assert!(cache.get().is_err());
+    }
+
+    #[test]
+    fn stale_load_is_not_cached() {
+        let cache = SettingCache::new();
+        // A reader misses the cache and starts loading the old value...
+        let generation = cache.get().unwrap_err();
+        // ...while the setting is changed
+        cache.invalidate();
+        cache.fill(generation, true);
+
+        assert!(cache.get().is_err());
+    }
+
+    /// Reads a setting straight from the database, bypassing the caches
+    /// shared by every test.
+    async fn stored(pool: &SqlitePool, key: &str) -> String {
+        sqlx::query_scalar("SELECT value FROM settings WHERE key = ?")
+            .bind(key)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn announcement_is_trimmed_and_cleared_when_empty(pool: SqlitePool) -> sqlx::Result<()> {
+        let admin = sqlx::query("INSERT INTO users (username, password_hash, is_admin) VALUES ('admin', '!', 1)")
+            .execute(&pool)
+            .await?
+            .last_insert_rowid();
+
+        set_site_announcement(&pool, "  Pizza at 6  ", true, admin).await?;
+        assert_eq!(stored(&pool, SITE_ANNOUNCEMENT).await, "Pizza at 6");
+        assert_eq!(stored(&pool, SITE_ANNOUNCEMENT_ACTIVE).await, "true");
+
+        set_site_announcement(&pool, "   ", true, admin).await?;
+        assert_eq!(stored(&pool, SITE_ANNOUNCEMENT).await, "");
+        assert_eq!(stored(&pool, SITE_ANNOUNCEMENT_ACTIVE).await, "false");
+        Ok(())
+    }
+
+    #[test]
+    fn only_active_announcements_are_shown() {
+        let announcement = |message: &str, active| SiteAnnouncement {
+            message: message.to_string(),
+            active,
+        };
+        assert_eq!(announcement("Pizza at 6", true).visible_message(), Some("Pizza at 6"));
+        assert_eq!(announcement("Pizza at 6", false).visible_message(), None);
+        assert_eq!(announcement("", true).visible_message(), None);
+    }
+}
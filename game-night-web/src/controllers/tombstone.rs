@@ -0,0 +1,238 @@
+//! # Tombstone Controller Module
+//!
+//! This module keeps a short-lived, in-memory copy of each deleted poll so
+//! the deletion can be undone for a few seconds afterwards.
+//!
+//! ## Key Functions
+//! - Copying a poll's rows before it is deleted
+//! - Holding the copy for [`UNDO_WINDOW_SECONDS`]
+//! - Re-inserting the copy to undo the deletion
+//!
+//! ## Storage
+//! Rows are copied with `SELECT *` and kept as raw SQLite values, so they
+//! are restored exactly, original IDs included, without this module needing
+//! to know every column. Tombstones live only in memory: they are lost on
+//! restart and pruned once the undo window has passed.
+
+use lazy_static::lazy_static;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Column, Row, SqliteConnection, TypeInfo, ValueRef};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::models::DeletedPoll;
+
+/// How long a deleted poll can be restored, in seconds.
+pub const UNDO_WINDOW_SECONDS: u64 = 30;
+
+/// Tables holding a poll's data, in the order they must be restored, with
+/// the condition selecting the poll's rows (bound to the poll ID).
+const POLL_TABLES: [(&str, &str); 5] = [
+    ("polls", "id = ?"),
+    ("options", "poll_id = ?"),
+    ("votes", "option_id IN (SELECT id FROM options WHERE poll_id = ?)"),
+    ("comments", "poll_id = ?"),
+    ("poll_results_snapshots", "poll_id = ?"),
+];
+
+/// A single column value as stored by SQLite.
+#[derive(Debug, Clone)]
+enum StoredValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+/// A copied row: column names alongside their values.
+#[derive(Debug, Clone)]
+struct StoredRow {
+    columns: Vec<String>,
+    values: Vec<StoredValue>,
+}
+
+/// Everything needed to restore a deleted poll.
+#[derive(Debug, Clone)]
+pub struct Tombstone {
+    /// ID of the user who deleted the poll
+    pub deleted_by: i64,
+    /// Title of the deleted poll
+    pub title: String,
+    /// When the poll was deleted
+    deleted_at: Instant,
+    /// Copied rows, per table, in restore order
+    tables: Vec<(&'static str, Vec<StoredRow>)>,
+}
+
+lazy_static! {
+    /// Tombstones of recently deleted polls, keyed by poll ID.
+    static ref TOMBSTONES: Mutex<HashMap<i64, Tombstone>> = Mutex::new(HashMap::new());
+}
+
+/// Drops tombstones whose undo window has passed.
+fn prune(tombstones: &mut HashMap<i64, Tombstone>) {
+    let window = Duration::from_secs(UNDO_WINDOW_SECONDS);
+    tombstones.retain(|_, tombstone| tombstone.deleted_at.elapsed() < window);
+}
+
+/// Copies a row's values, keeping each one's SQLite storage class.
+fn store_row(row: &SqliteRow) -> Result<StoredRow, sqlx::Error> {
+    let mut columns = Vec::new();
+    let mut values = Vec::new();
+
+    for (index, column) in row.columns().iter().enumerate() {
+        let raw = row.try_get_raw(index)?;
+        let value = if raw.is_null() {
+            StoredValue::Null
+        } else {
+            // The raw value reports its storage class, not the declared type
+            match raw.type_info().name() {
+                "INTEGER" => StoredValue::Integer(row.try_get_unchecked(index)?),
+                "REAL" => StoredValue::Real(row.try_get_unchecked(index)?),
+                "BLOB" => StoredValue::Blob(row.try_get_unchecked(index)?),
+                _ => StoredValue::Text(row.try_get_unchecked(index)?),
+            }
+        };
+        columns.push(column.name().to_string());
+        values.push(value);
+    }
+
+    Ok(StoredRow { columns, values })
+}
+
+/// Copies all of a poll's rows ahead of deleting it.
+///
+/// Run this inside the deleting transaction, before any rows are removed.
+///
+/// # Arguments
+/// * `conn` - Connection or transaction the poll is being deleted with
+/// * `poll_id` - ID of the poll being deleted
+/// * `deleted_by` - ID of the user deleting the poll
+///
+/// # Returns
+/// * `Ok(Tombstone)` - The copied poll, to pass to [`store`] once the deletion commits
+/// * `Err(sqlx::Error)` - Database error, or RowNotFound if the poll doesn't exist
+pub async fn capture(
+    conn: &mut SqliteConnection,
+    poll_id: i64,
+    deleted_by: i64,
+) -> Result<Tombstone, sqlx::Error> {
+    let title: String = sqlx::query_scalar("SELECT title FROM polls WHERE id = ?")
+        .bind(poll_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    let mut tables = Vec::new();
+    for (table, filter) in POLL_TABLES {
+        let rows = sqlx::query(&format!("SELECT * FROM {} WHERE {}", table, filter))
+            .bind(poll_id)
+            .fetch_all(&mut *conn)
+            .await?;
+        let rows = rows.iter().map(store_row).collect::<Result<Vec<_>, _>>()?;
+        tables.push((table, rows));
+    }
+
+    Ok(Tombstone {
+        deleted_by,
+        title,
+        deleted_at: Instant::now(),
+        tables,
+    })
+}
+
+/// Holds a deleted poll's tombstone for the undo window.
+///
+/// # Arguments
+/// * `poll_id` - ID of the deleted poll
+/// * `tombstone` - The poll's rows, from [`capture`]
+pub fn store(poll_id: i64, tombstone: Tombstone) {
+    let mut tombstones = TOMBSTONES.lock().unwrap();
+    prune(&mut tombstones);
+    tombstones.insert(poll_id, tombstone);
+}
+
+/// Takes a deleted poll's tombstone if it is still within the undo window.
+///
+/// The tombstone is removed, so the poll can only be restored once. Put it
+/// back with [`store`] if restoring fails.
+///
+/// # Arguments
+/// * `poll_id` - ID of the deleted poll
+///
+/// # Returns
+/// The tombstone, or `None` if the poll wasn't deleted recently
+pub fn take(poll_id: i64) -> Option<Tombstone> {
+    let mut tombstones = TOMBSTONES.lock().unwrap();
+    prune(&mut tombstones);
+    tombstones.remove(&poll_id)
+}
+
+/// Lists the polls a user deleted that can still be restored.
+///
+/// # Arguments
+/// * `user_id` - ID of the user who deleted the polls
+///
+/// # Returns
+/// The restorable polls, most recently deleted first
+pub fn deleted_by(user_id: i64) -> Vec<DeletedPoll> {
+    let mut tombstones = TOMBSTONES.lock().unwrap();
+    prune(&mut tombstones);
+
+    let window = Duration::from_secs(UNDO_WINDOW_SECONDS);
+    let mut deleted: Vec<(Instant, DeletedPoll)> = tombstones
+        .iter()
+        .filter(|(_, tombstone)| tombstone.deleted_by == user_id)
+        .map(|(poll_id, tombstone)| {
+            let remaining = window.saturating_sub(tombstone.deleted_at.elapsed());
+            (
+                tombstone.deleted_at,
+                DeletedPoll {
+                    id: *poll_id,
+                    title: tombstone.title.clone(),
+                    seconds_left: remaining.as_secs(),
+                },
+            )
+        })
+        .collect();
+    deleted.sort_by_key(|(deleted_at, _)| std::cmp::Reverse(*deleted_at));
+    deleted.into_iter().map(|(_, poll)| poll).collect()
+}
+
+/// Re-inserts a deleted poll's rows, with their original IDs.
+///
+/// # Arguments
+/// * `conn` - Transaction to restore the poll in
+/// * `tombstone` - The poll's rows, from [`take`]
+///
+/// # Returns
+/// * `Ok(())` - All rows re-inserted
+/// * `Err(sqlx::Error)` - Database error if an insert fails
+pub async fn restore(conn: &mut SqliteConnection, tombstone: &Tombstone) -> Result<(), sqlx::Error> {
+    for (table, rows) in &tombstone.tables {
+        for row in rows {
+            let placeholders = vec!["?"; row.columns.len()].join(", ");
+            let sql = format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                table,
+                row.columns.join(", "),
+                placeholders
+            );
+
+            let mut query = sqlx::query(&sql);
+            for value in &row.values {
+                query = match value {
+                    StoredValue::Null => query.bind(None::<i64>),
+                    StoredValue::Integer(value) => query.bind(*value),
+                    StoredValue::Real(value) => query.bind(*value),
+                    StoredValue::Text(value) => query.bind(value.clone()),
+                    StoredValue::Blob(value) => query.bind(value.clone()),
+                };
+            }
+            query.execute(&mut *conn).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -5,25 +5,98 @@
 //!
 //! ## Key Functions
 //! - User login and logout
-//! - User account creation (admin only)
+//! - The welcome page shown on a new user's first login
+//! - User account creation and bulk CSV import (admin only)
 //! - Password change functionality
 //! - User role management (admin promotion/demotion)
+//! - Session revocation (admin force-logout)
+//! - User deletion, following the `USER_DELETE_POLICY` setting (admin only)
+//! - Merging duplicate accounts into one (admin only)
+//! - Disabling inactive accounts and re-enabling them (admin only)
 //! - User statistics and profile information
+//! - Personal data export (a user's own account, polls, and votes)
+//!
+//! ## Failed Logins
+//! A failed login waits for `FAILED_LOGIN_DELAY_MS` (default 200) plus a
+//! random extra of up to `FAILED_LOGIN_JITTER_MS` (default 300) before
+//! responding, to slow down automated password guessing. The wait starts
+//! after the credential lookup has returned its database connection, and
+//! successful logins are not delayed.
+//!
+//! ## Inactive Accounts
+//! When `INACTIVE_ACCOUNT_DAYS` is set above 0, a background task disables
+//! non-admin accounts whose last login is older than that many days.
+//! Accounts that have never logged in are left alone unless
+//! `INACTIVE_INCLUDE_NEVER_LOGGED_IN` is `true`, in which case their
+//! creation time counts as their last activity. Disabled users can't log in
+//! until an admin re-enables them.
 
 use rocket::http::CookieJar;
 use rocket::response::{Flash, Redirect};
 use rocket::uri;
-use sqlx::SqlitePool;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use rocket::futures::StreamExt;
+use sqlx::{SqliteConnection, SqlitePool};
 use log::{info, error};
+use std::time::Duration;
+
+use crate::models::{
+    User, LoginForm, NewUserForm, ChangePasswordForm, PollVoteSummary, PublicUserInfo,
+    RoleChange, UserStatsDetailed, ExportedPoll, ExportedVote,
+};
+use crate::config::{self, Config};
+use crate::auth::{login_user, set_login_cookie, clear_login_cookie, PASSWORD_RESET_NEEDED};
+use crate::controllers::{audit, csv, polls};
+
+/// What happens to a user's polls, votes, and comments when they are deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserDeletePolicy {
+    /// Polls move to the deleting admin; votes, comments, and write-ins move
+    /// to a placeholder account, so poll results don't change
+    Reassign,
+    /// Polls, votes, and comments are deleted with the user
+    Cascade,
+}
+
+impl UserDeletePolicy {
+    /// Returns the policy's name as used in `USER_DELETE_POLICY`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UserDeletePolicy::Reassign => "reassign",
+            UserDeletePolicy::Cascade => "cascade",
+        }
+    }
+}
+
+/// Returns the configured user deletion policy.
+pub fn user_delete_policy() -> UserDeletePolicy {
+    config::get().user_delete_policy
+}
+
+/// Returns how many days without a login disable an account, or `None`
+/// when inactive accounts are never disabled.
+pub fn inactive_account_days() -> Option<i64> {
+    Some(config::get().inactive_account_days).filter(|days| *days > 0)
+}
 
-use crate::models::{User, LoginForm, NewUserForm, ChangePasswordForm};
-use crate::auth::{login_user, set_login_cookie, clear_login_cookie};
+/// Picks how long to wait before answering a failed login: the configured
+/// base delay plus a random jitter of up to the configured maximum.
+fn failed_login_delay(config: &Config) -> Duration {
+    let jitter = match config.failed_login_jitter_ms {
+        0 => 0,
+        max => rand::thread_rng().gen_range(0..=max),
+    };
+    Duration::from_millis(config.failed_login_delay_ms + jitter)
+}
 
 /// Handles user login authentication and session creation.
 /// 
 /// This function verifies the user's credentials against the database,
 /// sets a session cookie upon successful authentication, and redirects
-/// to the dashboard.
+/// to the dashboard, or to the welcome page on a new user's first login
+/// (see [`complete_onboarding`]). Failed attempts are answered only after a short,
+/// randomized delay (see the module docs).
 /// 
 /// # Arguments
 /// * `pool` - Database connection pool
@@ -31,7 +104,7 @@ use crate::auth::{login_user, set_login_cookie, clear_login_cookie};
 /// * `cookies` - Cookie jar for setting session cookies
 /// 
 /// # Returns
-/// * `Ok(Redirect)` - Redirects to dashboard on successful login
+/// * `Ok(Redirect)` - Redirects to dashboard (or the welcome page) on successful login
 /// * `Err(Flash<Redirect>)` - Redirects to login page with error message
 pub async fn login_controller(
     pool: &SqlitePool,
@@ -41,11 +114,28 @@ pub async fn login_controller(
     match login_user(pool, &form.username, &form.password).await {
         Ok(user) => {
             info!("User logged in: {}", user.username);
-            set_login_cookie(cookies, user.id);
+
+            // A failure here shouldn't block the login itself
+            let now = Utc::now();
+            if let Err(err) = sqlx::query("UPDATE users SET last_login_at = ?, last_active_at = ? WHERE id = ?")
+                .bind(now)
+                .bind(now)
+                .bind(user.id)
+                .execute(pool)
+                .await
+            {
+                error!("Failed to record last login for {}: {}", user.username, err);
+            }
+
+            set_login_cookie(cookies, user.id, user.session_epoch);
+            if !user.has_onboarded && config::get().onboarding_enabled {
+                return Ok(Redirect::to(uri!(crate::routes::welcome)));
+            }
             Ok(Redirect::to(uri!(crate::routes::dashboard)))
         }
         Err(err) => {
             error!("Login error: {}", err);
+            tokio::time::sleep(failed_login_delay(config::get())).await;
             Err(Flash::error(
                 Redirect::to(uri!(crate::routes::login_page)),
                 format!("Login failed: {}", err),
@@ -54,6 +144,27 @@ pub async fn login_controller(
     }
 }
 
+/// Marks the welcome page as seen, so later logins go straight to the
+/// dashboard.
+///
+/// Called whether the user finished the welcome page or skipped it.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `user_id` - ID of the user who has been onboarded
+///
+/// # Returns
+/// * `Ok(())` - The flag is set (including if it already was)
+/// * `Err(sqlx::Error)` - Database error
+pub async fn complete_onboarding(pool: &SqlitePool, user_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE users SET has_onboarded = 1 WHERE id = ?")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
 /// Handles user logout by clearing the session cookie.
 /// 
 /// This function removes the user's session cookie and redirects
@@ -72,6 +183,38 @@ pub fn logout_controller(cookies: &CookieJar<'_>) -> Flash<Redirect> {
     )
 }
 
+/// Validates the fields of a new user account.
+///
+/// Shared by single-user creation and bulk import so both apply the same rules.
+///
+/// # Arguments
+/// * `username` - Desired username
+/// * `password` - Plain text password
+/// * `confirm_password` - Password confirmation
+///
+/// # Returns
+/// * `Ok(())` - The fields are valid
+/// * `Err(&'static str)` - User-facing description of the first problem found
+fn validate_new_user(
+    username: &str,
+    password: &str,
+    confirm_password: &str,
+) -> Result<(), &'static str> {
+    if username.trim().is_empty() {
+        return Err("Username cannot be empty.");
+    }
+
+    if password.trim().is_empty() {
+        return Err("Password cannot be empty.");
+    }
+
+    if password != confirm_password {
+        return Err("Passwords do not match.");
+    }
+
+    Ok(())
+}
+
 /// Creates a new user account (admin functionality).
 /// 
 /// This function validates the form data, checks for existing users,
@@ -96,24 +239,12 @@ pub async fn add_user_controller(
     form: &NewUserForm,
 ) -> Result<Flash<Redirect>, Flash<Redirect>> {
     // Validate form inputs
-    if form.username.trim().is_empty() {
-        return Err(Flash::error(
-            Redirect::to(uri!(crate::routes::add_user_page)),
-            "Username cannot be empty.",
-        ));
-    }
-
-    if form.password.trim().is_empty() {
-        return Err(Flash::error(
-            Redirect::to(uri!(crate::routes::add_user_page)),
-            "Password cannot be empty.",
-        ));
-    }
-
-    if form.password != form.confirm_password {
+    if let Err(message) =
+        validate_new_user(&form.username, &form.password, &form.confirm_password)
+    {
         return Err(Flash::error(
             Redirect::to(uri!(crate::routes::add_user_page)),
-            "Passwords do not match.",
+            message,
         ));
     }
 
@@ -153,12 +284,14 @@ pub async fn add_user_controller(
     };
 
     // Insert the new user
+    // Admins skip the welcome page; everyone else sees it on first login
     let result = sqlx::query(
-        "INSERT INTO users (username, password_hash, is_admin) VALUES (?, ?, ?)",
+        "INSERT INTO users (username, password_hash, is_admin, has_onboarded) VALUES (?, ?, ?, ?)",
     )
     .bind(&form.username)
     .bind(&password_hash)
     .bind(form.is_admin)
+    .bind(form.is_admin)
     .execute(pool)
     .await;
 
@@ -180,6 +313,255 @@ pub async fn add_user_controller(
     }
 }
 
+/// A single user row parsed from a bulk import CSV file.
+struct ImportRow {
+    /// 1-based line number in the uploaded file, used in error messages
+    line: usize,
+    username: String,
+    password: String,
+    is_admin: bool,
+}
+
+/// Maximum number of per-row errors listed in the import summary flash.
+const MAX_IMPORT_ERRORS_SHOWN: usize = 5;
+
+/// Splits a single CSV line into fields.
+///
+/// Supports double-quoted fields containing commas and escaped quotes (`""`).
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - The parsed fields
+/// * `Err(&'static str)` - If a quoted field is not terminated
+fn split_csv_line(line: &str) -> Result<Vec<String>, &'static str> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err("unterminated quoted field");
+    }
+
+    fields.push(field);
+    Ok(fields)
+}
+
+/// Parses an `is_admin` CSV value, treating an empty value as `false`.
+fn parse_csv_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "1" | "yes" | "y" => Some(true),
+        "false" | "0" | "no" | "n" | "" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parses a user import CSV with columns `username,password,is_admin`.
+///
+/// A header row matching the column names is skipped, as are blank lines.
+///
+/// # Returns
+/// * `Ok(Vec<ImportRow>)` - All data rows in file order
+/// * `Err(String)` - Description of the first structural problem found
+fn parse_user_csv(contents: &str) -> Result<Vec<ImportRow>, String> {
+    let mut rows = Vec::new();
+
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let fields = split_csv_line(line)
+            .map_err(|err| format!("Line {}: {}.", line_number, err))?;
+
+        if fields.len() != 3 {
+            return Err(format!(
+                "Line {}: expected 3 columns (username,password,is_admin) but found {}.",
+                line_number,
+                fields.len()
+            ));
+        }
+
+        if rows.is_empty()
+            && fields[0].trim().eq_ignore_ascii_case("username")
+            && fields[1].trim().eq_ignore_ascii_case("password")
+            && fields[2].trim().eq_ignore_ascii_case("is_admin")
+        {
+            continue;
+        }
+
+        let is_admin = parse_csv_bool(&fields[2]).ok_or_else(|| {
+            format!(
+                "Line {}: is_admin must be true or false, found '{}'.",
+                line_number,
+                fields[2].trim()
+            )
+        })?;
+
+        rows.push(ImportRow {
+            line: line_number,
+            username: fields[0].trim().to_string(),
+            password: fields[1].clone(),
+            is_admin,
+        });
+    }
+
+    Ok(rows)
+}
+
+/// Creates user accounts in bulk from an uploaded CSV file (admin functionality).
+///
+/// Each row is validated with the same rules as [`add_user_controller`].
+/// Valid rows are created inside a single transaction; invalid rows are
+/// skipped and reported individually rather than aborting the import.
+/// A structurally malformed file is rejected as a whole.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `contents` - Raw CSV file contents
+/// * `admin_id` - ID of the admin performing the import
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Summary redirect to admin users page
+/// * `Err(Flash<Redirect>)` - Error redirect to add user page with message
+pub async fn import_users_controller(
+    pool: &SqlitePool,
+    contents: &str,
+    admin_id: i64,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    let rows = match parse_user_csv(contents) {
+        Ok(rows) if rows.is_empty() => {
+            return Err(Flash::error(
+                Redirect::to(uri!(crate::routes::add_user_page)),
+                "The uploaded CSV contains no users.",
+            ));
+        }
+        Ok(rows) => rows,
+        Err(message) => {
+            return Err(Flash::error(
+                Redirect::to(uri!(crate::routes::add_user_page)),
+                format!("Malformed CSV. {}", message),
+            ));
+        }
+    };
+
+    let mut errors = Vec::new();
+    let mut valid_rows = Vec::new();
+
+    // Validate and hash outside the transaction, since hashing is slow
+    for row in rows {
+        if let Err(message) = validate_new_user(&row.username, &row.password, &row.password) {
+            errors.push((row.line, message.to_string()));
+            continue;
+        }
+
+        match User::hash_password(&row.password) {
+            Ok(hash) => valid_rows.push((row, hash)),
+            Err(err) => {
+                error!("Error hashing password: {}", err);
+                errors.push((row.line, "Error creating user account.".to_string()));
+            }
+        }
+    }
+
+    let db_error = |err: sqlx::Error| {
+        error!("Database error importing users: {}", err);
+        Flash::error(
+            Redirect::to(uri!(crate::routes::add_user_page)),
+            "Database error occurred.",
+        )
+    };
+
+    let mut tx = pool.begin().await.map_err(db_error)?;
+    let mut created = 0;
+
+    for (row, password_hash) in valid_rows {
+        let existing_user = sqlx::query("SELECT id FROM users WHERE username = ?")
+            .bind(&row.username)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        if existing_user.is_some() {
+            errors.push((row.line, "Username already exists.".to_string()));
+            continue;
+        }
+
+        sqlx::query("INSERT INTO users (username, password_hash, is_admin, has_onboarded) VALUES (?, ?, ?, ?)")
+            .bind(&row.username)
+            .bind(&password_hash)
+            .bind(row.is_admin)
+            .bind(row.is_admin)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        info!("New user created via import: {}", row.username);
+        created += 1;
+    }
+
+    tx.commit().await.map_err(db_error)?;
+
+    errors.sort_by_key(|(line, _)| *line);
+
+    audit::record(
+        pool,
+        Some(admin_id),
+        "user.import",
+        &format!("Imported {} user(s) from CSV; {} row(s) failed", created, errors.len()),
+    )
+    .await;
+
+    let mut summary = format!("Imported {} user(s).", created);
+    if !errors.is_empty() {
+        summary.push_str(&format!(" {} row(s) failed: ", errors.len()));
+        summary.push_str(
+            &errors
+                .iter()
+                .take(MAX_IMPORT_ERRORS_SHOWN)
+                .map(|(line, message)| format!("Line {}: {}", line, message))
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+        if errors.len() > MAX_IMPORT_ERRORS_SHOWN {
+            summary.push_str(&format!(
+                " ...and {} more.",
+                errors.len() - MAX_IMPORT_ERRORS_SHOWN
+            ));
+        }
+    }
+
+    if created == 0 {
+        Err(Flash::error(
+            Redirect::to(uri!(crate::routes::add_user_page)),
+            summary,
+        ))
+    } else if errors.is_empty() {
+        Ok(Flash::success(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            summary,
+        ))
+    } else {
+        Ok(Flash::warning(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            summary,
+        ))
+    }
+}
+
 /// Retrieves user statistics for profile display.
 /// 
 /// This function queries the database to get the number of polls
@@ -211,6 +593,75 @@ pub async fn get_user_stats(
     Ok((polls_created, votes_cast))
 }
 
+/// Retrieves detailed user statistics for profile display.
+///
+/// In addition to the counts from [`get_user_stats`], this includes the
+/// user's most-voted poll, how many of their polls reached quorum, and when
+/// they were first and last active (creating a poll or voting).
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `user_id` - ID of the user to get statistics for
+///
+/// # Returns
+/// * `Ok(UserStatsDetailed)` - The user's statistics
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_user_stats_detailed(
+    pool: &SqlitePool,
+    user_id: i64,
+) -> Result<UserStatsDetailed, sqlx::Error> {
+    let (polls_created, votes_cast) = get_user_stats(pool, user_id).await?;
+
+    let most_voted_poll = sqlx::query_as::<_, PollVoteSummary>(
+        "SELECT p.id, p.title, COUNT(v.id) as vote_count
+         FROM polls p
+         LEFT JOIN options o ON o.poll_id = p.id
+         LEFT JOIN votes v ON v.option_id = o.id
+         WHERE p.creator_id = ?
+         GROUP BY p.id
+         ORDER BY vote_count DESC, p.created_at DESC
+         LIMIT 1",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let polls_reached_quorum = sqlx::query_scalar(
+        "SELECT COUNT(*)
+         FROM polls p
+         WHERE p.creator_id = ? AND p.quorum IS NOT NULL
+           AND (SELECT COUNT(DISTINCT v.user_id)
+                FROM votes v
+                JOIN options o ON v.option_id = o.id
+                WHERE o.poll_id = p.id) >= p.quorum",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+
+    let (first_activity_at, last_activity_at) =
+        sqlx::query_as::<_, (Option<DateTime<Utc>>, Option<DateTime<Utc>>)>(
+            "SELECT MIN(created_at), MAX(created_at) FROM (
+                 SELECT created_at FROM polls WHERE creator_id = ?
+                 UNION ALL
+                 SELECT created_at FROM votes WHERE user_id = ?
+             )",
+        )
+        .bind(user_id)
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(UserStatsDetailed {
+        polls_created,
+        votes_cast,
+        most_voted_poll,
+        polls_reached_quorum,
+        first_activity_at,
+        last_activity_at,
+    })
+}
+
 /// Handles user password change requests.
 /// 
 /// This function validates the current password, checks the new password
@@ -220,7 +671,8 @@ pub async fn get_user_stats(
 /// 1. Ensures new password is not empty
 /// 2. Verifies new password confirmation matches
 /// 3. Retrieves current user data from database
-/// 4. Verifies current password is correct
+/// 4. Verifies current password is correct, or asks for a password reset if
+///    the stored hash is malformed
 /// 5. Hashes the new password
 /// 6. Updates the password in the database
 /// 
@@ -254,7 +706,7 @@ pub async fn change_password(
     
     // Get current user data
     let user = match sqlx::query_as::<_, User>(
-        "SELECT id, username, password_hash, is_admin, created_at FROM users WHERE id = ?"
+        "SELECT id, username, password_hash, is_admin, created_at, session_epoch, last_login_at, vote_weight, disabled, has_onboarded FROM users WHERE id = ?"
     )
     .bind(user_id)
     .fetch_one(pool)
@@ -270,11 +722,21 @@ pub async fn change_password(
     };
     
     // Verify current password
-    if !user.verify_password(&form.current_password) {
-        return Err(Flash::error(
-            Redirect::to(uri!(crate::routes::profile)),
-            "Current password is incorrect.",
-        ));
+    match user.check_password(&form.current_password) {
+        Ok(true) => {}
+        Ok(false) => {
+            return Err(Flash::error(
+                Redirect::to(uri!(crate::routes::profile)),
+                "Current password is incorrect.",
+            ));
+        }
+        Err(err) => {
+            error!("Stored password for {} can't be checked: {}", user.username, err);
+            return Err(Flash::error(
+                Redirect::to(uri!(crate::routes::profile)),
+                PASSWORD_RESET_NEEDED,
+            ));
+        }
     }
     
     // Hash the new password
@@ -327,12 +789,202 @@ pub async fn change_password(
 /// * `Err(sqlx::Error)` - Database error if query fails
 pub async fn get_all_users(pool: &SqlitePool) -> Result<Vec<User>, sqlx::Error> {
     sqlx::query_as::<_, User>(
-        "SELECT id, username, password_hash, is_admin, created_at FROM users ORDER BY username",
+        "SELECT id, username, password_hash, is_admin, created_at, session_epoch, last_login_at, vote_weight, disabled, has_onboarded FROM users ORDER BY username",
     )
     .fetch_all(pool)
     .await
 }
 
+/// Retrieves the public profile of a user.
+///
+/// Only non-sensitive columns are selected. The last login time is
+/// included only when requested by an admin.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `user_id` - ID of the user to look up
+/// * `include_last_login` - Whether to include the user's last login time
+///
+/// # Returns
+/// * `Ok(Some(PublicUserInfo))` - The user's public profile
+/// * `Ok(None)` - No user with that ID exists
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn get_public_user_info(
+    pool: &SqlitePool,
+    user_id: i64,
+    include_last_login: bool,
+) -> Result<Option<PublicUserInfo>, sqlx::Error> {
+    sqlx::query_as::<_, PublicUserInfo>(
+        "SELECT u.id, u.username, u.is_admin, u.created_at,
+         (SELECT COUNT(*) FROM polls p WHERE p.creator_id = u.id) as poll_count,
+         CASE WHEN ? THEN u.last_login_at END as last_login_at
+         FROM users u
+         WHERE u.id = ?",
+    )
+    .bind(include_last_login)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Streams every user as CSV for the admin export.
+///
+/// Columns are `id,username,is_admin,created_at,last_login`. Password hashes
+/// are never included, and timestamps are written in RFC 3339 format with
+/// an empty field for users who have never logged in. Rows are written as
+/// they are read from the database rather than collected first.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// A stream of CSV rows, starting with the header row
+pub fn stream_users_csv(pool: SqlitePool) -> csv::CsvStream {
+    csv::spawn(move |tx| async move {
+        if tx
+            .send("id,username,is_admin,created_at,last_login\n".to_string())
+            .await
+            .is_err()
+        {
+            return;
+        }
+
+        let mut rows = sqlx::query_as::<_, User>(
+            "SELECT id, username, password_hash, is_admin, created_at, session_epoch, last_login_at, vote_weight, disabled, has_onboarded FROM users ORDER BY username",
+        )
+        .fetch(&pool);
+
+        while let Some(row) = rows.next().await {
+            let user = match row {
+                Ok(user) => user,
+                Err(err) => {
+                    error!("Failed to export users: {}", err);
+                    return;
+                }
+            };
+
+            let line = format!(
+                "{},{},{},{},{}\n",
+                user.id,
+                csv::escape_field(&user.username),
+                user.is_admin,
+                user.created_at.to_rfc3339(),
+                user.last_login_at
+                    .map(|at| at.to_rfc3339())
+                    .unwrap_or_default(),
+            );
+
+            // The client has gone away
+            if tx.send(line).await.is_err() {
+                return;
+            }
+        }
+    })
+}
+
+/// Streams everything associated with a user as a JSON document, for the
+/// user's own data export.
+///
+/// The document is `{"user": ..., "polls": [...], "votes": [...]}`: the
+/// account details (never the password hash or session details), every
+/// poll the user created with its options, and every vote they cast with
+/// the poll and option it was cast on. Polls and votes are written one at a
+/// time, reusing the CSV export's channel plumbing, so large accounts are
+/// never held in memory as a whole. On a database error the document is cut
+/// short and the error is logged.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `user` - The user whose data to export
+///
+/// # Returns
+/// A stream of JSON fragments that together form the document
+pub fn stream_user_export(pool: SqlitePool, user: User) -> csv::CsvStream {
+    csv::spawn(move |tx| async move {
+        let user_id = user.id;
+        let header = match serde_json::to_string(&user) {
+            Ok(json) => format!("{{\"user\":{},\"polls\":[", json),
+            Err(err) => {
+                error!("Failed to export data for user {}: {}", user_id, err);
+                return;
+            }
+        };
+        if tx.send(header).await.is_err() {
+            return;
+        }
+
+        let created = match polls::get_polls_by_creator(&pool, user_id).await {
+            Ok(created) => created,
+            Err(err) => {
+                error!("Failed to export polls for user {}: {}", user_id, err);
+                return;
+            }
+        };
+
+        for (index, poll) in created.into_iter().enumerate() {
+            let options = match polls::get_poll_options(&pool, poll.id).await {
+                Ok(options) => options,
+                Err(err) => {
+                    error!("Failed to export options for poll {}: {}", poll.id, err);
+                    return;
+                }
+            };
+
+            let json = match serde_json::to_string(&ExportedPoll { poll, options }) {
+                Ok(json) => json,
+                Err(err) => {
+                    error!("Failed to export data for user {}: {}", user_id, err);
+                    return;
+                }
+            };
+            let separator = if index == 0 { "" } else { "," };
+
+            // The client has gone away
+            if tx.send(format!("{}{}", separator, json)).await.is_err() {
+                return;
+            }
+        }
+
+        if tx.send("],\"votes\":[".to_string()).await.is_err() {
+            return;
+        }
+
+        let mut rows = sqlx::query_as::<_, ExportedVote>(
+            "SELECT v.id, p.id AS poll_id, p.title AS poll_title, o.id AS option_id,
+                    o.text AS option_text, v.vote_comment, v.created_at
+             FROM votes v
+             JOIN options o ON v.option_id = o.id
+             JOIN polls p ON o.poll_id = p.id
+             WHERE v.user_id = ?
+             ORDER BY v.created_at, v.id",
+        )
+        .bind(user_id)
+        .fetch(&pool);
+
+        let mut first = true;
+        while let Some(row) = rows.next().await {
+            let json = match row.map_err(|err| err.to_string()).and_then(|vote| {
+                serde_json::to_string(&vote).map_err(|err| err.to_string())
+            }) {
+                Ok(json) => json,
+                Err(err) => {
+                    error!("Failed to export votes for user {}: {}", user_id, err);
+                    return;
+                }
+            };
+            let separator = if first { "" } else { "," };
+            first = false;
+
+            // The client has gone away
+            if tx.send(format!("{}{}", separator, json)).await.is_err() {
+                return;
+            }
+        }
+
+        let _ = tx.send("]}".to_string()).await;
+    })
+}
+
 /// Toggles admin role for a user (admin functionality).
 /// 
 /// This function allows administrators to promote users to admin status
@@ -419,4 +1071,1279 @@ pub async fn toggle_user_role(
             ))
         }
     }
-}
\ No newline at end of file
+}
+
+/// Largest vote weight an admin may give a user.
+const MAX_VOTE_WEIGHT: i64 = 100;
+
+/// Sets how much a user's votes count toward poll results (admin functionality).
+///
+/// The weight applies to the user's existing votes as well as future ones,
+/// since results are computed from the current weights.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `user_id` - ID of the user whose vote weight should be changed
+/// * `vote_weight` - New vote weight, from 1 to [`MAX_VOTE_WEIGHT`]
+/// * `admin_id` - ID of the admin performing the action
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to admin users page
+/// * `Err(Flash<Redirect>)` - Error redirect to admin users page with message
+pub async fn set_vote_weight(
+    pool: &SqlitePool,
+    user_id: i64,
+    vote_weight: i64,
+    admin_id: i64,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    if !(1..=MAX_VOTE_WEIGHT).contains(&vote_weight) {
+        return Err(Flash::error(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            format!("Vote weight must be between 1 and {}.", MAX_VOTE_WEIGHT),
+        ));
+    }
+
+    let result = sqlx::query_scalar::<_, String>(
+        "UPDATE users SET vote_weight = ? WHERE id = ? RETURNING username",
+    )
+    .bind(vote_weight)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await;
+
+    match result {
+        Ok(Some(username)) => {
+            info!("Vote weight for user_id={} set to {} by admin_id={}", user_id, vote_weight, admin_id);
+            audit::record(
+                pool,
+                Some(admin_id),
+                "user.vote_weight",
+                &format!("Set the vote weight of {} (id {}) to {}", username, user_id, vote_weight),
+            )
+            .await;
+
+            Ok(Flash::success(
+                Redirect::to(uri!(crate::routes::admin_users)),
+                format!("Votes by {} now count {} time(s).", username, vote_weight),
+            ))
+        }
+        Ok(None) => Err(Flash::error(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            "User not found.",
+        )),
+        Err(err) => {
+            error!("Database error setting vote weight: {}", err);
+            Err(Flash::error(
+                Redirect::to(uri!(crate::routes::admin_users)),
+                "Error updating vote weight.",
+            ))
+        }
+    }
+}
+
+/// Deletes a user according to the configured [`UserDeletePolicy`] (admin functionality).
+///
+/// Everything happens in a single transaction. Rows are updated or deleted
+/// explicitly rather than relying on foreign key cascades, which older
+/// databases may not enforce.
+///
+/// # Policies
+/// - `reassign`: the user's polls are moved to the deleting admin. Their
+///   votes, comments, and write-ins are moved to a new placeholder account
+///   named `deleted-user-<id>` (with a numeric suffix if that name is
+///   taken), which keeps their vote weight, so poll results stay the same.
+///   The placeholder is disabled, so it can't log in and isn't nudged or
+///   sent broadcasts.
+/// - `cascade`: the user's polls are deleted along with everything on them,
+///   and their votes and comments on other polls are removed. Write-ins
+///   they suggested on other polls stay, without attribution.
+///
+/// # Safety Checks
+/// Admins cannot delete their own account.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `user_id` - ID of the user to delete
+/// * `admin_id` - ID of the admin performing the action
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to admin users page
+/// * `Err(Flash<Redirect>)` - Error redirect to admin users page with message
+pub async fn delete_user(
+    pool: &SqlitePool,
+    user_id: i64,
+    admin_id: i64,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    if user_id == admin_id {
+        return Err(Flash::error(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            "You can't delete your own account.",
+        ));
+    }
+
+    let policy = user_delete_policy();
+    let result = async {
+        let mut tx = pool.begin().await?;
+        let deleted = delete_user_rows(&mut tx, user_id, admin_id, policy).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(deleted)
+    }
+    .await;
+
+    match result {
+        Ok(deleted) => {
+            if policy == UserDeletePolicy::Cascade {
+                crate::db::increment_polls_deleted(crate::db::PollDeleter::Admin, deleted.polls);
+            }
+            info!(
+                "User {} (id {}) deleted by admin_id={} with policy {}",
+                deleted.username,
+                user_id,
+                admin_id,
+                policy.as_str()
+            );
+            let message = match policy {
+                UserDeletePolicy::Reassign => format!(
+                    "Deleted {}. Their {} poll(s) now belong to you, and their {} vote(s) are kept as {}.",
+                    deleted.username, deleted.polls, deleted.votes, deleted.placeholder
+                ),
+                UserDeletePolicy::Cascade => format!(
+                    "Deleted {} along with their {} poll(s) and {} vote(s).",
+                    deleted.username, deleted.polls, deleted.votes
+                ),
+            };
+            audit::record(
+                pool,
+                Some(admin_id),
+                "user.delete",
+                &format!("{} (id {}, policy {})", message, user_id, policy.as_str()),
+            )
+            .await;
+
+            Ok(Flash::success(
+                Redirect::to(uri!(crate::routes::admin_users)),
+                message,
+            ))
+        }
+        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            "User not found.",
+        )),
+        Err(err) => {
+            error!("Database error deleting user: {}", err);
+            Err(Flash::error(
+                Redirect::to(uri!(crate::routes::admin_users)),
+                "Error deleting user.",
+            ))
+        }
+    }
+}
+
+/// What [`delete_user_rows`] did, for reporting.
+struct DeletedUser {
+    /// Username of the deleted user
+    username: String,
+    /// Number of polls reassigned or deleted
+    polls: u64,
+    /// Number of votes reassigned or deleted
+    votes: u64,
+    /// Username of the placeholder account (empty for `cascade`)
+    placeholder: String,
+}
+
+/// Applies a deletion policy to a user's rows and deletes the user.
+///
+/// # Arguments
+/// * `conn` - Transaction to delete in
+/// * `user_id` - ID of the user to delete
+/// * `admin_id` - ID of the admin performing the action, who receives reassigned polls
+/// * `policy` - How to handle the user's polls, votes, and comments
+///
+/// # Returns
+/// * `Ok(DeletedUser)` - What was deleted or reassigned
+/// * `Err(sqlx::Error)` - Database error, or RowNotFound if the user doesn't exist
+async fn delete_user_rows(
+    conn: &mut SqliteConnection,
+    user_id: i64,
+    admin_id: i64,
+    policy: UserDeletePolicy,
+) -> Result<DeletedUser, sqlx::Error> {
+    let (username, vote_weight): (String, i64) =
+        sqlx::query_as("SELECT username, vote_weight FROM users WHERE id = ?")
+            .bind(user_id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+    let (polls, votes, placeholder) = match policy {
+        UserDeletePolicy::Reassign => {
+            // A placeholder per deleted user keeps votes unique per option, and
+            // its vote weight keeps weighted results unchanged. "!" is not a
+            // valid hash, so the placeholder can never log in.
+            let (placeholder_id, placeholder) =
+                insert_placeholder_user(&mut *conn, user_id, vote_weight).await?;
+
+            let polls = sqlx::query("UPDATE polls SET creator_id = ? WHERE creator_id = ?")
+                .bind(admin_id)
+                .bind(user_id)
+                .execute(&mut *conn)
+                .await?
+                .rows_affected();
+
+            let votes = sqlx::query("UPDATE votes SET user_id = ? WHERE user_id = ?")
+                .bind(placeholder_id)
+                .bind(user_id)
+                .execute(&mut *conn)
+                .await?
+                .rows_affected();
+
+            sqlx::query("UPDATE comments SET user_id = ? WHERE user_id = ?")
+                .bind(placeholder_id)
+                .bind(user_id)
+                .execute(&mut *conn)
+                .await?;
+
+            sqlx::query("UPDATE options SET suggested_by = ? WHERE suggested_by = ?")
+                .bind(placeholder_id)
+                .bind(user_id)
+                .execute(&mut *conn)
+                .await?;
+
+            (polls, votes, placeholder)
+        }
+        UserDeletePolicy::Cascade => {
+            // Everything on the user's own polls goes with them
+            for table_filter in [
+                "DELETE FROM votes WHERE option_id IN
+                     (SELECT o.id FROM options o JOIN polls p ON o.poll_id = p.id WHERE p.creator_id = ?)",
+                "DELETE FROM comments WHERE poll_id IN (SELECT id FROM polls WHERE creator_id = ?)",
+                "DELETE FROM poll_results_snapshots WHERE poll_id IN (SELECT id FROM polls WHERE creator_id = ?)",
+                "DELETE FROM options WHERE poll_id IN (SELECT id FROM polls WHERE creator_id = ?)",
+            ] {
+                sqlx::query(table_filter)
+                    .bind(user_id)
+                    .execute(&mut *conn)
+                    .await?;
+            }
+
+            let polls = sqlx::query("DELETE FROM polls WHERE creator_id = ?")
+                .bind(user_id)
+                .execute(&mut *conn)
+                .await?
+                .rows_affected();
+
+            let votes = sqlx::query("DELETE FROM votes WHERE user_id = ?")
+                .bind(user_id)
+                .execute(&mut *conn)
+                .await?
+                .rows_affected();
+
+            sqlx::query("DELETE FROM comments WHERE user_id = ?")
+                .bind(user_id)
+                .execute(&mut *conn)
+                .await?;
+
+            // Others may have voted for their write-ins, so those stay
+            sqlx::query("UPDATE options SET suggested_by = NULL WHERE suggested_by = ?")
+                .bind(user_id)
+                .execute(&mut *conn)
+                .await?;
+
+            (polls, votes, String::new())
+        }
+    };
+
+    sqlx::query("DELETE FROM notifications WHERE user_id = ?")
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await?;
+
+    sqlx::query("DELETE FROM users WHERE id = ?")
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(DeletedUser {
+        username,
+        polls,
+        votes,
+        placeholder,
+    })
+}
+
+/// Creates the disabled placeholder account that keeps a deleted user's votes.
+///
+/// The account is named `deleted-user-<id>`. Anyone can register that name
+/// first, so on a clash a suffix (`-2`, `-3`, ...) is added until one is free.
+///
+/// # Arguments
+/// * `conn` - Transaction to insert in
+/// * `user_id` - ID of the user being deleted
+/// * `vote_weight` - Vote weight for the placeholder
+///
+/// # Returns
+/// * `Ok((i64, String))` - The placeholder's ID and username
+/// * `Err(sqlx::Error)` - Database error
+async fn insert_placeholder_user(
+    conn: &mut SqliteConnection,
+    user_id: i64,
+    vote_weight: i64,
+) -> Result<(i64, String), sqlx::Error> {
+    let base = format!("deleted-user-{}", user_id);
+    let mut username = base.clone();
+    for attempt in 2.. {
+        let inserted = sqlx::query(
+            "INSERT INTO users (username, password_hash, is_admin, vote_weight, disabled) VALUES (?, '!', 0, ?, 1)",
+        )
+        .bind(&username)
+        .bind(vote_weight)
+        .execute(&mut *conn)
+        .await;
+
+        match inserted {
+            Ok(result) => return Ok((result.last_insert_rowid(), username)),
+            Err(sqlx::Error::Database(err)) if err.is_unique_violation() => {
+                username = format!("{}-{}", base, attempt);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    unreachable!("ran out of placeholder usernames")
+}
+
+/// Merges one user account into another (admin only).
+///
+/// For people who ended up with two accounts. Everything the source account
+/// owns moves to the target account: polls, votes, comments, suggested
+/// write-ins, notifications, finalized results, and audit entries. The
+/// source account is then deleted. The target keeps its own username,
+/// password, role, and vote weight.
+///
+/// Where both accounts voted in the same poll, the target's votes win: a
+/// source vote for an option the target also picked is dropped, and so is
+/// every source vote in a single-choice poll the target already voted in.
+///
+/// # Safety Checks
+/// 1. An account can't be merged into itself
+/// 2. Admins can't merge away their own account
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `source_id` - ID of the account to merge and delete
+/// * `target_id` - ID of the account that keeps everything
+/// * `admin_id` - ID of the admin performing the action
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to admin users page
+/// * `Err(Flash<Redirect>)` - Error redirect to admin users page with message
+pub async fn merge_users(
+    pool: &SqlitePool,
+    source_id: i64,
+    target_id: i64,
+    admin_id: i64,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    if source_id == target_id {
+        return Err(Flash::error(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            "Pick two different accounts to merge.",
+        ));
+    }
+    if source_id == admin_id {
+        return Err(Flash::error(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            "You can't merge away your own account.",
+        ));
+    }
+
+    let result = async {
+        let mut tx = pool.begin().await?;
+        let merged = merge_user_rows(&mut tx, source_id, target_id).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(merged)
+    }
+    .await;
+
+    match result {
+        Ok(merged) => {
+            info!(
+                "User {} (id {}) merged into {} (id {}) by admin_id={}",
+                merged.source, source_id, merged.target, target_id, admin_id
+            );
+            let message = format!(
+                "Merged {} into {}: moved {} poll(s) and {} vote(s), dropped {} duplicate vote(s).",
+                merged.source, merged.target, merged.polls, merged.votes, merged.conflicts
+            );
+            audit::record(
+                pool,
+                Some(admin_id),
+                "user.merge",
+                &format!("{} (id {} into id {})", message, source_id, target_id),
+            )
+            .await;
+
+            Ok(Flash::success(
+                Redirect::to(uri!(crate::routes::admin_users)),
+                message,
+            ))
+        }
+        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            "User not found.",
+        )),
+        Err(err) => {
+            error!("Database error merging users: {}", err);
+            Err(Flash::error(
+                Redirect::to(uri!(crate::routes::admin_users)),
+                "Error merging users.",
+            ))
+        }
+    }
+}
+
+/// What [`merge_user_rows`] did, for reporting.
+struct MergedUsers {
+    /// Username of the merged (deleted) account
+    source: String,
+    /// Username of the account that was kept
+    target: String,
+    /// Number of polls moved to the target
+    polls: u64,
+    /// Number of votes moved to the target
+    votes: u64,
+    /// Number of source votes dropped because the target's took precedence
+    conflicts: u64,
+}
+
+/// Moves a user's rows to another user and deletes the first user.
+///
+/// # Arguments
+/// * `conn` - Transaction to merge in
+/// * `source_id` - ID of the account to merge and delete
+/// * `target_id` - ID of the account that keeps everything
+///
+/// # Returns
+/// * `Ok(MergedUsers)` - What was moved or dropped
+/// * `Err(sqlx::Error)` - Database error, or RowNotFound if either user doesn't exist
+async fn merge_user_rows(
+    conn: &mut SqliteConnection,
+    source_id: i64,
+    target_id: i64,
+) -> Result<MergedUsers, sqlx::Error> {
+    let username = "SELECT username FROM users WHERE id = ?";
+    let source: String = sqlx::query_scalar(username)
+        .bind(source_id)
+        .fetch_one(&mut *conn)
+        .await?;
+    let target: String = sqlx::query_scalar(username)
+        .bind(target_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    // Votes are unique per user and option, so same-option duplicates must
+    // go before the rest move
+    let mut conflicts = sqlx::query(
+        "DELETE FROM votes WHERE user_id = ?
+         AND option_id IN (SELECT option_id FROM votes WHERE user_id = ?)",
+    )
+    .bind(source_id)
+    .bind(target_id)
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    // A single-choice poll can't hold two votes from the target
+    conflicts += sqlx::query(
+        "DELETE FROM votes WHERE user_id = ? AND option_id IN (
+             SELECT o.id FROM options o JOIN polls p ON o.poll_id = p.id
+             WHERE p.multi_select = 0 AND p.id IN (
+                 SELECT o2.poll_id FROM votes v2 JOIN options o2 ON v2.option_id = o2.id
+                 WHERE v2.user_id = ?))",
+    )
+    .bind(source_id)
+    .bind(target_id)
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    let votes = sqlx::query("UPDATE votes SET user_id = ? WHERE user_id = ?")
+        .bind(target_id)
+        .bind(source_id)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+    let polls = sqlx::query("UPDATE polls SET creator_id = ? WHERE creator_id = ?")
+        .bind(target_id)
+        .bind(source_id)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+    for statement in [
+        "UPDATE comments SET user_id = ? WHERE user_id = ?",
+        "UPDATE options SET suggested_by = ? WHERE suggested_by = ?",
+        "UPDATE notifications SET user_id = ? WHERE user_id = ?",
+        "UPDATE poll_results_snapshots SET finalized_by = ? WHERE finalized_by = ?",
+        "UPDATE audit_log SET actor_id = ? WHERE actor_id = ?",
+    ] {
+        sqlx::query(statement)
+            .bind(target_id)
+            .bind(source_id)
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    sqlx::query("DELETE FROM users WHERE id = ?")
+        .bind(source_id)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(MergedUsers {
+        source,
+        target,
+        polls,
+        votes,
+        conflicts,
+    })
+}
+
+/// Applies several role changes at once (admin functionality).
+///
+/// All changes are applied in a single transaction. Entries for the acting
+/// admin are skipped with a warning, as with [`toggle_user_role`], and
+/// entries for unknown users are skipped. Users already in the requested
+/// role are left alone and not counted as changed.
+///
+/// # Safety Checks
+/// 1. Skips the acting admin's own account
+/// 2. Skips users that don't exist
+/// 3. Rolls everything back if the batch would leave no admins
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `changes` - Requested role for each user
+/// * `admin_id` - ID of the admin performing the action
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Summary redirect to admin users page
+/// * `Err(Flash<Redirect>)` - Error redirect to admin users page with message
+pub async fn bulk_toggle_roles(
+    pool: &SqlitePool,
+    changes: &[RoleChange],
+    admin_id: i64,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    if changes.is_empty() {
+        return Err(Flash::error(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            "No role changes were submitted.",
+        ));
+    }
+
+    let db_error = |err: sqlx::Error| {
+        error!("Database error updating roles: {}", err);
+        Flash::error(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            "Error updating user roles.",
+        )
+    };
+
+    let mut tx = pool.begin().await.map_err(db_error)?;
+    let mut changed = 0;
+    let mut skipped_self = false;
+    let mut missing = 0;
+
+    for change in changes {
+        if change.user_id == admin_id {
+            skipped_self = true;
+            continue;
+        }
+
+        let current = sqlx::query_scalar::<_, bool>("SELECT is_admin FROM users WHERE id = ?")
+            .bind(change.user_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        match current {
+            Some(is_admin) if is_admin == change.set_admin => {}
+            Some(_) => {
+                sqlx::query("UPDATE users SET is_admin = ? WHERE id = ?")
+                    .bind(change.set_admin)
+                    .bind(change.user_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(db_error)?;
+                changed += 1;
+            }
+            None => missing += 1,
+        }
+    }
+
+    // Never leave the site without an admin
+    let admins = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM users WHERE is_admin = 1")
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    if admins == 0 {
+        return Err(Flash::error(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            "These changes would leave no admins, so none were applied.",
+        ));
+    }
+
+    tx.commit().await.map_err(db_error)?;
+
+    info!("Bulk role update by admin {}: {} user(s) changed", admin_id, changed);
+    audit::record(
+        pool,
+        Some(admin_id),
+        "user.role_bulk",
+        &format!("Changed the role of {} user(s)", changed),
+    )
+    .await;
+
+    let mut summary = format!("Updated the role of {} user(s).", changed);
+    if skipped_self {
+        summary.push_str(" You cannot change your own role, so your account was skipped.");
+    }
+    if missing > 0 {
+        summary.push_str(&format!(" {} unknown user(s) were skipped.", missing));
+    }
+
+    if skipped_self || missing > 0 {
+        Ok(Flash::warning(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            summary,
+        ))
+    } else {
+        Ok(Flash::success(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            summary,
+        ))
+    }
+}
+
+/// Revokes all active sessions for a user (admin functionality).
+///
+/// This function increments the user's session epoch, which invalidates every
+/// session cookie issued before the change. The action is recorded in the
+/// audit log.
+///
+/// # Self-Revocation
+/// Revoking the acting admin's own sessions requires explicit confirmation.
+/// When confirmed, a fresh cookie is issued for the current session so the
+/// admin stays logged in while all other sessions are terminated.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `cookies` - Cookie jar for re-issuing the admin's own session cookie
+/// * `user_id` - ID of the user whose sessions should be revoked
+/// * `admin_id` - ID of the admin performing the action
+/// * `confirm_self` - Whether self-revocation has been confirmed
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to admin users page
+/// * `Err(Flash<Redirect>)` - Error redirect to admin users page with message
+pub async fn revoke_sessions(
+    pool: &SqlitePool,
+    cookies: &CookieJar<'_>,
+    user_id: i64,
+    admin_id: i64,
+    confirm_self: bool,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    if user_id == admin_id && !confirm_self {
+        return Err(Flash::error(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            "Please confirm that you want to revoke your own sessions.",
+        ));
+    }
+
+    let result = sqlx::query_as::<_, (String, i64)>(
+        "UPDATE users SET session_epoch = session_epoch + 1 WHERE id = ?
+         RETURNING username, session_epoch",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await;
+
+    match result {
+        Ok(Some((username, session_epoch))) => {
+            info!("Sessions revoked for user_id={} by admin_id={}", user_id, admin_id);
+            audit::record(
+                pool,
+                Some(admin_id),
+                "user.revoke_sessions",
+                &format!("Revoked all sessions for user {} (id {})", username, user_id),
+            )
+            .await;
+
+            if user_id == admin_id {
+                set_login_cookie(cookies, admin_id, session_epoch);
+            }
+
+            Ok(Flash::success(
+                Redirect::to(uri!(crate::routes::admin_users)),
+                format!("All sessions for {} have been revoked.", username),
+            ))
+        }
+        Ok(None) => Err(Flash::error(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            "User not found.",
+        )),
+        Err(err) => {
+            error!("Database error revoking sessions: {}", err);
+            Err(Flash::error(
+                Redirect::to(uri!(crate::routes::admin_users)),
+                "Error revoking sessions.",
+            ))
+        }
+    }
+}
+
+/// Disables accounts that haven't logged in for `INACTIVE_ACCOUNT_DAYS`.
+///
+/// Run periodically by a background task. Inactivity is measured from an
+/// account's last login or, if later, when an admin last re-enabled it.
+/// Admins are never disabled, and accounts that have never logged in are
+/// only disabled when `INACTIVE_INCLUDE_NEVER_LOGGED_IN` is set (measured
+/// from when they were created or re-enabled). Each disabled account is
+/// recorded in the audit log. Does nothing when `INACTIVE_ACCOUNT_DAYS` is 0.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(usize)` - Number of accounts disabled
+/// * `Err(sqlx::Error)` - Database error if the update fails
+pub async fn disable_inactive_accounts(pool: &SqlitePool) -> Result<usize, sqlx::Error> {
+    let Some(days) = inactive_account_days() else {
+        return Ok(0);
+    };
+    let cutoff = Utc::now() - chrono::Duration::days(days);
+
+    let disabled = sqlx::query_as::<_, (i64, String)>(
+        "UPDATE users SET disabled = 1, session_epoch = session_epoch + 1
+         WHERE disabled = 0 AND is_admin = 0
+           AND (last_login_at IS NOT NULL OR ?)
+           AND julianday(COALESCE(last_active_at, created_at)) < julianday(?)
+         RETURNING id, username",
+    )
+    .bind(config::get().inactive_include_never_logged_in)
+    .bind(cutoff)
+    .fetch_all(pool)
+    .await?;
+
+    for (user_id, username) in &disabled {
+        info!("Disabled inactive account {} (id {})", username, user_id);
+        audit::record(
+            pool,
+            None,
+            "user.disable_inactive",
+            &format!("Disabled {} (id {}) after {} days without a login", username, user_id, days),
+        )
+        .await;
+    }
+
+    Ok(disabled.len())
+}
+
+/// Re-enables a disabled account (admin functionality).
+///
+/// The account's last activity is reset to now, so it isn't disabled again
+/// by the next inactivity check before the user has had a chance to log in.
+/// Their last login is left as it was.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `user_id` - ID of the user to re-enable
+/// * `admin_id` - ID of the admin performing the action
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to admin users page
+/// * `Err(Flash<Redirect>)` - Error redirect to admin users page with message
+pub async fn enable_user(
+    pool: &SqlitePool,
+    user_id: i64,
+    admin_id: i64,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    let result = sqlx::query_scalar::<_, String>(
+        "UPDATE users SET disabled = 0, last_active_at = ? WHERE id = ? AND disabled = 1
+         RETURNING username",
+    )
+    .bind(Utc::now())
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await;
+
+    match result {
+        Ok(Some(username)) => {
+            info!("User_id={} re-enabled by admin_id={}", user_id, admin_id);
+            audit::record(
+                pool,
+                Some(admin_id),
+                "user.enable",
+                &format!("Re-enabled {} (id {})", username, user_id),
+            )
+            .await;
+
+            Ok(Flash::success(
+                Redirect::to(uri!(crate::routes::admin_users)),
+                format!("{} can log in again.", username),
+            ))
+        }
+        Ok(None) => Err(Flash::error(
+            Redirect::to(uri!(crate::routes::admin_users)),
+            "User not found or not disabled.",
+        )),
+        Err(err) => {
+            error!("Database error enabling user: {}", err);
+            Err(Flash::error(
+                Redirect::to(uri!(crate::routes::admin_users)),
+                "Error enabling user.",
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controllers::notify;
+
+    #[test]
+    fn parses_rows_after_header() {
+        let rows = parse_user_csv("username,password,is_admin\nalice,secret,true\n\nbob,hunter2,\n")
+            .unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].line, 2);
+        assert_eq!(rows[0].username, "alice");
+        assert_eq!(rows[0].password, "secret");
+        assert!(rows[0].is_admin);
+        assert_eq!(rows[1].line, 4);
+        assert_eq!(rows[1].username, "bob");
+        assert!(!rows[1].is_admin);
+    }
+
+    #[test]
+    fn parses_quoted_fields() {
+        let rows = parse_user_csv("\"carol\",\"pass, with \"\"quotes\"\"\",no").unwrap();
+
+        assert_eq!(rows[0].username, "carol");
+        assert_eq!(rows[0].password, "pass, with \"quotes\"");
+    }
+
+    #[test]
+    fn rejects_wrong_column_count() {
+        let err = parse_user_csv("alice,secret").err().expect("the file should be rejected");
+        assert!(err.starts_with("Line 1: expected 3 columns"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_unknown_admin_flag() {
+        let err = parse_user_csv("alice,secret,maybe").err().expect("the file should be rejected");
+        assert_eq!(err, "Line 1: is_admin must be true or false, found 'maybe'.");
+    }
+
+    #[test]
+    fn rejects_unterminated_quote() {
+        let err = parse_user_csv("alice,\"secret,true").err().expect("the file should be rejected");
+        assert_eq!(err, "Line 1: unterminated quoted field.");
+    }
+
+    async fn add_user(pool: &SqlitePool, username: &str) -> i64 {
+        sqlx::query("INSERT INTO users (username, password_hash) VALUES (?, '!')")
+            .bind(username)
+            .execute(pool)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    async fn add_poll(pool: &SqlitePool, creator_id: i64, multi_select: bool) -> i64 {
+        sqlx::query("INSERT INTO polls (title, creator_id, expires_at, multi_select) VALUES ('Game night', ?, ?, ?)")
+            .bind(creator_id)
+            .bind(Utc::now() + chrono::Duration::days(1))
+            .bind(multi_select)
+            .execute(pool)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    async fn add_option(pool: &SqlitePool, poll_id: i64, text: &str) -> i64 {
+        sqlx::query("INSERT INTO options (poll_id, text) VALUES (?, ?)")
+            .bind(poll_id)
+            .bind(text)
+            .execute(pool)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    async fn add_vote(pool: &SqlitePool, user_id: i64, option_id: i64) {
+        sqlx::query("INSERT INTO votes (user_id, option_id) VALUES (?, ?)")
+            .bind(user_id)
+            .bind(option_id)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    /// Adds a poll by `creator_id` with one option that `voter_id` voted for.
+    async fn add_voted_poll(pool: &SqlitePool, creator_id: i64, voter_id: i64) -> i64 {
+        let poll_id = add_poll(pool, creator_id, false).await;
+        let option_id = add_option(pool, poll_id, "Catan").await;
+        add_vote(pool, voter_id, option_id).await;
+        poll_id
+    }
+
+    /// Returns each of a user's votes as (poll ID, option ID), in order.
+    async fn votes_of(pool: &SqlitePool, user_id: i64) -> Vec<(i64, i64)> {
+        sqlx::query_as(
+            "SELECT o.poll_id, o.id FROM votes v JOIN options o ON v.option_id = o.id
+             WHERE v.user_id = ? ORDER BY o.poll_id, o.id",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn reassign_keeps_votes_on_a_disabled_placeholder(pool: SqlitePool) -> sqlx::Result<()> {
+        let admin = add_user(&pool, "admin").await;
+        let alice = add_user(&pool, "alice").await;
+        add_user(&pool, &format!("deleted-user-{}", alice)).await;
+        let own_poll = add_voted_poll(&pool, alice, alice).await;
+        let other_poll = add_voted_poll(&pool, admin, alice).await;
+
+        let mut conn = pool.acquire().await?;
+        let deleted = delete_user_rows(&mut conn, alice, admin, UserDeletePolicy::Reassign).await?;
+        drop(conn);
+
+        assert_eq!(deleted.username, "alice");
+        assert_eq!((deleted.polls, deleted.votes), (1, 2));
+        assert_eq!(deleted.placeholder, format!("deleted-user-{}-2", alice));
+
+        let (placeholder_id, disabled): (i64, bool) =
+            sqlx::query_as("SELECT id, disabled FROM users WHERE username = ?")
+                .bind(&deleted.placeholder)
+                .fetch_one(&pool)
+                .await?;
+        assert!(disabled);
+
+        let creator: i64 = sqlx::query_scalar("SELECT creator_id FROM polls WHERE id = ?")
+            .bind(own_poll)
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(creator, admin);
+
+        let voters: Vec<i64> = sqlx::query_scalar("SELECT user_id FROM votes ORDER BY id")
+            .fetch_all(&pool)
+            .await?;
+        assert_eq!(voters, [placeholder_id, placeholder_id]);
+
+        let non_voters: Vec<String> = polls::get_non_voters(&pool, other_poll)
+            .await?
+            .into_iter()
+            .map(|user| user.username)
+            .collect();
+        assert_eq!(non_voters, ["admin".to_string(), format!("deleted-user-{}", alice)]);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn cascade_deletes_polls_and_votes(pool: SqlitePool) -> sqlx::Result<()> {
+        let admin = add_user(&pool, "admin").await;
+        let alice = add_user(&pool, "alice").await;
+        add_voted_poll(&pool, alice, alice).await;
+        let other_poll = add_voted_poll(&pool, admin, alice).await;
+
+        let mut conn = pool.acquire().await?;
+        let deleted = delete_user_rows(&mut conn, alice, admin, UserDeletePolicy::Cascade).await?;
+        drop(conn);
+
+        assert_eq!((deleted.polls, deleted.votes), (1, 1));
+        assert!(deleted.placeholder.is_empty());
+
+        let polls: Vec<i64> = sqlx::query_scalar("SELECT id FROM polls").fetch_all(&pool).await?;
+        assert_eq!(polls, [other_poll]);
+        let votes: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM votes").fetch_one(&pool).await?;
+        assert_eq!(votes, 0);
+        let users: Vec<String> = sqlx::query_scalar("SELECT username FROM users ORDER BY username")
+            .fetch_all(&pool)
+            .await?;
+        assert_eq!(users, ["admin"]);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn enable_user_keeps_last_login(pool: SqlitePool) -> sqlx::Result<()> {
+        let admin = add_user(&pool, "admin").await;
+        let alice = add_user(&pool, "alice").await;
+        let last_login = Utc::now() - chrono::Duration::days(100);
+        sqlx::query("UPDATE users SET disabled = 1, last_login_at = ?, last_active_at = ? WHERE id = ?")
+            .bind(last_login)
+            .bind(last_login)
+            .bind(alice)
+            .execute(&pool)
+            .await?;
+
+        assert!(enable_user(&pool, alice, admin).await.is_ok());
+
+        let (disabled, last_login_at, last_active_at): (bool, DateTime<Utc>, DateTime<Utc>) =
+            sqlx::query_as("SELECT disabled, last_login_at, last_active_at FROM users WHERE id = ?")
+                .bind(alice)
+                .fetch_one(&pool)
+                .await?;
+        assert!(!disabled);
+        assert_eq!(last_login_at, last_login);
+        assert!(last_active_at > Utc::now() - chrono::Duration::minutes(1));
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn merge_keeps_target_votes_and_moves_the_rest(pool: SqlitePool) -> sqlx::Result<()> {
+        let admin = add_user(&pool, "admin").await;
+        let old = add_user(&pool, "alice-old").await;
+        let alice = add_user(&pool, "alice").await;
+
+        // Single choice: the target already voted, so the source's vote is dropped
+        let single = add_poll(&pool, admin, false).await;
+        let catan = add_option(&pool, single, "Catan").await;
+        let azul = add_option(&pool, single, "Azul").await;
+        add_vote(&pool, alice, catan).await;
+        add_vote(&pool, old, azul).await;
+
+        // Multi-select: the shared option is dropped, the other one moves
+        let multi = add_poll(&pool, admin, true).await;
+        let friday = add_option(&pool, multi, "Friday").await;
+        let saturday = add_option(&pool, multi, "Saturday").await;
+        add_vote(&pool, alice, friday).await;
+        add_vote(&pool, old, friday).await;
+        add_vote(&pool, old, saturday).await;
+
+        let own_poll = add_voted_poll(&pool, old, old).await;
+        let own_option: i64 = sqlx::query_scalar("SELECT id FROM options WHERE poll_id = ?")
+            .bind(own_poll)
+            .fetch_one(&pool)
+            .await?;
+
+        let mut conn = pool.acquire().await?;
+        let merged = merge_user_rows(&mut conn, old, alice).await?;
+        drop(conn);
+
+        assert_eq!((merged.source.as_str(), merged.target.as_str()), ("alice-old", "alice"));
+        assert_eq!((merged.polls, merged.votes, merged.conflicts), (1, 2, 2));
+        assert_eq!(
+            votes_of(&pool, alice).await,
+            [(single, catan), (multi, friday), (multi, saturday), (own_poll, own_option)]
+        );
+
+        let creator: i64 = sqlx::query_scalar("SELECT creator_id FROM polls WHERE id = ?")
+            .bind(own_poll)
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(creator, alice);
+
+        let old_exists: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM users WHERE id = ?)")
+            .bind(old)
+            .fetch_one(&pool)
+            .await?;
+        assert!(!old_exists);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn merge_moves_polls_comments_and_notifications(pool: SqlitePool) -> sqlx::Result<()> {
+        let admin = add_user(&pool, "admin").await;
+        let old = add_user(&pool, "alice-old").await;
+        let alice = add_user(&pool, "alice").await;
+        let own_poll = add_poll(&pool, old, false).await;
+        let other_poll = add_poll(&pool, admin, false).await;
+        for poll_id in [own_poll, other_poll] {
+            sqlx::query("INSERT INTO comments (poll_id, user_id, body) VALUES (?, ?, 'Bring snacks')")
+                .bind(poll_id)
+                .bind(old)
+                .execute(&pool)
+                .await?;
+        }
+        notify::push(&pool, old, "nudge", "Time to vote", None).await?;
+        notify::push(&pool, alice, "nudge", "Time to vote", None).await?;
+
+        let mut conn = pool.acquire().await?;
+        merge_user_rows(&mut conn, old, alice).await?;
+        drop(conn);
+
+        let polls: Vec<i64> = sqlx::query_scalar("SELECT id FROM polls WHERE creator_id = ? ORDER BY id")
+            .bind(alice)
+            .fetch_all(&pool)
+            .await?;
+        assert_eq!(polls, [own_poll]);
+        let comments: Vec<i64> = sqlx::query_scalar("SELECT user_id FROM comments ORDER BY id")
+            .fetch_all(&pool)
+            .await?;
+        assert_eq!(comments, [alice, alice]);
+        let notifications: Vec<i64> = sqlx::query_scalar("SELECT user_id FROM notifications ORDER BY id")
+            .fetch_all(&pool)
+            .await?;
+        assert_eq!(notifications, [alice, alice]);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn users_csv_escapes_names_and_omits_hashes(pool: SqlitePool) -> sqlx::Result<()> {
+        let bob = add_user(&pool, "bob").await;
+        let carol = add_user(&pool, "carol, \"the dealer\"").await;
+        let last_login = Utc::now() - chrono::Duration::days(3);
+        sqlx::query("UPDATE users SET is_admin = 1, last_login_at = ? WHERE id = ?")
+            .bind(last_login)
+            .bind(bob)
+            .execute(&pool)
+            .await?;
+
+        let csv: String = stream_users_csv(pool.clone()).collect::<Vec<_>>().await.concat();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "id,username,is_admin,created_at,last_login");
+        assert!(lines[1].starts_with(&format!("{},bob,true,", bob)), "{}", lines[1]);
+        assert!(lines[1].ends_with(&format!(",{}", last_login.to_rfc3339())), "{}", lines[1]);
+        assert!(
+            lines[2].starts_with(&format!("{},\"carol, \"\"the dealer\"\"\",false,", carol)),
+            "{}",
+            lines[2]
+        );
+        assert!(lines[2].ends_with(','), "{}", lines[2]);
+        assert!(!csv.contains('!'));
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn detailed_stats_summarize_polls_and_votes(pool: SqlitePool) -> sqlx::Result<()> {
+        let alice = add_user(&pool, "alice").await;
+        let bob = add_user(&pool, "bob").await;
+        let carol = add_user(&pool, "carol").await;
+
+        let quiet = add_voted_poll(&pool, alice, alice).await;
+        let popular = add_poll(&pool, alice, false).await;
+        let option = add_option(&pool, popular, "Azul").await;
+        add_vote(&pool, bob, option).await;
+        add_vote(&pool, carol, option).await;
+        sqlx::query("UPDATE polls SET quorum = 2 WHERE id IN (?, ?)")
+            .bind(quiet)
+            .bind(popular)
+            .execute(&pool)
+            .await?;
+
+        let stats = get_user_stats_detailed(&pool, alice).await?;
+        assert_eq!(stats.polls_created, 2);
+        assert_eq!(stats.votes_cast, 1);
+        let most_voted = stats.most_voted_poll.unwrap();
+        assert_eq!((most_voted.id, most_voted.vote_count), (popular, 2));
+        assert_eq!(stats.polls_reached_quorum, 1);
+        assert!(stats.first_activity_at <= stats.last_activity_at);
+        assert!(stats.first_activity_at.is_some());
+
+        let idle = get_user_stats_detailed(&pool, add_user(&pool, "dave").await).await?;
+        assert_eq!((idle.polls_created, idle.votes_cast), (0, 0));
+        assert!(idle.most_voted_poll.is_none());
+        assert!(idle.first_activity_at.is_none() && idle.last_activity_at.is_none());
+        Ok(())
+    }
+
+    /// Returns the IDs of every admin, in order.
+    async fn admins(pool: &SqlitePool) -> Vec<i64> {
+        sqlx::query_scalar("SELECT id FROM users WHERE is_admin = 1 ORDER BY id")
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn bulk_roles_skip_self_and_unknown_users(pool: SqlitePool) -> sqlx::Result<()> {
+        let acting = add_user(&pool, "acting").await;
+        let other_admin = add_user(&pool, "other_admin").await;
+        let member = add_user(&pool, "member").await;
+        sqlx::query("UPDATE users SET is_admin = 1 WHERE id IN (?, ?)")
+            .bind(acting)
+            .bind(other_admin)
+            .execute(&pool)
+            .await?;
+
+        let changes = [
+            RoleChange { user_id: acting, set_admin: false },
+            RoleChange { user_id: other_admin, set_admin: false },
+            RoleChange { user_id: member, set_admin: true },
+            RoleChange { user_id: member + 100, set_admin: true },
+        ];
+        assert!(bulk_toggle_roles(&pool, &changes, acting).await.is_ok());
+        assert_eq!(admins(&pool).await, vec![acting, member]);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn bulk_roles_never_remove_the_last_admin(pool: SqlitePool) -> sqlx::Result<()> {
+        // The acting admin lost their role since their page loaded, so the
+        // batch would demote the only remaining admin
+        let acting = add_user(&pool, "acting").await;
+        let admin = add_user(&pool, "admin").await;
+        let member = add_user(&pool, "member").await;
+        sqlx::query("UPDATE users SET is_admin = 1 WHERE id = ?")
+            .bind(admin)
+            .execute(&pool)
+            .await?;
+
+        let changes = [
+            RoleChange { user_id: member, set_admin: true },
+            RoleChange { user_id: admin, set_admin: false },
+            RoleChange { user_id: member, set_admin: false },
+        ];
+        assert!(bulk_toggle_roles(&pool, &changes, acting).await.is_err());
+        assert_eq!(admins(&pool).await, vec![admin]);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn vote_weight_must_be_in_range_and_is_audited(pool: SqlitePool) -> sqlx::Result<()> {
+        let admin = add_user(&pool, "admin").await;
+        let alice = add_user(&pool, "alice").await;
+        let weight = || {
+            sqlx::query_scalar::<_, i64>("SELECT vote_weight FROM users WHERE id = ?")
+                .bind(alice)
+                .fetch_one(&pool)
+        };
+        assert_eq!(weight().await?, 1);
+
+        for rejected in [0, -1, MAX_VOTE_WEIGHT + 1] {
+            assert!(set_vote_weight(&pool, alice, rejected, admin).await.is_err());
+        }
+        assert!(set_vote_weight(&pool, alice + 100, 3, admin).await.is_err());
+        assert_eq!(weight().await?, 1);
+
+        assert!(set_vote_weight(&pool, alice, MAX_VOTE_WEIGHT, admin).await.is_ok());
+        assert_eq!(weight().await?, MAX_VOTE_WEIGHT);
+        let details: Vec<String> = sqlx::query_scalar(
+            "SELECT details FROM audit_log WHERE action = 'user.vote_weight' AND actor_id = ?",
+        )
+        .bind(admin)
+        .fetch_all(&pool)
+        .await?;
+        assert_eq!(details, [format!("Set the vote weight of alice (id {}) to 100", alice)]);
+        Ok(())
+    }
+
+    #[test]
+    fn failed_login_delay_adds_up_to_the_configured_jitter() {
+        let mut config = config::get().clone();
+        config.failed_login_delay_ms = 200;
+        config.failed_login_jitter_ms = 0;
+        assert_eq!(failed_login_delay(&config), Duration::from_millis(200));
+
+        config.failed_login_jitter_ms = 50;
+        for _ in 0..100 {
+            let delay = failed_login_delay(&config);
+            assert!((200..=250).contains(&delay.as_millis()), "{:?}", delay);
+        }
+    }
+}
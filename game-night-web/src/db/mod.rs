@@ -5,13 +5,15 @@
 //!
 //! ## Key Components
 //! - SQLite connection pool management
-//! - Default admin user initialization
+//! - Default admin user initialization and seeding
 //! - Database connection request guard
 //! - Prometheus metrics collection and reporting
 //!
 //! ## Database Configuration
 //! The database connection uses the `DATABASE_URL` environment variable,
-//! defaulting to `sqlite:game_night.db` if not specified.
+//! defaulting to `sqlite:game_night.db` if not specified. The URL may be
+//! `sqlite:<path>`, `sqlite://<path>`, or a bare file path, and is resolved
+//! to an absolute path by [`database_path`].
 //!
 //! ## Metrics
 //! This module exposes Prometheus metrics for monitoring:
@@ -19,21 +21,101 @@
 //! - Total votes and users
 //! - Login attempt statistics
 
-use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
-use std::env;
-use std::time::Duration;
+use sqlx::migrate::{Migration, Migrator};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use crate::config::{self, Config};
 use crate::models::User;
 use lazy_static::lazy_static;
+use serde::Serialize;
 use prometheus::{
-    Encoder, IntCounter, IntGauge, TextEncoder, register_int_counter, register_int_gauge,
+    Encoder, IntCounter, IntCounterVec, IntGauge, TextEncoder, register_int_counter,
+    register_int_counter_vec, register_int_gauge,
 };
 
+/// Embedded database migrations from the `migrations/` directory.
+///
+/// Shared by application startup and the readiness check so both agree on
+/// which migrations are expected to have been applied.
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
 /// Wrapper around a SQLite database connection for use as a Rocket request guard.
 /// 
 /// This struct implements Rocket's `FromRequest` trait to provide automatic
 /// database connection injection into route handlers.
 pub struct DbConn(pub sqlx::pool::PoolConnection<sqlx::Sqlite>);
 
+/// Resolves a SQLite database URL to an absolute file path.
+///
+/// Accepts `sqlite:<path>`, `sqlite://<path>`, and bare file paths. Relative
+/// paths are resolved against the current directory. Query parameters such
+/// as `?mode=rwc` are ignored, since the pool always creates a missing
+/// database file.
+///
+/// # Arguments
+/// * `url` - Database URL or file path
+///
+/// # Returns
+/// * `Ok(PathBuf)` - Absolute path to the database file
+/// * `Err(String)` - Why the URL can't be used
+pub fn resolve_database_path(url: &str) -> Result<PathBuf, String> {
+    let url = url.trim();
+    let path = url
+        .strip_prefix("sqlite://")
+        .or_else(|| url.strip_prefix("sqlite:"))
+        .unwrap_or(url);
+    let path = path.split('?').next().unwrap_or_default();
+
+    if path.is_empty() {
+        return Err(format!("'{}' does not name a database file", url));
+    }
+    if path.contains("://") {
+        return Err(format!("'{}' is not a SQLite URL; only sqlite: URLs and file paths are supported", url));
+    }
+    if path == ":memory:" || path.starts_with("file::memory:") {
+        return Err(format!("'{}' is an in-memory database, which would not be shared between connections", url));
+    }
+
+    let path = std::path::absolute(Path::new(path))
+        .map_err(|err| format!("Failed to resolve '{}' to an absolute path: {}", url, err))?;
+
+    // A missing directory otherwise surfaces as an unhelpful "unable to open database file"
+    if let Some(parent) = path.parent() {
+        if !parent.is_dir() {
+            return Err(format!("the directory {} does not exist", parent.display()));
+        }
+    }
+    if path.is_dir() {
+        return Err(format!("{} is a directory, not a database file", path.display()));
+    }
+
+    Ok(path)
+}
+
+/// Returns the absolute path of the database file named by `DATABASE_URL`
+/// (defaults to "sqlite:game_night.db").
+///
+/// # Panics
+/// Panics with a description of the problem if `DATABASE_URL` is invalid
+pub fn database_path() -> PathBuf {
+    config::get().database_path.clone()
+}
+
+/// Returns connection options for the database file at `path`.
+///
+/// The database file is created if it doesn't exist yet.
+///
+/// # Arguments
+/// * `path` - Database file path, usually from [`database_path`]
+pub fn connect_options(path: &Path) -> SqliteConnectOptions {
+    SqliteConnectOptions::new()
+        .filename(path)
+        .create_if_missing(true)
+}
+
 /// Initializes and returns a SQLite connection pool.
 /// 
 /// This function creates a connection pool with the following configuration:
@@ -48,28 +130,16 @@ pub struct DbConn(pub sqlx::pool::PoolConnection<sqlx::Sqlite>);
 /// A configured SQLite connection pool ready for use
 /// 
 /// # Panics
-/// Panics if unable to establish database connection
+/// Panics if `DATABASE_URL` is invalid or unable to establish database connection
 pub async fn init_pool() -> SqlitePool {
-    let database_url =
-        env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:game_night.db".to_string());
+    let path = database_path();
 
-    // Extract the database filename from the URL
-    let db_filename = if database_url.starts_with("sqlite:") {
-        &database_url[7..]
-    } else {
-        "game_night.db"
-    };
-
-    log::info!("Connecting to database at: {}", db_filename);
+    log::info!("Connecting to database at: {}", path.display());
 
     let pool = SqlitePoolOptions::new()
         .max_connections(5)
         .acquire_timeout(Duration::from_secs(3))
-        .connect_with(
-            sqlx::sqlite::SqliteConnectOptions::new()
-                .filename(db_filename)
-                .create_if_missing(true)
-        )
+        .connect_with(connect_options(&path))
         .await;
 
     match pool {
@@ -84,6 +154,82 @@ pub async fn init_pool() -> SqlitePool {
     }
 }
 
+/// Creates an admin user if no admin users exist in the database.
+/// 
+/// This is the idempotent seeding step shared by application startup and the
+/// `ensure_admin` example, so operators can pre-seed a database before boot.
+/// When any admin already exists nothing is changed, even if it has a
+/// different username.
+/// 
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `username` - Username for the admin account to create
+/// * `password` - Plain text password for the admin account to create
+/// 
+/// # Returns
+/// * `Ok(true)` - An admin user was created
+/// * `Ok(false)` - An admin user already existed, so nothing was done
+/// * `Err(sqlx::Error)` - Database or password hashing error
+pub async fn ensure_admin(
+    pool: &SqlitePool,
+    username: &str,
+    password: &str,
+) -> Result<bool, sqlx::Error> {
+    // Check if any admin users exist
+    let admin_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE is_admin = 1")
+        .fetch_one(pool)
+        .await?;
+
+    if admin_count > 0 {
+        return Ok(false);
+    }
+
+    let password_hash = match User::hash_password(password) {
+        Ok(hash) => hash,
+        Err(err) => {
+            log::error!("Failed to hash admin password: {}", err);
+            return Err(sqlx::Error::Protocol("Failed to hash password".into()));
+        }
+    };
+
+    sqlx::query("INSERT INTO users (username, password_hash, is_admin) VALUES (?, ?, 1)")
+        .bind(username)
+        .bind(&password_hash)
+        .execute(pool)
+        .await?;
+
+    Ok(true)
+}
+
+/// Password given to the default admin account created on first startup.
+const DEFAULT_ADMIN_PASSWORD: &str = "admin";
+
+/// Lists the admins whose password is still the default admin password.
+///
+/// Checked on demand rather than stored, so it reflects password changes
+/// immediately. Each admin costs one bcrypt verification.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - Usernames of admins using the default password
+/// * `Err(sqlx::Error)` - Database error if query fails
+pub async fn admins_with_default_password(pool: &SqlitePool) -> Result<Vec<String>, sqlx::Error> {
+    let admins = sqlx::query_as::<_, User>(
+        "SELECT id, username, password_hash, is_admin, created_at, session_epoch, last_login_at, vote_weight, disabled, has_onboarded
+         FROM users WHERE is_admin = 1 ORDER BY username",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(admins
+        .into_iter()
+        .filter(|admin| admin.verify_password(DEFAULT_ADMIN_PASSWORD))
+        .map(|admin| admin.username)
+        .collect())
+}
+
 /// Initializes a default admin user if no admin users exist in the database.
 /// 
 /// This function ensures there's always at least one admin user in the system
@@ -102,31 +248,7 @@ pub async fn init_pool() -> SqlitePool {
 /// * `Ok(())` - Admin initialization completed successfully
 /// * `Err(sqlx::Error)` - Database error during initialization
 pub async fn init_default_admin(pool: &SqlitePool) -> Result<(), sqlx::Error> {
-    // Check if any admin users exist
-    let admin_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE is_admin = 1")
-        .fetch_one(pool)
-        .await?;
-
-    if admin_count == 0 {
-        log::info!("No admin users found. Creating default admin user...");
-        
-        // Create default admin user with password 'admin'
-        let default_password = "admin";
-        let password_hash = match User::hash_password(default_password) {
-            Ok(hash) => hash,
-            Err(err) => {
-                log::error!("Failed to hash default admin password: {}", err);
-                return Err(sqlx::Error::Protocol("Failed to hash password".into()));
-            }
-        };
-
-        sqlx::query(
-            "INSERT INTO users (username, password_hash, is_admin) VALUES ('admin', ?, 1)"
-        )
-        .bind(&password_hash)
-        .execute(pool)
-        .await?;
-
+    if ensure_admin(pool, "admin", DEFAULT_ADMIN_PASSWORD).await? {
         log::info!("✅ Default admin user created successfully (username: 'admin', password: 'admin')");
         log::warn!("⚠️  Please change the default admin password after first login!");
     } else {
@@ -136,6 +258,72 @@ pub async fn init_default_admin(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     Ok(())
 }
 
+/// Verifies that the database is ready to serve requests.
+///
+/// This check acquires a connection from the pool, pings the database, and
+/// confirms that every embedded migration has been successfully applied.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(true)` - Database is reachable and fully migrated
+/// * `Ok(false)` - Database is reachable but migrations are still pending
+/// * `Err(sqlx::Error)` - Database could not be reached or queried
+pub async fn check_ready(pool: &SqlitePool) -> Result<bool, sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+
+    sqlx::query("SELECT 1").execute(&mut *conn).await?;
+
+    let applied: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_migrations WHERE success = 1")
+            .fetch_one(&mut *conn)
+            .await?;
+
+    Ok(applied as usize >= MIGRATOR.iter().count())
+}
+
+/// Lists the embedded migrations that have not yet been applied.
+///
+/// Nothing is written, so this is safe to run against a database that has
+/// never been migrated (every migration is then pending).
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Vec<&Migration>)` - Pending migrations in the order they would run
+/// * `Err(sqlx::Error)` - Database could not be queried
+pub async fn pending_migrations(pool: &SqlitePool) -> Result<Vec<&'static Migration>, sqlx::Error> {
+    let has_table: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let applied: Vec<i64> = if has_table {
+        sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success = 1")
+            .fetch_all(pool)
+            .await?
+    } else {
+        Vec::new()
+    };
+
+    Ok(MIGRATOR
+        .iter()
+        .filter(|migration| !applied.contains(&migration.version))
+        .collect())
+}
+
+/// Whether the server applies pending migrations as it starts, from
+/// `RUN_MIGRATIONS_ON_START` (default true).
+///
+/// Deployments that run the `migrate` example as a separate step can set
+/// this to `false` so a new server never changes the schema itself.
+pub fn run_migrations_on_start() -> bool {
+    config::get().run_migrations_on_start
+}
+
 // /// Database initialization hook for Rocket
 // pub fn init_db() -> AdHoc {
 //     AdHoc::try_on_ignite("SQLite Database", |rocket| async {
@@ -186,66 +374,405 @@ impl<'r> rocket::request::FromRequest<'r> for DbConn {
 // Prometheus Metrics
 // ============================================================================
 
-/// Global Prometheus metrics for monitoring application performance and usage.
-/// These metrics are automatically updated and exposed at the `/metrics` endpoint.
+/// Tracks when the database-backed metrics were last refreshed.
+///
+/// Managed by Rocket so that frequent scrapes of `/metrics` reuse the last
+/// computed gauges instead of running the count queries every time. Values
+/// are at most one refresh interval stale.
+pub struct MetricsRefresh {
+    /// Minimum time between refreshes
+    interval: Duration,
+    /// When the last refresh was started, if any
+    last_refresh: Mutex<Option<Instant>>,
+}
+
+impl MetricsRefresh {
+    /// Creates a refresh tracker with the given minimum interval.
+    pub fn new(interval: Duration) -> Self {
+        MetricsRefresh {
+            interval,
+            last_refresh: Mutex::new(None),
+        }
+    }
+
+    /// Creates a refresh tracker using `METRICS_REFRESH_SECONDS` from the
+    /// configuration. A value of `0` refreshes on every scrape.
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(Duration::from_secs(config.metrics_refresh_seconds))
+    }
+
+    /// Claims the next refresh if the interval has elapsed.
+    ///
+    /// Returns `true` at most once per interval, so concurrent scrapes don't
+    /// all hit the database at the same time.
+    fn claim(&self) -> bool {
+        let mut last_refresh = self.last_refresh.lock().unwrap();
+        let now = Instant::now();
+
+        if last_refresh.is_none_or(|last| now.duration_since(last) >= self.interval) {
+            *last_refresh = Some(now);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Forgets the last refresh so the next scrape retries immediately.
+    fn reset(&self) {
+        *self.last_refresh.lock().unwrap() = None;
+    }
+}
+
+// Global Prometheus metrics for monitoring application performance and usage.
+// These metrics are automatically updated and exposed at the `/metrics` endpoint.
+// Every name is prefixed with `METRICS_PREFIX`, which is read once before
+// the first metric is registered.
 lazy_static! {
-    static ref ACTIVE_POLLS: IntGauge =
-        register_int_gauge!("game_night_active_polls", "Number of active polls").unwrap();
-    static ref TOTAL_POLLS: IntGauge =
-        register_int_gauge!("game_night_total_polls", "Total number of polls").unwrap();
-    static ref TOTAL_VOTES: IntGauge =
-        register_int_gauge!("game_night_total_votes", "Total number of votes cast").unwrap();
-    static ref TOTAL_USERS: IntGauge =
-        register_int_gauge!("game_night_total_users", "Total number of registered users").unwrap();
-    static ref LOGIN_ATTEMPTS: IntCounter =
-        register_int_counter!("game_night_login_attempts", "Number of login attempts").unwrap();
+    static ref METRICS_PREFIX: String = config::get().metrics_prefix.clone();
+    static ref ACTIVE_POLLS: IntGauge = register_int_gauge!(
+        format!("{}_active_polls", *METRICS_PREFIX),
+        "Number of active polls"
+    )
+    .unwrap();
+    static ref TOTAL_POLLS: IntGauge = register_int_gauge!(
+        format!("{}_total_polls", *METRICS_PREFIX),
+        "Total number of polls"
+    )
+    .unwrap();
+    static ref TOTAL_VOTES: IntGauge = register_int_gauge!(
+        format!("{}_total_votes", *METRICS_PREFIX),
+        "Total number of votes cast"
+    )
+    .unwrap();
+    static ref TOTAL_USERS: IntGauge = register_int_gauge!(
+        format!("{}_total_users", *METRICS_PREFIX),
+        "Total number of registered users"
+    )
+    .unwrap();
+    static ref LOGIN_ATTEMPTS: IntCounter = register_int_counter!(
+        format!("{}_login_attempts", *METRICS_PREFIX),
+        "Number of login attempts"
+    )
+    .unwrap();
     static ref SUCCESSFUL_LOGINS: IntCounter = register_int_counter!(
-        "game_night_successful_logins",
+        format!("{}_successful_logins", *METRICS_PREFIX),
         "Number of successful logins"
     )
     .unwrap();
-    static ref FAILED_LOGINS: IntCounter =
-        register_int_counter!("game_night_failed_logins", "Number of failed logins").unwrap();
-    static ref API_REQUESTS: IntCounter =
-        register_int_counter!("game_night_api_requests", "Number of API requests").unwrap();
+    static ref FAILED_LOGINS: IntCounter = register_int_counter!(
+        format!("{}_failed_logins", *METRICS_PREFIX),
+        "Number of failed logins"
+    )
+    .unwrap();
+    static ref VOTES_CAST: IntCounter = register_int_counter!(
+        format!("{}_votes_cast_total", *METRICS_PREFIX),
+        "Number of votes cast since startup"
+    )
+    .unwrap();
+    static ref VOTES_REMOVED: IntCounter = register_int_counter!(
+        format!("{}_votes_removed_total", *METRICS_PREFIX),
+        "Number of votes removed since startup"
+    )
+    .unwrap();
+    static ref POLLS_CREATED: IntCounter = register_int_counter!(
+        format!("{}_polls_created_total", *METRICS_PREFIX),
+        "Number of polls created since startup"
+    )
+    .unwrap();
+    static ref POLLS_DELETED: IntCounter = register_int_counter!(
+        format!("{}_polls_deleted_total", *METRICS_PREFIX),
+        "Number of polls deleted since startup"
+    )
+    .unwrap();
+    static ref POLLS_DELETED_BY: IntCounterVec = register_int_counter_vec!(
+        format!("{}_polls_deleted_by_total", *METRICS_PREFIX),
+        "Number of polls deleted since startup, by who deleted them",
+        &["initiator"]
+    )
+    .unwrap();
+    static ref API_REQUESTS: IntCounter = register_int_counter!(
+        format!("{}_api_requests", *METRICS_PREFIX),
+        "Number of API requests"
+    )
+    .unwrap();
 }
 
-/// Updates all database-derived metrics by querying current counts.
+/// Point-in-time counts of the main application entities.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct AppStats {
+    /// Number of polls that have not yet expired
+    pub active_polls: i64,
+    /// Total number of polls
+    pub total_polls: i64,
+    /// Total number of votes cast
+    pub total_votes: i64,
+    /// Total number of registered users
+    pub total_users: i64,
+}
+
+/// Queries current poll, vote, and user counts from the database.
 /// 
-/// This function refreshes the Prometheus metrics with current database
-/// statistics including poll counts, vote counts, and user counts.
+/// Shared by the Prometheus metrics and the JSON status endpoint. Poll
+/// counts leave out archived polls when `METRICS_INCLUDE_ARCHIVED=false`.
 /// 
 /// # Arguments
 /// * `pool` - Database connection pool
 /// 
 /// # Returns
-/// * `Ok(())` - Metrics updated successfully
-/// * `Err(sqlx::Error)` - Database error during metric collection
-pub async fn update_metrics(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+/// * `Ok(AppStats)` - Current counts
+/// * `Err(sqlx::Error)` - Database error during collection
+pub async fn collect_stats(pool: &SqlitePool) -> Result<AppStats, sqlx::Error> {
     // Get active polls count
-    let active_polls: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM polls WHERE expires_at > datetime('now')")
-            .fetch_one(pool)
-            .await?;
-    ACTIVE_POLLS.set(active_polls);
+    let active_polls: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM polls WHERE julianday(expires_at) > julianday(?) AND (? OR archived = 0)",
+    )
+    .bind(chrono::Utc::now())
+    .bind(config::get().metrics_include_archived)
+    .fetch_one(pool)
+    .await?;
 
     // Get total polls count
-    let total_polls: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM polls")
+    let total_polls: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM polls WHERE ? OR archived = 0")
+        .bind(config::get().metrics_include_archived)
         .fetch_one(pool)
         .await?;
-    TOTAL_POLLS.set(total_polls);
 
     // Get total votes count
     let total_votes: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM votes")
         .fetch_one(pool)
         .await?;
-    TOTAL_VOTES.set(total_votes);
 
     // Get total users count
     let total_users: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
         .fetch_one(pool)
         .await?;
-    TOTAL_USERS.set(total_users);
+
+    Ok(AppStats {
+        active_polls,
+        total_polls,
+        total_votes,
+        total_users,
+    })
+}
+
+/// Site-wide aggregates shown on the admin statistics page.
+#[derive(Debug, Clone, Serialize)]
+pub struct AdminStats {
+    /// Total number of registered users
+    pub total_users: i64,
+    /// Number of users with admin privileges
+    pub admin_users: i64,
+    /// Total number of polls
+    pub total_polls: i64,
+    /// Number of polls that have not yet expired
+    pub active_polls: i64,
+    /// Number of polls that have expired
+    pub expired_polls: i64,
+    /// Total number of votes cast
+    pub total_votes: i64,
+    /// Mean number of options per poll, to one decimal place (0 when there are no polls)
+    pub avg_options_per_poll: f64,
+    /// Mean number of distinct voters per poll, to one decimal place (0 when there are no polls)
+    pub avg_voters_per_poll: f64,
+    /// Day (YYYY-MM-DD, UTC) with the most votes cast, if any votes exist
+    pub busiest_day: Option<String>,
+    /// Number of votes cast on the busiest day
+    pub busiest_day_votes: i64,
+}
+
+/// Rounds a value to one decimal place for display.
+fn round_tenths(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Computes site-wide aggregates for the admin statistics page.
+///
+/// Ties for the busiest day go to the most recent day.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(AdminStats)` - Current aggregates
+/// * `Err(sqlx::Error)` - Database error during collection
+pub async fn get_admin_stats(pool: &SqlitePool) -> Result<AdminStats, sqlx::Error> {
+    let (total_users, admin_users): (i64, i64) =
+        sqlx::query_as("SELECT COUNT(*), COALESCE(SUM(is_admin), 0) FROM users")
+            .fetch_one(pool)
+            .await?;
+
+    let (total_polls, active_polls): (i64, i64) =
+        sqlx::query_as(
+            "SELECT COUNT(*), COALESCE(SUM(julianday(expires_at) > julianday(?)), 0) FROM polls",
+        )
+        .bind(chrono::Utc::now())
+        .fetch_one(pool)
+        .await?;
+
+    let total_votes: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM votes")
+        .fetch_one(pool)
+        .await?;
+
+    let avg_options_per_poll: Option<f64> = sqlx::query_scalar(
+        "SELECT AVG(option_count) FROM (
+             SELECT COUNT(o.id) as option_count
+             FROM polls p LEFT JOIN options o ON o.poll_id = p.id
+             GROUP BY p.id
+         )",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let avg_voters_per_poll: Option<f64> = sqlx::query_scalar(
+        "SELECT AVG(voter_count) FROM (
+             SELECT COUNT(DISTINCT v.user_id) as voter_count
+             FROM polls p
+             LEFT JOIN options o ON o.poll_id = p.id
+             LEFT JOIN votes v ON v.option_id = o.id
+             GROUP BY p.id
+         )",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let busiest_day: Option<(String, i64)> = sqlx::query_as(
+        "SELECT date(created_at) as day, COUNT(*) as vote_count FROM votes
+         GROUP BY day
+         ORDER BY vote_count DESC, day DESC
+         LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(AdminStats {
+        total_users,
+        admin_users,
+        total_polls,
+        active_polls,
+        expired_polls: total_polls - active_polls,
+        total_votes,
+        avg_options_per_poll: round_tenths(avg_options_per_poll.unwrap_or(0.0)),
+        avg_voters_per_poll: round_tenths(avg_voters_per_poll.unwrap_or(0.0)),
+        busiest_day_votes: busiest_day.as_ref().map_or(0, |(_, count)| *count),
+        busiest_day: busiest_day.map(|(day, _)| day),
+    })
+}
+
+/// Longest window, in days, the admin trends page covers.
+pub const MAX_TREND_DAYS: i64 = 365;
+
+/// Polls created and votes cast on one day (UTC).
+#[derive(Debug, Clone, Serialize)]
+pub struct TrendDay {
+    /// The day, as YYYY-MM-DD
+    pub day: String,
+    /// Number of polls created that day
+    pub polls_created: i64,
+    /// Number of votes cast that day
+    pub votes_cast: i64,
+}
+
+/// Daily poll creation and voting over a window ending today, shown on the
+/// admin trends page.
+#[derive(Debug, Clone, Serialize)]
+pub struct Trends {
+    /// Number of days covered, including today
+    pub days: i64,
+    /// One entry per day, oldest first, with zeros for days without activity
+    pub daily: Vec<TrendDay>,
+    /// Polls created over the whole window
+    pub total_polls_created: i64,
+    /// Votes cast over the whole window
+    pub total_votes_cast: i64,
+    /// Most polls created on a single day in the window
+    pub peak_polls_created: i64,
+    /// Most votes cast on a single day in the window
+    pub peak_votes_cast: i64,
+}
+
+/// Counts rows per day (UTC) of `created_at` in `table`, from `since` on.
+async fn count_by_day(
+    pool: &SqlitePool,
+    table: &str,
+    since: chrono::NaiveDate,
+) -> Result<HashMap<String, i64>, sqlx::Error> {
+    // Compare as Julian days, since timestamps may be stored in either
+    // SQLite's or RFC 3339 format
+    let rows: Vec<(String, i64)> = sqlx::query_as(&format!(
+        "SELECT date(created_at) as day, COUNT(*) FROM {}
+         WHERE julianday(created_at) >= julianday(?)
+         GROUP BY day",
+        table
+    ))
+    .bind(since.format("%Y-%m-%d").to_string())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().collect())
+}
+
+/// Computes daily counts of polls created and votes cast over the last
+/// `days` days, including today.
+///
+/// Each table is counted with one grouped query; days without activity
+/// are filled in with zeros.
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `days` - Length of the window, from 1 to [`MAX_TREND_DAYS`]
+///
+/// # Returns
+/// * `Ok(Trends)` - The daily counts and their totals
+/// * `Err(sqlx::Error)` - Database error if a query fails
+pub async fn get_trends(pool: &SqlitePool, days: i64) -> Result<Trends, sqlx::Error> {
+    let days = days.clamp(1, MAX_TREND_DAYS);
+    let today = chrono::Utc::now().date_naive();
+    let since = today - chrono::Duration::days(days - 1);
+
+    let polls = count_by_day(pool, "polls", since).await?;
+    let votes = count_by_day(pool, "votes", since).await?;
+
+    let daily: Vec<TrendDay> = since
+        .iter_days()
+        .take(days as usize)
+        .map(|date| {
+            let day = date.format("%Y-%m-%d").to_string();
+            TrendDay {
+                polls_created: polls.get(&day).copied().unwrap_or(0),
+                votes_cast: votes.get(&day).copied().unwrap_or(0),
+                day,
+            }
+        })
+        .collect();
+
+    Ok(Trends {
+        days,
+        total_polls_created: daily.iter().map(|day| day.polls_created).sum(),
+        total_votes_cast: daily.iter().map(|day| day.votes_cast).sum(),
+        peak_polls_created: daily.iter().map(|day| day.polls_created).max().unwrap_or(0),
+        peak_votes_cast: daily.iter().map(|day| day.votes_cast).max().unwrap_or(0),
+        daily,
+    })
+}
+
+/// Updates all database-derived metrics by querying current counts.
+/// 
+/// This function refreshes the Prometheus metrics with current database
+/// statistics including poll counts, vote counts, and user counts.
+/// 
+/// # Arguments
+/// * `pool` - Database connection pool
+/// 
+/// # Returns
+/// * `Ok(())` - Metrics updated successfully
+/// * `Err(sqlx::Error)` - Database error during metric collection
+pub async fn update_metrics(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    let stats = collect_stats(pool).await?;
+
+    ACTIVE_POLLS.set(stats.active_polls);
+    TOTAL_POLLS.set(stats.total_polls);
+    TOTAL_VOTES.set(stats.total_votes);
+    TOTAL_USERS.set(stats.total_users);
 
     Ok(())
 }
@@ -274,6 +801,61 @@ pub fn increment_failed_login() {
     FAILED_LOGINS.inc();
 }
 
+/// Increments the votes cast counter.
+/// 
+/// Unlike the total votes gauge, this only ever goes up, so Prometheus can
+/// compute a voting rate from it. Called for every vote added.
+pub fn increment_votes_cast() {
+    VOTES_CAST.inc();
+}
+
+/// Increments the votes removed counter.
+/// 
+/// Called for every vote a user takes back.
+pub fn increment_votes_removed() {
+    VOTES_REMOVED.inc();
+}
+
+/// Increments the polls created counter.
+/// 
+/// Complements the total polls gauge with a count Prometheus can compute a
+/// creation rate from. Called for every poll created, however it was made.
+pub fn increment_polls_created() {
+    POLLS_CREATED.inc();
+}
+
+/// Who deleted a poll, for the `initiator` label on the deletion counter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollDeleter {
+    /// The poll's own creator (including an admin deleting their own poll)
+    Creator,
+    /// An admin deleting someone else's poll, directly or by deleting its creator
+    Admin,
+}
+
+impl PollDeleter {
+    /// Returns the label value used in the metrics.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PollDeleter::Creator => "creator",
+            PollDeleter::Admin => "admin",
+        }
+    }
+}
+
+/// Adds to the polls deleted counters.
+/// 
+/// Called for every poll deleted, including polls removed along with
+/// their creator's account.
+/// 
+/// # Arguments
+/// * `deleter` - Who deleted the polls
+/// * `count` - Number of polls deleted
+pub fn increment_polls_deleted(deleter: PollDeleter, count: u64) {
+    POLLS_DELETED.inc_by(count);
+    POLLS_DELETED_BY.with_label_values(&[deleter.as_str()]).inc_by(count);
+}
+
 // Increment the API requests counter
 // pub fn increment_api_request() {
 //     API_REQUESTS.inc();
@@ -281,18 +863,21 @@ pub fn increment_failed_login() {
 
 /// Generates a Prometheus-formatted metrics response.
 /// 
-/// This function updates all metrics from the database and returns
-/// a text response in Prometheus exposition format suitable for
-/// scraping by monitoring systems.
+/// This function updates the metrics from the database when the refresh
+/// interval has elapsed and returns a text response in Prometheus exposition
+/// format suitable for scraping by monitoring systems.
 /// 
 /// # Arguments
 /// * `pool` - Database connection pool for updating metrics
+/// * `refresh` - Tracks when the metrics were last refreshed
 /// 
 /// # Returns
 /// String containing Prometheus-formatted metrics data
-pub async fn get_metrics(pool: &SqlitePool) -> String {
-    // Update metrics from database
-    let _ = update_metrics(pool).await;
+pub async fn get_metrics(pool: &SqlitePool, refresh: &MetricsRefresh) -> String {
+    // Update metrics from database, at most once per refresh interval
+    if refresh.claim() && update_metrics(pool).await.is_err() {
+        refresh.reset();
+    }
 
     // Gather all registered metrics
     let mut buffer = Vec::new();
@@ -307,3 +892,339 @@ pub async fn get_metrics(pool: &SqlitePool) -> String {
 // pub fn track_request(_request: &Request) {
 //     increment_api_request();
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn ensure_admin_creates_admin_once(pool: SqlitePool) -> sqlx::Result<()> {
+        assert!(ensure_admin(&pool, "admin", "first").await?);
+        assert!(!ensure_admin(&pool, "admin", "second").await?);
+        assert!(!ensure_admin(&pool, "other", "third").await?);
+
+        let admins: Vec<(String, String)> =
+            sqlx::query_as("SELECT username, password_hash FROM users WHERE is_admin = 1")
+                .fetch_all(&pool)
+                .await?;
+        assert_eq!(admins.len(), 1);
+        assert_eq!(admins[0].0, "admin");
+        assert!(crate::password::verify("first", &admins[0].1));
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn ensure_admin_keeps_existing_admin(pool: SqlitePool) -> sqlx::Result<()> {
+        sqlx::query("INSERT INTO users (username, password_hash, is_admin) VALUES ('root', '!', 1)")
+            .execute(&pool)
+            .await?;
+
+        assert!(!ensure_admin(&pool, "admin", "admin").await?);
+
+        let users: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(users, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn resolves_sqlite_urls_and_paths() {
+        let dir = std::env::temp_dir();
+        let expected = dir.join("game_night.db");
+        let file = expected.to_str().unwrap();
+
+        for url in [
+            format!("sqlite://{}", file),
+            format!("sqlite:{}", file),
+            format!("sqlite:{}?mode=rwc", file),
+            format!("  {}  ", file),
+        ] {
+            assert_eq!(resolve_database_path(&url).unwrap(), expected, "{}", url);
+        }
+        assert!(resolve_database_path("sqlite:game_night.db").unwrap().is_absolute());
+    }
+
+    #[test]
+    fn rejects_unusable_database_urls() {
+        let missing_dir = std::env::temp_dir().join("no-such-dir").join("game_night.db");
+        let dir = std::env::temp_dir();
+
+        for url in [
+            "sqlite:".to_string(),
+            "sqlite://?mode=rwc".to_string(),
+            "postgres://localhost/game_night".to_string(),
+            "sqlite::memory:".to_string(),
+            "sqlite:file::memory:?cache=shared".to_string(),
+            format!("sqlite:{}", missing_dir.display()),
+            format!("sqlite:{}", dir.display()),
+        ] {
+            assert!(resolve_database_path(&url).is_err(), "{}", url);
+        }
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn exported_metric_names_use_the_configured_prefix(pool: SqlitePool) -> sqlx::Result<()> {
+        increment_login_attempt();
+        let metrics = get_metrics(&pool, &MetricsRefresh::new(Duration::ZERO)).await;
+
+        let prefix = &config::get().metrics_prefix;
+        for name in ["active_polls", "total_polls", "total_votes", "total_users", "login_attempts"] {
+            let line = format!("\n{}_{} ", prefix, name);
+            assert!(metrics.contains(&line), "{} missing from:\n{}", line.trim(), metrics);
+        }
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn ready_only_once_every_migration_is_applied(pool: SqlitePool) -> sqlx::Result<()> {
+        assert!(check_ready(&pool).await?);
+
+        sqlx::query("DELETE FROM _sqlx_migrations WHERE version = (SELECT MAX(version) FROM _sqlx_migrations)")
+            .execute(&pool)
+            .await?;
+        assert!(!check_ready(&pool).await?);
+
+        pool.close().await;
+        assert!(check_ready(&pool).await.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn metrics_refresh_is_claimed_once_per_interval() {
+        let refresh = MetricsRefresh::new(Duration::from_secs(60));
+        assert!(refresh.claim());
+        assert!(!refresh.claim());
+
+        // A failed refresh is retried on the next scrape
+        refresh.reset();
+        assert!(refresh.claim());
+        assert!(!refresh.claim());
+
+        let every_scrape = MetricsRefresh::new(Duration::ZERO);
+        assert!(every_scrape.claim());
+        assert!(every_scrape.claim());
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn admin_stats_count_polls_votes_and_averages(pool: SqlitePool) -> sqlx::Result<()> {
+        let empty = get_admin_stats(&pool).await?;
+        assert_eq!((empty.total_polls, empty.avg_options_per_poll), (0, 0.0));
+        assert_eq!(empty.busiest_day, None);
+
+        sqlx::query(
+            "INSERT INTO users (id, username, password_hash, is_admin)
+             VALUES (1, 'admin', '!', 1), (2, 'alice', '!', 0), (3, 'bob', '!', 0)",
+        )
+        .execute(&pool)
+        .await?;
+        let now = chrono::Utc::now();
+        sqlx::query(
+            "INSERT INTO polls (id, title, creator_id, expires_at)
+             VALUES (1, 'Open', 1, ?), (2, 'Closed', 1, ?)",
+        )
+        .bind(now + chrono::Duration::days(1))
+        .bind(now - chrono::Duration::days(1))
+        .execute(&pool)
+        .await?;
+        sqlx::query(
+            "INSERT INTO options (id, poll_id, text)
+             VALUES (1, 1, 'Catan'), (2, 1, 'Azul'), (3, 2, 'Go')",
+        )
+        .execute(&pool)
+        .await?;
+        sqlx::query(
+            "INSERT INTO votes (user_id, option_id, created_at) VALUES
+             (2, 1, '2024-05-01 18:00:00'),
+             (3, 1, '2024-05-01 19:00:00'),
+             (2, 3, '2024-05-02 10:00:00')",
+        )
+        .execute(&pool)
+        .await?;
+
+        let stats = get_admin_stats(&pool).await?;
+        assert_eq!((stats.total_users, stats.admin_users), (3, 1));
+        assert_eq!((stats.total_polls, stats.active_polls, stats.expired_polls), (2, 1, 1));
+        assert_eq!(stats.total_votes, 3);
+        assert_eq!(stats.avg_options_per_poll, 1.5);
+        assert_eq!(stats.avg_voters_per_poll, 1.5);
+        assert_eq!(stats.busiest_day.as_deref(), Some("2024-05-01"));
+        assert_eq!(stats.busiest_day_votes, 2);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn voting_and_unvoting_bump_the_vote_counters(pool: SqlitePool) -> sqlx::Result<()> {
+        sqlx::query("INSERT INTO users (id, username, password_hash) VALUES (1, 'alice', '!')")
+            .execute(&pool)
+            .await?;
+        sqlx::query(
+            "INSERT INTO polls (id, title, creator_id, expires_at) VALUES (1, 'Next game', 1, ?)",
+        )
+        .bind(chrono::Utc::now() + chrono::Duration::days(1))
+        .execute(&pool)
+        .await?;
+        sqlx::query("INSERT INTO options (id, poll_id, text) VALUES (1, 1, 'Catan')")
+            .execute(&pool)
+            .await?;
+
+        // Other tests vote at the same time, so only check that the counters moved
+        let (cast, removed) = (VOTES_CAST.get(), VOTES_REMOVED.get());
+        crate::controllers::polls::vote_on_poll(&pool, 1, 1, 1, None).await?;
+        assert!(VOTES_CAST.get() > cast);
+        crate::controllers::polls::vote_on_poll(&pool, 1, 1, 1, None).await?;
+        assert!(VOTES_REMOVED.get() > removed);
+
+        let metrics = get_metrics(&pool, &MetricsRefresh::new(Duration::ZERO)).await;
+        let prefix = &config::get().metrics_prefix;
+        for name in ["votes_cast_total", "votes_removed_total"] {
+            assert!(metrics.contains(&format!("\n{}_{} ", prefix, name)), "{} missing", name);
+        }
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn creating_and_deleting_polls_bump_the_poll_counters(
+        pool: SqlitePool,
+    ) -> sqlx::Result<()> {
+        use crate::controllers::polls;
+        use crate::models::NewPollForm;
+
+        sqlx::query(
+            "INSERT INTO users (id, username, password_hash, is_admin)
+             VALUES (1, 'alice', '!', 0), (2, 'admin', '!', 1)",
+        )
+        .execute(&pool)
+        .await?;
+        let form = |title: &str| NewPollForm {
+            title: title.to_string(),
+            description: None,
+            expires_at: (chrono::Utc::now() + chrono::Duration::days(1))
+                .format("%Y-%m-%dT%H:%M")
+                .to_string(),
+            votes_lock_at: None,
+            quorum: None,
+            open_voters: false,
+            require_vote_comment: false,
+            auto_close_at_quorum: false,
+            poll_kind: None,
+            creator_can_vote: None,
+            multi_select: None,
+            allow_write_in: false,
+            options: "Catan".to_string(),
+            options_separator: None,
+            copy_options_from: None,
+            confirm_duplicate: false,
+        };
+        let by = |initiator: PollDeleter| POLLS_DELETED_BY.with_label_values(&[initiator.as_str()]);
+
+        // Other tests create and delete polls at the same time, so only check
+        // that the counters moved
+        let created = POLLS_CREATED.get();
+        let first = polls::create_poll(&pool, &form("Next game"), 1).await?;
+        let second = polls::create_poll(&pool, &form("Game after"), 1).await?;
+        assert!(POLLS_CREATED.get() >= created + 2);
+
+        let (deleted, by_creator, by_admin) =
+            (POLLS_DELETED.get(), by(PollDeleter::Creator).get(), by(PollDeleter::Admin).get());
+        polls::delete_poll(&pool, first, 1, false).await?;
+        assert!(by(PollDeleter::Creator).get() > by_creator);
+        polls::delete_poll(&pool, second, 2, true).await?;
+        assert!(by(PollDeleter::Admin).get() > by_admin);
+        assert!(POLLS_DELETED.get() >= deleted + 2);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn trends_count_each_day_in_the_window(pool: SqlitePool) -> sqlx::Result<()> {
+        let now = chrono::Utc::now();
+        sqlx::query("INSERT INTO users (id, username, password_hash) VALUES (1, 'alice', '!')")
+            .execute(&pool)
+            .await?;
+        // Today's poll gets SQLite's own timestamp format, the others RFC 3339
+        sqlx::query(
+            "INSERT INTO polls (id, title, creator_id, expires_at, created_at)
+             VALUES (1, 'Today', 1, ?1, CURRENT_TIMESTAMP),
+                    (2, 'Two days ago', 1, ?1, ?2),
+                    (3, 'Too old', 1, ?1, ?3)",
+        )
+        .bind(now + chrono::Duration::days(1))
+        .bind(now - chrono::Duration::days(2))
+        .bind(now - chrono::Duration::days(40))
+        .execute(&pool)
+        .await?;
+        sqlx::query("INSERT INTO options (id, poll_id, text) VALUES (1, 1, 'Catan')")
+            .execute(&pool)
+            .await?;
+        sqlx::query("INSERT INTO votes (user_id, option_id, created_at) VALUES (1, 1, ?)")
+            .bind(now - chrono::Duration::days(1))
+            .execute(&pool)
+            .await?;
+
+        let trends = get_trends(&pool, 30).await?;
+        assert_eq!(trends.days, 30);
+        assert_eq!(trends.daily.len(), 30);
+        let today = &trends.daily[29];
+        assert_eq!(today.day, now.format("%Y-%m-%d").to_string());
+        assert_eq!((today.polls_created, today.votes_cast), (1, 0));
+        assert_eq!((trends.daily[28].polls_created, trends.daily[28].votes_cast), (0, 1));
+        assert_eq!(trends.daily[27].polls_created, 1);
+        assert_eq!((trends.total_polls_created, trends.total_votes_cast), (2, 1));
+        assert_eq!((trends.peak_polls_created, trends.peak_votes_cast), (1, 1));
+
+        assert_eq!(get_trends(&pool, 0).await?.daily.len(), 1);
+        assert_eq!(get_trends(&pool, 1000).await?.days, MAX_TREND_DAYS);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn pending_migrations_lists_what_is_not_applied(pool: SqlitePool) -> sqlx::Result<()> {
+        assert!(pending_migrations(&pool).await?.is_empty());
+
+        let latest = MIGRATOR.iter().last().unwrap().version;
+        sqlx::query("DELETE FROM _sqlx_migrations WHERE version = ?")
+            .bind(latest)
+            .execute(&pool)
+            .await?;
+        let pending: Vec<i64> = pending_migrations(&pool)
+            .await?
+            .iter()
+            .map(|migration| migration.version)
+            .collect();
+        assert_eq!(pending, [latest]);
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = false)]
+    async fn pending_migrations_writes_nothing(pool: SqlitePool) -> sqlx::Result<()> {
+        assert_eq!(pending_migrations(&pool).await?.len(), MIGRATOR.iter().count());
+
+        let tables: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table'")
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(tables, 0);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn lists_only_admins_still_on_the_default_password(pool: SqlitePool) -> sqlx::Result<()> {
+        let default_hash = bcrypt::hash(DEFAULT_ADMIN_PASSWORD, 4).unwrap();
+        for (username, password_hash, is_admin) in [
+            ("zed", default_hash.clone(), true),
+            ("amy", default_hash.clone(), true),
+            ("root", bcrypt::hash("changed", 4).unwrap(), true),
+            ("member", default_hash, false),
+        ] {
+            sqlx::query("INSERT INTO users (username, password_hash, is_admin) VALUES (?, ?, ?)")
+                .bind(username)
+                .bind(password_hash)
+                .bind(is_admin)
+                .execute(&pool)
+                .await?;
+        }
+
+        assert_eq!(admins_with_default_password(&pool).await?, ["amy", "zed"]);
+        Ok(())
+    }
+}
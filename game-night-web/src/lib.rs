@@ -5,15 +5,24 @@
 //!
 //! ## Modules
 //! - [`auth`] - Authentication and authorization system
+//! - [`config`] - Environment settings, loaded and validated at startup
+//! - [`content_filter`] - Optional blocklist for user-submitted content
 //! - [`controllers`] - Business logic layer for handling requests
 //! - [`db`] - Database connection and operations
 //! - [`models`] - Data structures and models
+//! - [`password`] - Password hashing with a configurable algorithm
 //! - [`routes`] - HTTP route definitions and handlers
 
 /// Authentication and authorization module providing user login/logout,
 /// session management, and role-based access control.
 pub mod auth;
 
+/// Configuration module gathering the application's environment variables.
+pub mod config;
+
+/// Content filter module providing an optional word blocklist for poll text.
+pub mod content_filter;
+
 /// Controllers module containing business logic for handling HTTP requests
 /// and coordinating between routes and database operations.
 pub mod controllers;
@@ -24,5 +33,8 @@ pub mod db;
 /// Models module defining data structures, forms, and database entity representations.
 pub mod models;
 
+/// Password module hashing and verifying passwords with bcrypt or argon2.
+pub mod password;
+
 /// Routes module defining HTTP endpoints and request handlers for the web application.
 pub mod routes;
\ No newline at end of file
@@ -13,28 +13,136 @@
 extern crate rocket;
 use dotenv::dotenv;
 use rocket::fairing::AdHoc;
+use rocket::figment::Figment;
 use rocket::fs::{relative, FileServer};
-use rocket::response::Redirect;
-use rocket::{catch, catchers, uri};
+use rocket::request::Request;
+use rocket::response::{Flash, Redirect};
+use rocket::{catch, catchers, uri, Either};
 use rocket_dyn_templates::Template;
-use std::env;
+use sqlx::SqlitePool;
+use std::time::{Duration, Instant};
 
-mod auth;
-mod controllers;
-mod db;
-mod models;
-mod routes;
+use game_night_web::controllers::users;
+use game_night_web::config::{self, Config};
+use game_night_web::{auth, db, routes};
 
 /// Error catcher for 401 Unauthorized responses.
 ///
 /// This catcher intercepts 401 status responses and redirects unauthenticated
-/// users to the login page instead of showing a raw error response.
+/// users to the login page instead of showing a raw error response. Users
+/// whose session has ended are told why they need to log in again.
 ///
 /// # Returns
-/// Redirect to the login page
+/// * `Left(Redirect)` - Plain redirect to the login page
+/// * `Right(Flash<Redirect>)` - Redirect to the login page explaining the session ended
 #[catch(401)]
-fn unauthorized() -> Redirect {
-    Redirect::to(uri!(routes::login_page))
+fn unauthorized(request: &Request<'_>) -> Either<Redirect, Flash<Redirect>> {
+    if auth::is_stale_session(request) {
+        Either::Right(Flash::error(
+            Redirect::to(uri!(routes::login_page)),
+            "Your session ended, please log in again.",
+        ))
+    } else {
+        Either::Left(Redirect::to(uri!(routes::login_page)))
+    }
+}
+
+/// Error catcher for 413 Payload Too Large responses.
+///
+/// Rocket rejects form submissions over the configured size limit with a
+/// 413; this replaces the generic error page with an explanation.
+///
+/// # Returns
+/// A plain-text message stating the limit
+#[catch(413)]
+fn payload_too_large() -> String {
+    format!(
+        "The submitted form is too large. Forms are limited to {} KB; \
+         try submitting fewer or shorter options.",
+        config::get().max_form_bytes / 1024
+    )
+}
+
+/// How often accounts are checked for inactivity, when enabled.
+const INACTIVE_ACCOUNT_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Builds the Rocket configuration figment.
+///
+/// Configuration is resolved in the following order, with later sources
+/// taking precedence:
+/// 1. Rocket defaults (address `127.0.0.1`, port `8000`)
+/// 2. `Rocket.toml`, if present
+/// 3. `ROCKET_*` environment variables (e.g. `ROCKET_ADDRESS`, `ROCKET_PORT`)
+/// 4. `TLS_CERT` and `TLS_KEY` from the application configuration, which
+///    enable TLS
+/// 5. `MAX_FORM_BYTES` from the application configuration, which bounds the
+///    size of form submissions and their text fields
+///
+/// # Arguments
+/// * `config` - The loaded application configuration
+///
+/// # Panics
+/// Panics if only one of `TLS_CERT` and `TLS_KEY` is set, since TLS cannot
+/// be enabled without both a certificate chain and a private key.
+/// Also panics outside the debug profile if no secret key is configured
+/// (see [`check_secret_key`]).
+fn figment(config: &Config) -> Figment {
+    let figment = rocket::Config::figment();
+    check_secret_key(&figment);
+
+    // Bound form bodies so oversized submissions are refused up front
+    let max_form_bytes = config.max_form_bytes;
+    let figment = figment
+        .merge(("limits.form", max_form_bytes))
+        .merge(("limits.string", max_form_bytes));
+
+    match (config.tls_cert.as_deref(), config.tls_key.as_deref()) {
+        (Some(cert), Some(key)) => {
+            log::info!("TLS enabled using certificate {} and key {}", cert, key);
+            figment
+                .merge(("tls.certs", cert))
+                .merge(("tls.key", key))
+        }
+        (None, None) => figment,
+        (Some(_), None) => {
+            panic!("TLS_CERT is set but TLS_KEY is not; set both to enable TLS or neither to disable it")
+        }
+        (None, Some(_)) => {
+            panic!("TLS_KEY is set but TLS_CERT is not; set both to enable TLS or neither to disable it")
+        }
+    }
+}
+
+/// Checks that a secret key is configured for the session cookies.
+///
+/// Login sessions are stored in private cookies, which are encrypted with
+/// `ROCKET_SECRET_KEY`. In the debug profile Rocket generates a throwaway
+/// key, so sessions only last until the server restarts; a warning is
+/// logged. In any other profile a missing key is a misconfiguration that
+/// would otherwise show up as users being logged out over and over, so
+/// startup is aborted with instructions instead.
+///
+/// # Arguments
+/// * `figment` - The resolved Rocket configuration
+///
+/// # Panics
+/// Panics outside the debug profile if no secret key is configured.
+fn check_secret_key(figment: &Figment) {
+    if auth::secret_key_configured(figment) {
+        return;
+    }
+
+    if figment.profile() == rocket::Config::DEBUG_PROFILE {
+        log::warn!(
+            "ROCKET_SECRET_KEY is not set; using a generated key, so logins will not survive a restart"
+        );
+    } else {
+        panic!(
+            "ROCKET_SECRET_KEY must be set in the {} profile so login sessions can be encrypted. \
+             Generate one with `openssl rand -base64 32` and set it in the environment or .env file.",
+            figment.profile()
+        );
+    }
 }
 
 /// Main application entry point that configures and launches the Rocket web server.
@@ -42,12 +150,15 @@ fn unauthorized() -> Redirect {
 /// This function:
 /// - Loads environment variables from .env file
 /// - Initializes logging
+/// - Loads and validates the application configuration
+/// - Resolves listen address, port, and optional TLS configuration
 /// - Sets up all HTTP routes
 /// - Configures static file serving
 /// - Attaches template engine
 /// - Initializes database connection pool
 /// - Runs database migrations
 /// - Creates default admin user if needed
+/// - Starts the inactive account check, if `INACTIVE_ACCOUNT_DAYS` is set
 ///
 /// # Returns
 /// A configured Rocket instance ready for launch
@@ -59,7 +170,13 @@ fn rocket() -> _ {
     // Configure logging
     env_logger::init();
 
-    rocket::build()
+    // Stop before launching if any setting is invalid
+    let config = config::init().unwrap_or_else(|err| panic!("Invalid configuration: {}", err));
+
+    rocket::custom(figment(config))
+        .manage(config.clone())
+        .manage(routes::StartTime(Instant::now()))
+        .manage(db::MetricsRefresh::from_config(config))
         .mount(
             "/",
             rocket::routes![
@@ -67,42 +184,197 @@ fn rocket() -> _ {
                 routes::login_page,
                 routes::login_post,
                 routes::logout,
+                routes::reauth_page,
+                routes::reauth_post,
+                routes::welcome,
+                routes::complete_welcome,
                 routes::dashboard,
+                routes::notifications,
+                routes::mark_notification_read,
+                routes::mark_all_notifications_read,
                 routes::get_polls,
+                routes::archived_polls,
                 routes::poll_detail,
+                routes::poll_results,
+                routes::poll_counts_json,
+                routes::poll_timeline_json,
+                routes::poll_meta_json,
+                routes::poll_options_json,
                 routes::poll_voters,
+                routes::option_voters_json,
+                routes::export_poll_voters_csv,
+                routes::poll_ballots,
                 routes::create_poll_page,
                 routes::create_poll_post,
+                routes::poll_template_json,
+                routes::import_poll,
                 routes::vote_on_poll,
+                routes::add_write_in,
                 routes::add_options_to_poll,
                 routes::remove_poll_option,
                 routes::delete_poll,
+                routes::undo_delete_poll,
+                routes::reopen_poll,
+                routes::nudge_non_voters,
+                routes::close_and_announce,
+                routes::archive_poll,
+                routes::finalize_poll,
+                routes::add_option_comment,
+                routes::poll_snapshot,
+                routes::create_runoff,
+                routes::calendar,
                 routes::profile,
+                routes::public_profile,
+                routes::current_user,
+                routes::export_my_data,
+                routes::dashboard_json,
                 routes::change_password,
                 routes::admin_users,
+                routes::export_users_csv,
                 routes::add_user_page,
                 routes::add_user_post,
+                routes::import_users,
                 routes::toggle_user_role,
-                routes::metrics_endpoint
+                routes::bulk_toggle_roles,
+                routes::set_vote_weight,
+                routes::revoke_user_sessions,
+                routes::enable_user,
+                routes::delete_user,
+                routes::merge_users,
+                routes::admin_audit,
+                routes::admin_stats,
+                routes::admin_security,
+                routes::admin_integrity,
+                routes::repair_integrity,
+                routes::admin_pending_polls,
+                routes::approve_poll,
+                routes::admin_stats_json,
+                routes::admin_trends,
+                routes::admin_trends_json,
+                routes::admin_settings,
+                routes::update_settings,
+                routes::update_announcement,
+                routes::broadcast,
+                routes::metrics_endpoint,
+                routes::status,
+                routes::health,
+                routes::ready
             ],
         )
         .mount("/static", FileServer::from(relative!("src/static")))
-        .register("/", catchers![unauthorized])
+        .register("/", catchers![unauthorized, payload_too_large])
         .attach(Template::fairing())
         .attach(AdHoc::try_on_ignite("Database Setup", |rocket| async {
             let pool = db::init_pool().await;
 
-            sqlx::migrate!("./migrations")
-                .run(&pool)
-                .await
-                .expect("failed to run database migrations");
+            if db::run_migrations_on_start() {
+                db::MIGRATOR
+                    .run(&pool)
+                    .await
+                    .expect("failed to run database migrations");
+            } else {
+                log::info!("RUN_MIGRATIONS_ON_START is off; skipping database migrations");
+            }
 
             // Initialize default admin user if needed
             if let Err(e) = db::init_default_admin(&pool).await {
                 log::error!("Failed to initialize default admin user: {}", e);
-                panic!("Failed to initialize default admin user: {}", e);
+                return Err(rocket);
             }
 
             Ok(rocket.manage(pool))
         }))
+        .attach(AdHoc::on_liftoff("Inactive Accounts", |rocket| {
+            Box::pin(async move {
+                let Some(days) = users::inactive_account_days() else {
+                    return;
+                };
+                let pool = rocket.state::<SqlitePool>().unwrap().clone();
+                log::info!("Disabling accounts after {} days without a login", days);
+
+                tokio::spawn(async move {
+                    let mut interval = tokio::time::interval(INACTIVE_ACCOUNT_CHECK_INTERVAL);
+                    loop {
+                        interval.tick().await;
+                        if let Err(err) = users::disable_inactive_accounts(&pool).await {
+                            log::error!("Failed to disable inactive accounts: {}", err);
+                        }
+                    }
+                });
+            })
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::http::{ContentType, Cookie, Status};
+    use rocket::local::asynchronous::Client;
+
+    /// Starts a client with one authenticated route and the 401 catcher.
+    async fn client(pool: &SqlitePool) -> Client {
+        let rocket = rocket::build()
+            .manage(pool.clone())
+            .mount("/", rocket::routes![routes::poll_ballots])
+            .register("/", catchers![unauthorized]);
+        Client::untracked(rocket).await.unwrap()
+    }
+
+    #[sqlx::test(migrator = "game_night_web::db::MIGRATOR")]
+    async fn ended_session_is_explained_on_the_login_redirect(pool: SqlitePool) {
+        let user_id = sqlx::query("INSERT INTO users (username, password_hash) VALUES ('alice', '!')")
+            .execute(&pool)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        let client = client(&pool).await;
+
+        // Without a session cookie the redirect carries no message
+        let response = client.get("/polls/1/ballots.json").dispatch().await;
+        assert_eq!(response.status(), Status::SeeOther);
+        assert_eq!(response.headers().get_one("Location"), Some("/login"));
+        assert!(response.cookies().get("_flash").is_none());
+
+        // A cookie from before the sessions were revoked gets the message
+        sqlx::query("UPDATE users SET session_epoch = 1 WHERE id = ?")
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let response = client
+            .get("/polls/1/ballots.json")
+            .private_cookie(Cookie::new("user_id", format!("{}:0", user_id)))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::SeeOther);
+        assert_eq!(response.headers().get_one("Location"), Some("/login"));
+        let flash = response.cookies().get("_flash").unwrap().value().to_string();
+        assert!(flash.ends_with("Your session ended, please log in again."), "{}", flash);
+    }
+
+    #[sqlx::test(migrator = "game_night_web::db::MIGRATOR")]
+    async fn oversized_forms_are_refused_with_an_explanation(pool: SqlitePool) {
+        let mut config = config::get().clone();
+        config.max_form_bytes = 1024;
+        let rocket = rocket::custom(figment(&config))
+            .manage(pool)
+            .mount("/", rocket::routes![routes::login_post])
+            .register("/", catchers![payload_too_large]);
+        let client = Client::untracked(rocket).await.unwrap();
+        let login = |password: String| {
+            client
+                .post("/login")
+                .header(ContentType::Form)
+                .body(format!("username=alice&password={}", password))
+                .dispatch()
+        };
+
+        let response = login("x".repeat(2048)).await;
+        assert_eq!(response.status(), Status::PayloadTooLarge);
+        let body = response.into_string().await.unwrap();
+        assert!(body.starts_with("The submitted form is too large."), "{}", body);
+
+        // A form within the limit reaches the route
+        assert_eq!(login("secret".to_string()).await.status(), Status::SeeOther);
+    }
 }
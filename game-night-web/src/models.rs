@@ -12,8 +12,11 @@
 
 use chrono::{DateTime, Utc};
 use rocket::form::FromForm;
+use rocket::fs::TempFile;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::collections::BTreeMap;
+use std::fmt;
 
 // ============================================================================
 // Common structures and enums
@@ -42,19 +45,17 @@ pub enum MessageType {
     Error,
 }
 
-impl ToString for MessageType {
-    /// Converts the MessageType enum to a string representation.
+impl fmt::Display for MessageType {
+    /// Formats the MessageType enum as its string representation.
     /// Used for CSS class names and template rendering.
-    ///
-    /// # Returns
-    /// A string representation of the message type
-    fn to_string(&self) -> String {
-        match self {
-            MessageType::Success => "success".to_string(),
-            MessageType::Info => "info".to_string(),
-            MessageType::Warning => "warning".to_string(),
-            MessageType::Error => "error".to_string(),
-        }
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MessageType::Success => "success",
+            MessageType::Info => "info",
+            MessageType::Warning => "warning",
+            MessageType::Error => "error",
+        };
+        f.write_str(name)
     }
 }
 
@@ -69,13 +70,41 @@ pub struct User {
     pub id: i64,
     /// Unique username for authentication
     pub username: String,
-    /// Bcrypt-hashed password (excluded from serialization for security)
+    /// Bcrypt or argon2 password hash (excluded from serialization for security)
     #[serde(skip_serializing)]
     pub password_hash: String,
     /// Whether the user has administrative privileges
     pub is_admin: bool,
     /// Timestamp when the user account was created
     pub created_at: DateTime<Utc>,
+    /// Session generation counter; incrementing it invalidates all existing sessions
+    #[serde(skip_serializing)]
+    pub session_epoch: i64,
+    /// Timestamp of the user's most recent successful login, if any
+    pub last_login_at: Option<DateTime<Utc>>,
+    /// How much each of the user's votes counts toward poll results (default 1)
+    pub vote_weight: i64,
+    /// Whether the account has been disabled for inactivity and can't log in
+    pub disabled: bool,
+    /// Whether the user has seen or skipped the welcome page
+    pub has_onboarded: bool,
+}
+
+/// Form data structure for leaving the welcome page.
+#[derive(Debug, FromForm, Deserialize)]
+pub struct WelcomeForm {
+    /// Page to continue to, such as the dashboard or the poll creation form
+    pub next: Option<String>,
+}
+
+/// Form data structure for confirming the current user's password before
+/// a sensitive action.
+#[derive(Debug, FromForm, Deserialize)]
+pub struct ReauthForm {
+    /// Plain text password (will be verified against stored hash)
+    pub password: String,
+    /// Page to return to once confirmed
+    pub next: Option<String>,
 }
 
 /// Form data structure for user login requests.
@@ -102,6 +131,14 @@ pub struct NewUserForm {
     pub is_admin: bool,
 }
 
+/// Multipart form data structure for bulk user import.
+/// Carries an uploaded CSV file with columns `username,password,is_admin`.
+#[derive(Debug, FromForm)]
+pub struct ImportUsersForm<'r> {
+    /// Uploaded CSV file
+    pub file: TempFile<'r>,
+}
+
 /// Form data structure for password change requests.
 /// Allows users to update their password with current password verification.
 #[derive(Debug, FromForm, Deserialize)]
@@ -124,27 +161,396 @@ pub struct ToggleRoleForm {
     pub set_admin: bool,
 }
 
+/// Form data structure for setting a user's vote weight.
+#[derive(Debug, FromForm, Deserialize)]
+pub struct VoteWeightForm {
+    /// How much each of the user's votes should count (1 to 100)
+    pub vote_weight: i64,
+}
+
+/// Form data structure for merging one user account into another.
+#[derive(Debug, FromForm, Deserialize)]
+pub struct MergeUsersForm {
+    /// ID of the account to merge and delete
+    pub source_id: i64,
+    /// ID of the account that keeps the merged polls and votes
+    pub target_id: i64,
+}
+
+/// A single role change within a bulk role update.
+#[derive(Debug, FromForm, Deserialize)]
+pub struct RoleChange {
+    /// ID of the user whose role should be changed
+    pub user_id: i64,
+    /// Whether the user should be an admin after the change
+    pub set_admin: bool,
+}
+
+/// Form data structure for changing several users' roles at once.
+/// Submitted from the user management table as `changes[N].user_id` and
+/// `changes[N].set_admin` pairs.
+#[derive(Debug, FromForm, Deserialize)]
+pub struct BulkRoleForm {
+    /// Requested role for each listed user
+    pub changes: Vec<RoleChange>,
+}
+
 impl User {
     /// Verifies a plain text password against the user's stored password hash.
     ///
+    /// Works with both bcrypt and argon2 hashes, whichever the stored hash is.
+    ///
     /// # Arguments
     /// * `password` - The plain text password to verify
     ///
     /// # Returns
     /// `true` if the password matches the stored hash, `false` otherwise
+    /// (including when the hash is malformed, which is logged)
     pub fn verify_password(&self, password: &str) -> bool {
-        bcrypt::verify(password, &self.password_hash).unwrap_or(false)
+        crate::password::verify(password, &self.password_hash)
+    }
+
+    /// Checks a plain text password against the user's stored hash, telling
+    /// a malformed hash apart from a wrong password.
+    ///
+    /// # Arguments
+    /// * `password` - The plain text password to check
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Whether the password matches
+    /// * `Err(PasswordHashError::MalformedHash)` - If the stored hash can't be
+    ///   read, so the password needs to be reset
+    pub fn check_password(
+        &self,
+        password: &str,
+    ) -> Result<bool, crate::password::PasswordHashError> {
+        crate::password::check(password, &self.password_hash)
     }
 
-    /// Hashes a plain text password using bcrypt with cost factor 12.
+    /// Hashes a plain text password with the algorithm chosen by
+    /// `PASSWORD_HASH_ALGO` (bcrypt by default).
     ///
     /// # Arguments
     /// * `password` - The plain text password to hash
     ///
     /// # Returns
     /// `Ok(String)` containing the hashed password, or `Err` if hashing fails
-    pub fn hash_password(password: &str) -> Result<String, bcrypt::BcryptError> {
-        bcrypt::hash(password, 12)
+    pub fn hash_password(password: &str) -> Result<String, crate::password::PasswordHashError> {
+        crate::password::hash(password)
+    }
+}
+
+/// Form data structure for revoking a user's sessions.
+/// Used by administrators to force-logout a possibly compromised account.
+#[derive(Debug, FromForm, Deserialize)]
+pub struct RevokeSessionsForm {
+    /// Must be set to confirm revoking the acting admin's own sessions
+    pub confirm_self: bool,
+}
+
+/// Public view of a user that is safe to show to other users.
+/// Never carries the password hash or session details.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PublicUserInfo {
+    /// Unique identifier for the user
+    pub id: i64,
+    /// The user's username
+    pub username: String,
+    /// Whether the user has administrator privileges
+    pub is_admin: bool,
+    /// Timestamp when the user account was created
+    pub created_at: DateTime<Utc>,
+    /// Number of polls the user has created
+    pub poll_count: i64,
+    /// Timestamp of the user's most recent login (only shown to admins)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_login_at: Option<DateTime<Utc>>,
+}
+
+/// The logged-in user's own account details and activity counts.
+/// Built on [`User`], so the password hash and session details are never
+/// serialized.
+#[derive(Debug, Clone, Serialize)]
+pub struct CurrentUserInfo {
+    /// The user's account details
+    #[serde(flatten)]
+    pub user: User,
+    /// Number of polls the user has created
+    pub polls_created: i64,
+    /// Number of votes the user has cast
+    pub votes_cast: i64,
+}
+
+/// A poll the user created, with its options, for the personal data export.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedPoll {
+    /// The poll and its creator
+    #[serde(flatten)]
+    pub poll: PollWithCreator,
+    /// The poll's options and their vote counts
+    pub options: Vec<PollOption>,
+}
+
+/// A poll's structure without its votes or creator, for sharing polls
+/// between instances. Served at `/polls/<id>/template.json` and accepted
+/// by the poll import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PollTemplate {
+    /// Template format version, see `polls::POLL_TEMPLATE_VERSION`
+    pub version: u32,
+    /// Title of the poll
+    pub title: String,
+    /// Optional description of the poll
+    pub description: Option<String>,
+    /// The poll's voting settings
+    pub settings: PollTemplateSettings,
+    /// The poll's options, in order
+    pub options: Vec<PollTemplateOption>,
+}
+
+/// Voting settings carried by a [`PollTemplate`].
+///
+/// Times are left out, since the importer supplies a new expiry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PollTemplateSettings {
+    /// Kind of options allowed: `mixed`, `text`, or `dates`
+    pub poll_kind: String,
+    /// Whether any user can see who voted for what
+    pub open_voters: bool,
+    /// Whether voters must leave a comment with their vote
+    pub require_vote_comment: bool,
+    /// Number of voters needed for the result to count, if any
+    pub quorum: Option<i64>,
+    /// Whether the poll closes once the quorum is reached
+    pub auto_close_at_quorum: bool,
+    /// Whether the poll's creator may vote on it
+    pub creator_can_vote: bool,
+    /// Whether voters may pick more than one option
+    pub multi_select: bool,
+    /// Whether voters may suggest their own options
+    pub allow_write_in: bool,
+}
+
+/// An option in a [`PollTemplate`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PollTemplateOption {
+    /// Option text (the date in YYYY-MM-DDTHH:MM form for date options)
+    pub text: String,
+    /// Whether the option is a date
+    pub is_date: bool,
+    /// Parsed date/time for date options
+    pub date_time: Option<DateTime<Utc>>,
+    /// Image shown with the option, if any
+    pub image_url: Option<String>,
+    /// Most votes the option accepts, if capped
+    pub max_votes: Option<i64>,
+}
+
+/// Multipart form data structure for importing a poll template.
+#[derive(Debug, FromForm)]
+pub struct ImportPollForm<'r> {
+    /// Uploaded template JSON file
+    pub file: TempFile<'r>,
+    /// Expiration date/time for the new poll in format YYYY-MM-DDTHH:MM
+    pub expires_at: String,
+}
+
+/// A vote the user cast, with the poll and option it was cast on,
+/// for the personal data export.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ExportedVote {
+    /// Unique identifier for the vote
+    pub id: i64,
+    /// ID of the poll the vote belongs to
+    pub poll_id: i64,
+    /// Title of the poll the vote belongs to
+    pub poll_title: String,
+    /// ID of the option voted for
+    pub option_id: i64,
+    /// Text of the option voted for
+    pub option_text: String,
+    /// Comment left with the vote, if any
+    pub vote_comment: Option<String>,
+    /// Timestamp when the vote was cast
+    pub created_at: DateTime<Utc>,
+}
+
+/// Everything the dashboard shows, for the dashboard JSON endpoint.
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardData {
+    /// The current user's account details
+    pub user: User,
+    /// Polls still open for voting, newest first
+    pub active_polls: Vec<DashboardPoll>,
+    /// Polls that have expired, most recently expired first
+    pub expired_polls: Vec<DashboardPoll>,
+    /// The current user's unread notifications, newest first
+    pub notifications: Vec<Notification>,
+    /// The current user's activity statistics
+    pub stats: UserStatsDetailed,
+}
+
+/// A poll listed on the dashboard, with its vote totals.
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardPoll {
+    /// The poll and its creator
+    #[serde(flatten)]
+    pub poll: PollWithCreator,
+    /// Number of votes cast across all of the poll's options
+    pub total_votes: i64,
+    /// Number of distinct users who have voted
+    pub voter_count: i64,
+}
+
+/// A poll together with how many votes it has received.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PollVoteSummary {
+    /// Unique identifier for the poll
+    pub id: i64,
+    /// The poll's title/question
+    pub title: String,
+    /// Number of votes cast across all of the poll's options
+    pub vote_count: i64,
+}
+
+/// Detailed activity statistics shown on a user's profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserStatsDetailed {
+    /// Number of polls the user has created
+    pub polls_created: i64,
+    /// Number of votes the user has cast
+    pub votes_cast: i64,
+    /// The user's poll that has received the most votes, if they created any
+    pub most_voted_poll: Option<PollVoteSummary>,
+    /// Number of the user's polls with a quorum that has been reached
+    pub polls_reached_quorum: i64,
+    /// Time of the user's first poll or vote, if any
+    pub first_activity_at: Option<DateTime<Utc>>,
+    /// Time of the user's most recent poll or vote, if any
+    pub last_activity_at: Option<DateTime<Utc>>,
+}
+
+// ============================================================================
+// Audit log models
+// ============================================================================
+/// Represents an entry in the audit log.
+/// Records who performed an administrative or security-relevant action and when.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditEntry {
+    /// Unique identifier for the entry
+    pub id: i64,
+    /// ID of the user who performed the action (None if the user was removed)
+    pub actor_id: Option<i64>,
+    /// Username of the acting user, if still present
+    pub actor_username: Option<String>,
+    /// Short dotted identifier for the action
+    pub action: String,
+    /// Human-readable description of the action
+    pub details: Option<String>,
+    /// Timestamp when the action was recorded
+    pub created_at: DateTime<Utc>,
+}
+
+/// Counts of inconsistent rows found, or removed, by a database integrity check.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    /// Votes for a missing option, an option of a missing poll, or by a missing user
+    pub orphaned_votes: i64,
+    /// Comments on a missing poll or option, or by a missing user
+    pub orphaned_comments: i64,
+    /// Options of a missing poll
+    pub orphaned_options: i64,
+    /// Polls that have no options at all (reported only, never removed)
+    pub polls_without_options: i64,
+}
+
+/// Represents an in-app notification for a user.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Notification {
+    /// Unique identifier for the notification
+    pub id: i64,
+    /// ID of the user being notified
+    pub user_id: i64,
+    /// Short identifier for the type of notification (e.g. `nudge`)
+    pub kind: String,
+    /// Human-readable notification text
+    pub body: String,
+    /// Optional path the notification points to
+    pub link: Option<String>,
+    /// Timestamp when the notification was read, if it has been
+    pub read_at: Option<DateTime<Utc>>,
+    /// Timestamp when the notification was created
+    pub created_at: DateTime<Utc>,
+}
+
+/// Represents a discussion comment on a poll, with the commenter's username.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Comment {
+    /// Unique identifier for the comment
+    pub id: i64,
+    /// ID of the poll being discussed
+    pub poll_id: i64,
+    /// ID of the option the comment is about, or `None` for the poll as a whole
+    pub option_id: Option<i64>,
+    /// ID of the user who wrote the comment
+    pub user_id: i64,
+    /// Username of the user who wrote the comment
+    pub username: String,
+    /// Comment text
+    pub body: String,
+    /// Timestamp when the comment was posted
+    pub created_at: DateTime<Utc>,
+}
+
+/// Form data structure for posting a comment.
+#[derive(Debug, FromForm, Deserialize)]
+pub struct CommentForm {
+    /// Comment text
+    pub body: String,
+}
+
+/// Form data structure for updating runtime settings.
+#[derive(Debug, FromForm, Deserialize)]
+pub struct SettingsForm {
+    /// Whether users may create new polls
+    pub poll_creation_enabled: bool,
+}
+
+/// Form data structure for setting or clearing the site announcement.
+/// An empty message clears the announcement.
+#[derive(Debug, FromForm, Deserialize)]
+pub struct AnnouncementForm {
+    /// Text of the announcement
+    pub message: String,
+    /// Whether the announcement is shown to users
+    pub active: bool,
+}
+
+/// Form data structure for broadcasting a notification to every user.
+#[derive(Debug, FromForm, Deserialize)]
+pub struct BroadcastForm {
+    /// Short subject line, shown before the message
+    pub subject: String,
+    /// Text of the message
+    pub body: String,
+}
+
+/// Site-wide announcement shown as a banner on authenticated pages.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SiteAnnouncement {
+    /// Text of the announcement
+    pub message: String,
+    /// Whether the announcement is currently shown
+    pub active: bool,
+}
+
+impl SiteAnnouncement {
+    /// Returns the message to display, if the announcement is active and non-empty.
+    pub fn visible_message(&self) -> Option<&str> {
+        Some(self.message.as_str()).filter(|message| self.active && !message.is_empty())
     }
 }
 
@@ -167,6 +573,31 @@ pub struct Poll {
     pub created_at: DateTime<Utc>,
     /// Timestamp when the poll expires and voting closes
     pub expires_at: DateTime<Utc>,
+    /// Optional timestamp after which votes can no longer be cast or removed
+    pub votes_lock_at: Option<DateTime<Utc>>,
+    /// Optional number of unique voters needed for the poll to be decided
+    pub quorum: Option<i64>,
+    /// Whether users who have voted may see who else voted
+    pub open_voters: bool,
+    /// Whether each vote must include a comment explaining it
+    pub require_vote_comment: bool,
+    /// Whether voting closes as soon as the quorum is reached
+    pub auto_close_at_quorum: bool,
+    /// Which options the poll accepts: `mixed`, `text`, or `dates`
+    pub poll_kind: String,
+    /// Whether the poll is hidden from the active and expired listings
+    pub archived: bool,
+    /// Whether the poll's creator may vote on it
+    pub creator_can_vote: bool,
+    /// Whether voters may pick several options rather than just one
+    pub multi_select: bool,
+    /// Whether the poll has been finalized, freezing its results
+    pub finalized: bool,
+    /// Whether voters may suggest their own option as a write-in
+    pub allow_write_in: bool,
+    /// Whether the poll has been approved; unpublished polls are shown only
+    /// to their creator and admins
+    pub published: bool,
 }
 
 /// Extended poll information that includes the creator's username.
@@ -187,6 +618,31 @@ pub struct PollWithCreator {
     pub created_at: DateTime<Utc>,
     /// Timestamp when the poll expires and voting closes
     pub expires_at: DateTime<Utc>,
+    /// Optional timestamp after which votes can no longer be cast or removed
+    pub votes_lock_at: Option<DateTime<Utc>>,
+    /// Optional number of unique voters needed for the poll to be decided
+    pub quorum: Option<i64>,
+    /// Whether users who have voted may see who else voted
+    pub open_voters: bool,
+    /// Whether each vote must include a comment explaining it
+    pub require_vote_comment: bool,
+    /// Whether voting closes as soon as the quorum is reached
+    pub auto_close_at_quorum: bool,
+    /// Which options the poll accepts: `mixed`, `text`, or `dates`
+    pub poll_kind: String,
+    /// Whether the poll is hidden from the active and expired listings
+    pub archived: bool,
+    /// Whether the poll's creator may vote on it
+    pub creator_can_vote: bool,
+    /// Whether voters may pick several options rather than just one
+    pub multi_select: bool,
+    /// Whether the poll has been finalized, freezing its results
+    pub finalized: bool,
+    /// Whether voters may suggest their own option as a write-in
+    pub allow_write_in: bool,
+    /// Whether the poll has been approved; unpublished polls are shown only
+    /// to their creator and admins
+    pub published: bool,
 }
 
 /// Represents a voting option within a poll.
@@ -203,9 +659,21 @@ pub struct PollOption {
     pub is_date: bool,
     /// Optional date/time value for date-based options
     pub date_time: Option<DateTime<Utc>>,
+    /// Optional image (e.g. game cover art) shown with the option
+    pub image_url: Option<String>,
+    /// Optional maximum number of votes the option can receive (e.g. seats in a slot)
+    pub max_votes: Option<i64>,
+    /// ID of the voter who suggested this option as a write-in, if any
+    pub suggested_by: Option<i64>,
+    /// Username of the voter who suggested this option (joined field)
+    #[sqlx(default)]
+    pub suggested_by_username: Option<String>,
     /// Number of votes this option has received (calculated field)
     #[sqlx(default)]
     pub vote_count: i64,
+    /// Sum of the vote weights of this option's voters (calculated field)
+    #[sqlx(default)]
+    pub weighted_votes: i64,
 }
 
 /// Represents a user's vote on a specific poll option.
@@ -224,7 +692,7 @@ pub struct Vote {
 
 /// Form data structure for creating new polls.
 /// Captures all necessary information to create a poll with options.
-#[derive(Debug, FromForm, Deserialize)]
+#[derive(Debug, FromForm, Serialize, Deserialize)]
 pub struct NewPollForm {
     /// Title/question for the poll
     pub title: String,
@@ -232,8 +700,33 @@ pub struct NewPollForm {
     pub description: Option<String>,
     /// Expiration date/time in format YYYY-MM-DDTHH:MM
     pub expires_at: String,
-    /// Comma-separated list of poll options
+    /// Optional vote lock date/time in format YYYY-MM-DDTHH:MM (must not be after expiration)
+    pub votes_lock_at: Option<String>,
+    /// Optional number of unique voters needed for the poll to be decided
+    pub quorum: Option<i64>,
+    /// Whether users who have voted may see who else voted
+    pub open_voters: bool,
+    /// Whether each vote must include a comment explaining it
+    pub require_vote_comment: bool,
+    /// Whether voting closes as soon as the quorum is reached
+    pub auto_close_at_quorum: bool,
+    /// Which options the poll accepts: `mixed` (default), `text`, or `dates`
+    pub poll_kind: Option<String>,
+    /// Whether the creator may vote on their own poll (default true)
+    pub creator_can_vote: Option<bool>,
+    /// Whether voters may pick several options (default true) or only one
+    pub multi_select: Option<bool>,
+    /// Whether voters may suggest their own option as a write-in
+    pub allow_write_in: bool,
+    /// List of poll options, split according to `options_separator`
     pub options: String,
+    /// How `options` is split: `comma` (default) or `newline`
+    pub options_separator: Option<String>,
+    /// Optional ID of an earlier poll whose options are copied in ahead of `options`
+    pub copy_options_from: Option<i64>,
+    /// Whether the creator confirmed creating a poll with the same title as
+    /// one of their open polls
+    pub confirm_duplicate: bool,
 }
 
 /// Form data structure for creating new poll options.
@@ -241,14 +734,72 @@ pub struct NewPollForm {
 pub struct NewOptionsForm {
     /// Comma-separated list of poll options
     pub options: String,
+    /// Whether to also move the poll's expiration to `expires_at`,
+    /// reopening it if it has expired
+    pub also_extend: bool,
+    /// New expiration date/time in format YYYY-MM-DDTHH:MM (used with `also_extend`)
+    pub expires_at: Option<String>,
+}
+
+/// Form data structure for suggesting a write-in option.
+#[derive(Debug, FromForm, Deserialize)]
+pub struct WriteInForm {
+    /// Text of the suggested option, or a date in format YYYY-MM-DDTHH:MM
+    pub text: String,
+}
+
+/// Form data structure for reopening an expired poll.
+#[derive(Debug, FromForm, Deserialize)]
+pub struct ReopenPollForm {
+    /// New expiration date/time in format YYYY-MM-DDTHH:MM
+    pub expires_at: String,
+}
+
+/// Form data structure for archiving or unarchiving a poll.
+#[derive(Debug, FromForm, Deserialize)]
+pub struct ArchivePollForm {
+    /// Whether the poll should be archived (true) or restored (false)
+    pub archived: bool,
+}
+
+/// Form data structure for starting a runoff from a poll's top options.
+#[derive(Debug, FromForm, Deserialize)]
+pub struct RunoffForm {
+    /// Number of highest-voted options to carry over
+    pub top_n: i64,
+    /// Expiration date/time for the runoff in format YYYY-MM-DDTHH:MM
+    pub expires_at: String,
 }
 
 /// Form data structure for casting votes on poll options.
-/// Simple form containing only the option ID being voted for.
+/// Contains the option ID being voted for and an optional comment.
 #[derive(Debug, FromForm, Deserialize)]
 pub struct VoteForm {
     /// ID of the poll option to vote for
     pub option_id: i64,
+    /// Optional comment explaining the vote (required on some polls)
+    pub comment: Option<String>,
+}
+
+/// Result of toggling a vote, used to give the voter specific feedback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VoteOutcome {
+    /// The user's first vote in this poll was recorded
+    FirstVote,
+    /// A vote was added alongside the user's existing votes in this poll
+    Added,
+    /// The user's vote in a single-choice poll was moved to another option
+    Changed {
+        /// Text of the option the vote was moved from
+        from: String,
+        /// Text of the option the vote was moved to
+        to: String,
+    },
+    /// The user's vote for the option was removed
+    Removed,
+    /// The user's first vote was recorded and brought the poll to its quorum,
+    /// closing it
+    ClosedAtQuorum,
 }
 
 /// Extended vote information that includes the voter's username.
@@ -263,8 +814,12 @@ pub struct VoteWithUser {
     pub username: String,
     /// ID of the poll option that was voted for
     pub option_id: i64,
+    /// Comment left with the vote, if any
+    pub vote_comment: Option<String>,
     /// Timestamp when the vote was cast
     pub created_at: DateTime<Utc>,
+    /// How much the vote counts, from the voter's current vote weight
+    pub vote_weight: i64,
 }
 
 /// Poll option with detailed voter information.
@@ -283,8 +838,14 @@ pub struct OptionWithVoters {
     pub date_time: Option<DateTime<Utc>>,
     /// Total number of votes for this option
     pub vote_count: i64,
-    /// Detailed list of all votes cast for this option
+    /// Sum of the vote weights of this option's voters
+    pub weighted_votes: i64,
+    /// Detailed list of the votes cast for this option (only the first few
+    /// when the details are summarized)
     pub voters: Vec<VoteWithUser>,
+    /// Number of votes left out of `voters` (0 unless summarized)
+    #[serde(default)]
+    pub more_voters: i64,
 }
 
 /// Complete poll information including voting details and statistics.
@@ -297,8 +858,179 @@ pub struct PollVotingDetails {
     pub options_with_voters: Vec<OptionWithVoters>,
     /// Total number of votes cast across all options
     pub total_votes: i64,
+    /// Sum of the weighted votes across all options
+    pub total_weighted_votes: i64,
     /// Total number of unique voters who participated
     pub total_voters: i64,
+    /// Whether the poll's quorum has been reached (always true without a quorum)
+    pub quorum_met: bool,
+    /// Number of additional unique voters needed to reach the quorum
+    pub quorum_remaining: i64,
+    /// Whether each option lists only its first voters, because the poll
+    /// has too many voters to show them all
+    #[serde(default)]
+    pub summarized: bool,
+}
+
+/// One page of the voters for a poll option.
+#[derive(Debug, Clone, Serialize)]
+pub struct OptionVotersPage {
+    /// ID of the option
+    pub option_id: i64,
+    /// Page number, starting at 1
+    pub page: i64,
+    /// Maximum number of voters per page
+    pub per_page: i64,
+    /// Total number of votes for the option
+    pub total: i64,
+    /// The voters on this page, oldest vote first
+    pub voters: Vec<VoteWithUser>,
+}
+
+/// A single vote in a poll's raw ballot export.
+/// Flat, per-vote record joined with the voter and option details.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Ballot {
+    /// ID of the user who cast the vote
+    pub user_id: i64,
+    /// Username of the voter
+    pub username: String,
+    /// ID of the option voted for
+    pub option_id: i64,
+    /// Text of the option voted for
+    pub option_text: String,
+    /// Timestamp when the vote was cast
+    pub created_at: DateTime<Utc>,
+}
+
+/// Results of a poll recorded when it was finalized.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ResultsSnapshot {
+    /// ID of the finalized poll
+    pub poll_id: i64,
+    /// The poll's [`PollVotingDetails`] at finalization, serialized as JSON
+    pub results: String,
+    /// ID of the user who finalized the poll, if they still exist
+    pub finalized_by: Option<i64>,
+    /// Timestamp when the poll was finalized
+    pub created_at: DateTime<Utc>,
+}
+
+/// Vote counts for each of a poll's options, for clients polling for live results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteCounts {
+    /// Number of votes for each option, keyed by option ID
+    pub counts: BTreeMap<i64, i64>,
+    /// Total votes across all options
+    pub total: i64,
+    /// Sum of the voters' vote weights for each option, keyed by option ID
+    pub weighted: BTreeMap<i64, i64>,
+    /// Total weighted votes across all options
+    pub weighted_total: i64,
+    /// Open slots on each vote-capped option, keyed by option ID (null when uncapped)
+    pub remaining: BTreeMap<i64, Option<i64>>,
+    /// Whether each vote-capped option is full, keyed by option ID (null when uncapped)
+    pub full: BTreeMap<i64, Option<bool>>,
+    /// Time of the most recent vote, if any (not serialized)
+    #[serde(skip)]
+    pub last_vote_at: Option<DateTime<Utc>>,
+}
+
+/// Size of the time buckets in a poll's vote timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimelineBucket {
+    /// One point per hour
+    Hourly,
+    /// One point per day (UTC)
+    Daily,
+}
+
+impl TimelineBucket {
+    /// Parses a bucket size from a query parameter: `hourly` (or `hour`)
+    /// or `daily` (or `day`).
+    pub fn from_param(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "hourly" | "hour" => Some(TimelineBucket::Hourly),
+            "daily" | "day" => Some(TimelineBucket::Daily),
+            _ => None,
+        }
+    }
+
+    /// Returns the length of one bucket.
+    pub fn duration(self) -> chrono::Duration {
+        match self {
+            TimelineBucket::Hourly => chrono::Duration::hours(1),
+            TimelineBucket::Daily => chrono::Duration::days(1),
+        }
+    }
+}
+
+/// One bucket in a poll's vote timeline.
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelinePoint {
+    /// Start of the bucket
+    pub timestamp: DateTime<Utc>,
+    /// Votes cast up to the end of the bucket
+    pub cumulative_votes: i64,
+}
+
+/// Cumulative votes on a poll over time, for charts.
+#[derive(Debug, Clone, Serialize)]
+pub struct PollTimeline {
+    /// ID of the poll
+    pub poll_id: i64,
+    /// Size of each bucket
+    pub bucket: TimelineBucket,
+    /// Evenly spaced buckets from the first vote to the last, oldest first;
+    /// empty when the poll has no votes
+    pub buckets: Vec<TimelinePoint>,
+}
+
+/// A poll the user deleted that can still be restored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletedPoll {
+    /// ID the poll had, and will have again if restored
+    pub id: i64,
+    /// Title of the deleted poll
+    pub title: String,
+    /// Seconds left to restore the poll
+    pub seconds_left: u64,
+}
+
+/// Basic poll information without options or votes, for link previews.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PollMeta {
+    /// Unique identifier for the poll
+    pub id: i64,
+    /// Poll title/question
+    pub title: String,
+    /// Optional detailed description of the poll
+    pub description: Option<String>,
+    /// Username of the user who created the poll
+    pub creator_username: String,
+    /// Timestamp when the poll was created
+    pub created_at: DateTime<Utc>,
+    /// Timestamp when the poll expires
+    pub expires_at: DateTime<Utc>,
+    /// Whether the poll has expired
+    pub is_expired: bool,
+}
+
+/// A date option the user voted for on a poll that is still open.
+/// Used to build the user's personal game night calendar.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CalendarEntry {
+    /// ID of the poll the option belongs to
+    pub poll_id: i64,
+    /// Title of the poll the option belongs to
+    pub poll_title: String,
+    /// ID of the date option
+    pub option_id: i64,
+    /// Text description of the option
+    pub text: String,
+    /// Date/time of the option
+    pub date_time: DateTime<Utc>,
 }
 
 // impl Poll {
@@ -0,0 +1,177 @@
+//! # Password Hashing Module
+//!
+//! This module hashes and verifies user passwords with a configurable
+//! algorithm, so deployments that standardize on argon2 can use it while
+//! everyone else keeps bcrypt.
+//!
+//! ## Configuration
+//! Set `PASSWORD_HASH_ALGO` to `bcrypt` (the default) or `argon2` to choose
+//! how new passwords are hashed, and `BCRYPT_COST` (4 to 31, default 12) to
+//! tune bcrypt. Other values stop the server at startup.
+//!
+//! ## Mixed Hashes
+//! Both algorithms produce self-describing hashes: bcrypt hashes start with
+//! `$2`, and argon2 hashes are PHC strings starting with `$argon2`.
+//! Verification dispatches on the stored hash rather than the setting, so
+//! existing bcrypt hashes keep working after switching to argon2 (and the
+//! other way around). Each hash is only replaced when its password changes.
+//!
+//! ## Malformed Hashes
+//! A stored hash that neither algorithm can read (after a bad import or a
+//! manual edit, say) fails every login. [`check`] reports it as
+//! [`PasswordHashError::MalformedHash`] so callers can tell it apart from a
+//! wrong password, and [`verify`] logs it as an error.
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use rand::RngCore;
+
+use log::error;
+
+use crate::config;
+
+/// Algorithms available for hashing new passwords.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    /// bcrypt with the cost factor from `BCRYPT_COST`
+    Bcrypt,
+    /// argon2id with the argon2 crate's default parameters
+    Argon2,
+}
+
+/// Error returned when a password can't be hashed, or a stored hash can't
+/// be read.
+#[derive(Debug, thiserror::Error)]
+pub enum PasswordHashError {
+    /// bcrypt failed to hash the password
+    #[error("bcrypt error: {0}")]
+    Bcrypt(#[from] bcrypt::BcryptError),
+    /// argon2 failed to hash the password
+    #[error("argon2 error: {0}")]
+    Argon2(String),
+    /// The stored hash isn't a valid bcrypt or argon2 hash
+    #[error("malformed password hash: {0}")]
+    MalformedHash(String),
+}
+
+/// Returns the algorithm used for new password hashes.
+pub fn configured_algorithm() -> HashAlgorithm {
+    config::get().password_hash_algo
+}
+
+/// Hashes a plain text password with the configured algorithm.
+///
+/// # Arguments
+/// * `password` - The plain text password to hash
+///
+/// # Returns
+/// `Ok(String)` containing the hashed password, or `Err` if hashing fails
+pub fn hash(password: &str) -> Result<String, PasswordHashError> {
+    hash_with(configured_algorithm(), password)
+}
+
+/// Hashes a plain text password with a specific algorithm.
+///
+/// # Arguments
+/// * `algorithm` - The algorithm to hash with
+/// * `password` - The plain text password to hash
+///
+/// # Returns
+/// `Ok(String)` containing the hashed password, or `Err` if hashing fails
+pub fn hash_with(algorithm: HashAlgorithm, password: &str) -> Result<String, PasswordHashError> {
+    match algorithm {
+        HashAlgorithm::Bcrypt => Ok(bcrypt::hash(password, config::get().bcrypt_cost)?),
+        HashAlgorithm::Argon2 => {
+            let mut salt = [0u8; 16];
+            rand::thread_rng().fill_bytes(&mut salt);
+            let salt = SaltString::encode_b64(&salt)
+                .map_err(|err| PasswordHashError::Argon2(err.to_string()))?;
+
+            Argon2::default()
+                .hash_password(password.as_bytes(), &salt)
+                .map(|hash| hash.to_string())
+                .map_err(|err| PasswordHashError::Argon2(err.to_string()))
+        }
+    }
+}
+
+/// Checks a plain text password against a stored hash of either algorithm.
+///
+/// # Arguments
+/// * `password` - The plain text password to check
+/// * `stored_hash` - The stored bcrypt or argon2 hash
+///
+/// # Returns
+/// * `Ok(bool)` - Whether the password matches the hash
+/// * `Err(PasswordHashError::MalformedHash)` - If the stored hash can't be read
+pub fn check(password: &str, stored_hash: &str) -> Result<bool, PasswordHashError> {
+    let malformed = |err: &dyn std::fmt::Display| PasswordHashError::MalformedHash(err.to_string());
+
+    if stored_hash.starts_with("$argon2") {
+        let hash = PasswordHash::new(stored_hash).map_err(|err| malformed(&err))?;
+        // argon2 reports a missing salt or output as a mismatch
+        if hash.salt.is_none() || hash.hash.is_none() {
+            return Err(malformed(&"missing salt or hash output"));
+        }
+        match Argon2::default().verify_password(password.as_bytes(), &hash) {
+            Ok(()) => Ok(true),
+            Err(argon2::password_hash::Error::Password) => Ok(false),
+            Err(err) => Err(malformed(&err)),
+        }
+    } else {
+        bcrypt::verify(password, stored_hash).map_err(|err| malformed(&err))
+    }
+}
+
+/// Verifies a plain text password against a stored hash of either algorithm.
+///
+/// A malformed hash is logged and treated as a wrong password. Use
+/// [`check`] to handle it separately.
+///
+/// # Arguments
+/// * `password` - The plain text password to verify
+/// * `stored_hash` - The stored bcrypt or argon2 hash
+///
+/// # Returns
+/// `true` if the password matches the hash, `false` otherwise (including
+/// when the hash is malformed)
+pub fn verify(password: &str, stored_hash: &str) -> bool {
+    check(password, stored_hash).unwrap_or_else(|err| {
+        error!("Can't verify password: {}", err);
+        false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_bcrypt_hashes() {
+        let stored_hash = bcrypt::hash("hunter2", 4).unwrap();
+
+        assert!(check("hunter2", &stored_hash).unwrap());
+        assert!(!check("hunter3", &stored_hash).unwrap());
+    }
+
+    #[test]
+    fn checks_argon2_hashes() {
+        let stored_hash = hash_with(HashAlgorithm::Argon2, "hunter2").unwrap();
+
+        assert!(stored_hash.starts_with("$argon2id$"), "{}", stored_hash);
+        assert!(check("hunter2", &stored_hash).unwrap());
+        assert!(!check("hunter3", &stored_hash).unwrap());
+    }
+
+    #[test]
+    fn reports_malformed_hashes() {
+        for stored_hash in ["", "not a hash", "$2b$12$short", "$argon2id$v=19$m=19456,t=2,p=1"] {
+            assert!(
+                matches!(check("hunter2", stored_hash), Err(PasswordHashError::MalformedHash(_))),
+                "{:?}",
+                stored_hash
+            );
+            assert!(!verify("hunter2", stored_hash));
+        }
+    }
+}
@@ -14,6 +14,25 @@
 //! - `AuthenticatedUser` - Requires valid session
 //! - `AdminUser` - Requires admin privileges
 //!
+//! ## Not Found vs Forbidden
+//! Published polls are listed for and visible to every signed-in user. A
+//! poll waiting for approval is visible only to its creator and admins (see
+//! [`polls::can_view_poll`]); to everyone else it must look exactly like a
+//! poll that doesn't exist, so it can't be probed for. Poll routes
+//! therefore answer:
+//! - `404 Not Found` when the poll (or option) doesn't exist, or the user
+//!   can't see it
+//! - `403 Forbidden` when the user can see the poll but may not act on it,
+//!   such as viewing voters or deleting someone else's poll
+//! - `500 Internal Server Error` when the lookup itself fails
+//!
+//! Routes check visibility first, with [`get_visible_poll`] (or
+//! [`get_visible_poll_or_flash`] for form routes), before any permission
+//! check. Form routes redirect with a flash message instead of returning a
+//! status, and keep the same distinction in their wording. Controllers
+//! signal a missing or hidden poll with `sqlx::Error::RowNotFound` and a
+//! refused action on a visible poll with [`polls::forbidden`].
+//!
 //! ## Template Rendering
 //! All routes use Tera templates with consistent context data including:
 //! - Page title for SEO and navigation
@@ -23,20 +42,217 @@
 
 use rocket::form::Form;
 use rocket::get;
-use rocket::http::{CookieJar, Status};
+use rocket::http::{ContentType, CookieJar, Header, Status};
 use rocket::post;
-use rocket::response::{Flash, Redirect};
+use rocket::request::{self, FromRequest, Request};
+use rocket::response::stream::TextStream;
+use rocket::response::{self, Flash, Redirect, Responder, Response};
+use rocket::serde::json::Json;
 use rocket::uri;
+use rocket::Either;
 use rocket::State;
 use rocket_dyn_templates::{context, Template};
 use sqlx::SqlitePool;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::Instant;
+use tokio::io::AsyncReadExt;
 
-use crate::auth::{AdminUser, AuthenticatedUser};
-use crate::controllers::{polls, users};
+use crate::auth::{self, AdminUser, AuthenticatedUser};
+use crate::controllers::csv::CsvStream;
+use crate::controllers::{audit, integrity, notify, polls, settings, tombstone, users};
 use crate::models::{
-    ChangePasswordForm, LoginForm, NewOptionsForm, NewPollForm, NewUserForm, ToggleRoleForm, VoteForm,
+    AnnouncementForm, ArchivePollForm, Ballot, BroadcastForm, BulkRoleForm, ChangePasswordForm,
+    CommentForm, CurrentUserInfo, DashboardData, ImportPollForm, ImportUsersForm, LoginForm, MergeUsersForm, NewOptionsForm, NewPollForm,
+    NewUserForm, OptionVotersPage, PollMeta, PollOption, PollTemplate, PollTimeline, PollWithCreator, PublicUserInfo, ReauthForm, ReopenPollForm, RevokeSessionsForm, RunoffForm,
+    SettingsForm, TimelineBucket, ToggleRoleForm, User, VoteCounts, VoteForm, VoteOutcome, VoteWeightForm,
+    WelcomeForm, WriteInForm,
 };
 
+// ============================================================================
+// Unread notifications
+// ============================================================================
+
+/// Request guard carrying the current user's unread notification count.
+///
+/// Authenticated pages pass it to their templates so the navigation can show
+/// it. Anonymous requests, and failures to load the count, give zero.
+pub struct UnreadNotifications(i64);
+
+impl UnreadNotifications {
+    /// Folds the count into an ETag, so new notifications invalidate cached
+    /// copies of the page.
+    fn tag(&self, etag: String) -> String {
+        format!("{}-n{}\"", etag.trim_end_matches('"'), self.0)
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for UnreadNotifications {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let user = match request.guard::<AuthenticatedUser>().await {
+            request::Outcome::Success(user) => user,
+            _ => return request::Outcome::Success(UnreadNotifications(0)),
+        };
+        let pool = request.rocket().state::<SqlitePool>().unwrap();
+
+        let count = match notify::unread_count(pool, user.id).await {
+            Ok(count) => count,
+            Err(err) => {
+                log::warn!("Failed to count unread notifications: {}", err);
+                0
+            }
+        };
+
+        request::Outcome::Success(UnreadNotifications(count))
+    }
+}
+
+// ============================================================================
+// Conditional GET support
+// ============================================================================
+
+/// Request guard exposing the `If-None-Match` header, if present.
+pub struct IfNoneMatch(Option<String>);
+
+impl IfNoneMatch {
+    /// Returns whether the header matches the given ETag.
+    ///
+    /// Uses weak comparison, so `W/` prefixes are ignored on either side.
+    fn matches(&self, etag: &str) -> bool {
+        let strip = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+
+        match &self.0 {
+            Some(header) => header
+                .split(',')
+                .any(|tag| tag.trim() == "*" || strip(tag) == strip(etag)),
+            None => false,
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IfNoneMatch {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        request::Outcome::Success(IfNoneMatch(
+            request.headers().get_one("If-None-Match").map(String::from),
+        ))
+    }
+}
+
+/// Response that carries an ETag, or a bodyless 304 when the client's copy is current.
+#[derive(rocket::Responder)]
+pub enum Conditional<R> {
+    /// Full response with its ETag
+    Fresh(R, Header<'static>),
+    /// The client's cached copy is still valid
+    #[response(status = 304)]
+    NotModified((), Header<'static>),
+}
+
+/// Maps a failed poll lookup to a response status, following the
+/// not-found-vs-forbidden policy in the module docs.
+fn poll_lookup_status(err: sqlx::Error) -> Status {
+    match err {
+        sqlx::Error::RowNotFound => Status::NotFound,
+        _ => Status::InternalServerError,
+    }
+}
+
+/// Looks up a poll the user may see (see [`polls::can_view_poll`]).
+///
+/// A poll waiting for approval isn't visible to everyone, so other users
+/// get `404 Not Found` as if it didn't exist, following the module docs.
+async fn get_visible_poll(
+    pool: &SqlitePool,
+    poll_id: i64,
+    user: &User,
+) -> Result<PollWithCreator, Status> {
+    let poll = polls::get_poll_by_id(pool, poll_id)
+        .await
+        .map_err(poll_lookup_status)?;
+    if !polls::can_view_poll(&poll, user) {
+        return Err(Status::NotFound);
+    }
+
+    Ok(poll)
+}
+
+/// Looks up a poll the user may see, for form routes.
+///
+/// Like [`get_visible_poll`], but answers with a flash redirect: a poll
+/// that doesn't exist or isn't visible to the user sends them to the
+/// dashboard with "Poll not found.", and a failed lookup returns to the
+/// poll with `failure` and the error.
+async fn get_visible_poll_or_flash(
+    pool: &SqlitePool,
+    poll_id: i64,
+    user: &User,
+    failure: &str,
+) -> Result<PollWithCreator, Flash<Redirect>> {
+    let poll = match polls::get_poll_by_id(pool, poll_id).await {
+        Ok(poll) => Some(poll),
+        Err(sqlx::Error::RowNotFound) => None,
+        Err(err) => {
+            return Err(Flash::error(
+                Redirect::to(uri!(poll_detail(poll_id))),
+                format!("{}: {}", failure, err),
+            ));
+        }
+    };
+
+    poll.filter(|poll| polls::can_view_poll(poll, user))
+        .ok_or_else(|| Flash::error(Redirect::to(uri!(dashboard)), "Poll not found."))
+}
+
+// ============================================================================
+// Site announcement
+// ============================================================================
+
+/// Request guard carrying the active site announcement, if any.
+///
+/// Authenticated pages pass it to their templates so the layout can show
+/// the banner. A failure to load the announcement never fails the request.
+pub struct Announcement(Option<String>);
+
+impl Announcement {
+    /// Folds the announcement into an ETag, so changing the banner
+    /// invalidates cached copies of the page.
+    fn tag(&self, etag: String) -> String {
+        match &self.0 {
+            Some(message) => {
+                let mut hasher = DefaultHasher::new();
+                message.hash(&mut hasher);
+                format!("{}-{:x}\"", etag.trim_end_matches('"'), hasher.finish())
+            }
+            None => etag,
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Announcement {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let pool = request.rocket().state::<SqlitePool>().unwrap();
+
+        let message = match settings::site_announcement(pool).await {
+            Ok(announcement) => announcement.visible_message().map(String::from),
+            Err(err) => {
+                log::warn!("Failed to load site announcement: {}", err);
+                None
+            }
+        };
+
+        request::Outcome::Success(Announcement(message))
+    }
+}
+
 // ============================================================================
 // Public routes (no authentication required)
 // ============================================================================
@@ -122,6 +338,168 @@ pub async fn logout(cookies: &CookieJar<'_>) -> Flash<Redirect> {
 // Authenticated routes (require valid session)
 // ============================================================================
 
+/// Returns `next` if it is a path on this site, so confirming a password
+/// can't redirect elsewhere.
+fn local_redirect_target(next: Option<&str>) -> Option<&str> {
+    next.filter(|next| next.starts_with('/') && !next.starts_with("//") && !next.contains('\\'))
+}
+
+/// Checks that the user confirmed their password recently enough for a
+/// sensitive action.
+///
+/// # Parameters
+/// * `cookies` - Cookie jar holding the re-authentication marker
+/// * `user` - The user attempting the action
+/// * `next` - Page to return to once the password is confirmed
+///
+/// # Returns
+/// * `None` - The action may proceed
+/// * `Some(Flash<Redirect>)` - Redirect to the password prompt, to return as the error
+fn reauth_prompt(cookies: &CookieJar<'_>, user: &User, next: &str) -> Option<Flash<Redirect>> {
+    if auth::has_recent_reauth(cookies, user) {
+        return None;
+    }
+
+    Some(Flash::warning(
+        Redirect::to(uri!(reauth_page(Some(next)))),
+        "Please confirm your password to continue.",
+    ))
+}
+
+/// Displays the password prompt for sensitive actions.
+///
+/// # Parameters
+/// * `next` - Page to return to once confirmed
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
+/// * `flash` - Optional flash messages, such as why the prompt appeared
+///
+/// # Returns
+/// Password prompt template
+#[get("/reauth?<next>")]
+pub async fn reauth_page(
+    next: Option<String>,
+    user: AuthenticatedUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
+    flash: Option<rocket::request::FlashMessage<'_>>,
+) -> Template {
+    Template::render(
+        "reauth",
+        context! {
+            title: "Confirm Password - Platform Engineering Game Night",
+            user: user.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            next: local_redirect_target(next.as_deref()).unwrap_or("/dashboard"),
+            flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
+        },
+    )
+}
+
+/// Handles the password prompt for sensitive actions.
+///
+/// A correct password sets the re-authentication marker and returns the
+/// user to `next`, where they can repeat the action; a wrong one shows the
+/// prompt again.
+///
+/// # Parameters
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `form` - The user's password and the page to return to
+/// * `cookies` - Cookie jar for setting the re-authentication marker
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Redirects to `next` (default the dashboard)
+/// * `Err(Flash<Redirect>)` - Redirects back to the prompt with an error
+#[post("/reauth", data = "<form>")]
+pub async fn reauth_post(
+    user: AuthenticatedUser,
+    form: Form<ReauthForm>,
+    cookies: &CookieJar<'_>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    let next = local_redirect_target(form.next.as_deref()).unwrap_or("/dashboard");
+
+    if !user.verify_password(&form.password) {
+        return Err(Flash::error(
+            Redirect::to(uri!(reauth_page(Some(next)))),
+            "Password is incorrect.",
+        ));
+    }
+
+    auth::set_reauth_cookie(cookies, &user);
+    Ok(Flash::success(
+        Redirect::to(next.to_string()),
+        "Password confirmed. You can now continue.",
+    ))
+}
+
+/// Displays the welcome page that new users see after their first login.
+///
+/// The page explains voting and creating polls. Its content lives in the
+/// `welcome` template, so deployments can adapt it. It stays reachable
+/// after onboarding as a help page.
+///
+/// # Parameters
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
+/// * `flash` - Optional flash messages
+///
+/// # Returns
+/// Welcome page template
+#[get("/welcome")]
+pub async fn welcome(
+    user: AuthenticatedUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
+    flash: Option<rocket::request::FlashMessage<'_>>,
+) -> Template {
+    Template::render(
+        "welcome",
+        context! {
+            title: "Welcome - Platform Engineering Game Night",
+            user: user.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
+        },
+    )
+}
+
+/// Marks the welcome page as seen, whether it was finished or skipped.
+///
+/// # Parameters
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `form` - The page to continue to
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Redirect)` - Redirects to `next` (default the dashboard)
+/// * `Err(Status::InternalServerError)` - If the flag can't be saved
+#[post("/welcome/complete", data = "<form>")]
+pub async fn complete_welcome(
+    user: AuthenticatedUser,
+    form: Form<WelcomeForm>,
+    pool: &State<SqlitePool>,
+) -> Result<Redirect, Status> {
+    users::complete_onboarding(pool, user.id).await.map_err(|err| {
+        log::error!("Failed to record onboarding for user_id={}: {}", user.id, err);
+        Status::InternalServerError
+    })?;
+
+    let next = local_redirect_target(form.next.as_deref()).unwrap_or("/dashboard");
+    Ok(Redirect::to(next.to_string()))
+}
+
+/// Builds the layout settings shared by the pages that list polls as cards.
+///
+/// Passed to templates as `layout`; `layout.columns` is the configured
+/// number of cards per row, or null for the default layout.
+fn poll_list_layout() -> serde_json::Value {
+    serde_json::json!({ "columns": polls::dashboard_columns() })
+}
+
 /// Main dashboard page showing active and expired polls.
 ///
 /// This is the primary landing page for authenticated users,
@@ -129,6 +507,8 @@ pub async fn logout(cookies: &CookieJar<'_>) -> Flash<Redirect> {
 ///
 /// # Parameters
 /// * `user` - Authenticated user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
 /// * `pool` - Database connection pool
 /// * `flash` - Optional flash messages from previous actions
 ///
@@ -138,6 +518,8 @@ pub async fn logout(cookies: &CookieJar<'_>) -> Flash<Redirect> {
 #[get("/dashboard")]
 pub async fn dashboard(
     user: AuthenticatedUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
     pool: &State<SqlitePool>,
     flash: Option<rocket::request::FlashMessage<'_>>,
 ) -> Result<Template, Status> {
@@ -149,18 +531,180 @@ pub async fn dashboard(
         .await
         .map_err(|_| Status::InternalServerError)?;
 
+    let notifications = notify::get_unread(pool, user.id, 10)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    let recently_deleted = tombstone::deleted_by(user.id);
+
+    // The user's own polls waiting for approval, which the listings leave out
+    let pending_polls: Vec<_> = polls::get_polls_by_creator(pool, user.id)
+        .await
+        .map_err(|_| Status::InternalServerError)?
+        .into_iter()
+        .filter(|poll| !poll.published)
+        .collect();
+
     Ok(Template::render(
         "dashboard",
         context! {
             title: "Dashboard - Platform Engineering Game Night",
             user: user.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
             active_polls: active_polls,
             expired_polls: expired_polls,
+            pending_polls: pending_polls,
+            notifications: notifications,
+            recently_deleted: recently_deleted,
+            layout: poll_list_layout(),
+            flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
+        },
+    ))
+}
+
+/// Returns everything the dashboard shows as JSON, for single-page front ends.
+///
+/// The body holds the user, the active and expired polls (archived polls
+/// are left out, as on the dashboard) each with `total_votes` and
+/// `voter_count`, the user's unread notifications, and the user's
+/// statistics as shown on their profile.
+///
+/// # Parameters
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Json<DashboardData>)` - The dashboard contents
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/api/dashboard")]
+pub async fn dashboard_json(
+    user: AuthenticatedUser,
+    pool: &State<SqlitePool>,
+) -> Result<Json<DashboardData>, Status> {
+    let active_polls = polls::get_active_polls(pool)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let active_polls = polls::with_vote_totals(pool, active_polls)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    let expired_polls = polls::get_expired_polls(pool)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let expired_polls = polls::with_vote_totals(pool, expired_polls)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    let notifications = notify::get_unread(pool, user.id, 10)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    let stats = users::get_user_stats_detailed(pool, user.id)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    Ok(Json(DashboardData {
+        user: user.user,
+        active_polls,
+        expired_polls,
+        notifications,
+        stats,
+    }))
+}
+
+/// Lists the user's recent notifications, read and unread.
+///
+/// # Parameters
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
+/// * `pool` - Database connection pool
+/// * `flash` - Optional flash messages from previous actions
+///
+/// # Returns
+/// * `Ok(Template)` - Notifications page template
+/// * `Err(Status)` - Internal server error if database query fails
+#[get("/notifications")]
+pub async fn notifications(
+    user: AuthenticatedUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
+    pool: &State<SqlitePool>,
+    flash: Option<rocket::request::FlashMessage<'_>>,
+) -> Result<Template, Status> {
+    let notifications = notify::get_recent(pool, user.id, 50)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    Ok(Template::render(
+        "notifications",
+        context! {
+            title: "Notifications - Platform Engineering Game Night",
+            user: user.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            notifications: notifications,
             flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
         },
     ))
 }
 
+/// Marks one of the user's notifications as read.
+///
+/// # Parameters
+/// * `notification_id` - Unique identifier of the notification
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Redirect)` - Redirects back to the notifications page
+/// * `Err(Flash<Redirect>)` - Redirects with error message
+#[post("/notifications/<notification_id>/read")]
+pub async fn mark_notification_read(
+    notification_id: i64,
+    user: AuthenticatedUser,
+    pool: &State<SqlitePool>,
+) -> Result<Redirect, Flash<Redirect>> {
+    match notify::mark_read(pool, user.id, notification_id).await {
+        Ok(_) => Ok(Redirect::to(uri!(notifications))),
+        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
+            Redirect::to(uri!(notifications)),
+            "Notification not found.",
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(notifications)),
+            format!("Failed to update notification: {}", err),
+        )),
+    }
+}
+
+/// Marks all of the user's notifications as read.
+///
+/// # Parameters
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to the notifications page
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/notifications/read-all")]
+pub async fn mark_all_notifications_read(
+    user: AuthenticatedUser,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    match notify::mark_all_read(pool, user.id).await {
+        Ok(count) => Ok(Flash::success(
+            Redirect::to(uri!(notifications)),
+            format!("Marked {} notification{} as read.", count, if count == 1 { "" } else { "s" }),
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(notifications)),
+            format!("Failed to update notifications: {}", err),
+        )),
+    }
+}
+
 /// Displays all polls page with active and expired polls.
 ///
 /// This route provides a comprehensive view of all polls in the system,
@@ -168,6 +712,8 @@ pub async fn dashboard(
 ///
 /// # Parameters
 /// * `user` - Authenticated user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
 /// * `pool` - Database connection pool
 ///
 /// # Returns
@@ -176,6 +722,8 @@ pub async fn dashboard(
 #[get("/polls")]
 pub async fn get_polls(
     user: AuthenticatedUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
     pool: &State<SqlitePool>,
 ) -> Result<Template, Status> {
     let active_polls = polls::get_active_polls(pool)
@@ -191,539 +739,2475 @@ pub async fn get_polls(
         context! {
             title: "All Polls - Platform Engineering Game Night",
             user: user.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
             active_polls: active_polls,
             expired_polls: expired_polls,
+            layout: poll_list_layout(),
         },
     ))
 }
 
-/// Displays detailed view of a specific poll with voting options.
+/// Displays archived polls.
 ///
-/// This route shows a poll's details, options, vote counts, and allows
-/// users to cast or change their votes if the poll is still active.
+/// Archived polls are hidden from the dashboard and the all-polls page but
+/// remain viewable here and by direct link.
 ///
 /// # Parameters
-/// * `poll_id` - Unique identifier of the poll to display
 /// * `user` - Authenticated user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
 /// * `pool` - Database connection pool
-/// * `flash` - Optional flash messages from voting or other actions
+/// * `flash` - Optional flash messages
 ///
 /// # Returns
-/// * `Ok(Template)` - Poll detail template with voting interface
-/// * `Err(Status::NotFound)` - If poll doesn't exist
-/// * `Err(Status::InternalServerError)` - If database query fails
-#[get("/polls/<poll_id>")]
-pub async fn poll_detail(
-    poll_id: i64,
+/// * `Ok(Template)` - Archived polls page template
+/// * `Err(Status)` - Internal server error if database query fails
+#[get("/polls/archived")]
+pub async fn archived_polls(
     user: AuthenticatedUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
     pool: &State<SqlitePool>,
     flash: Option<rocket::request::FlashMessage<'_>>,
 ) -> Result<Template, Status> {
-    let poll = polls::get_poll_by_id(pool, poll_id)
-        .await
-        .map_err(|_| Status::NotFound)?;
-
-    let options = polls::get_poll_options(pool, poll_id)
-        .await
-        .map_err(|_| Status::InternalServerError)?;
-
-    let user_votes = polls::get_user_votes(pool, poll_id, user.id)
+    let archived_polls = polls::get_archived_polls(pool)
         .await
         .map_err(|_| Status::InternalServerError)?;
 
-    let poll_data = polls::format_poll_for_template(&poll, &options, &user_votes);
-
     Ok(Template::render(
-        "poll_detail",
+        "archived_polls",
         context! {
-            title: format!("{} - Platform Engineering Game Night", poll.title),
+            title: "Archived Polls - Platform Engineering Game Night",
             user: user.user,
-            poll: poll_data,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            archived_polls: archived_polls,
+            layout: poll_list_layout(),
             flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
         },
     ))
 }
 
-/// Displays detailed voter information for a poll (creator/admin only).
+/// Displays detailed view of a specific poll with voting options.
 ///
-/// This route shows who voted for each option in a poll. Access is restricted
-/// to the poll creator and admin users for privacy reasons.
-///
-/// # Access Control
-/// - Poll creators can view voters for their own polls
-/// - Admin users can view voters for any poll
-/// - Regular users cannot access this information
+/// This route shows a poll's details, options, vote counts, and allows
+/// users to cast or change their votes if the poll is still active.
 ///
 /// # Parameters
-/// * `poll_id` - Unique identifier of the poll
+/// * `poll_id` - Unique identifier of the poll to display
 /// * `user` - Authenticated user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
 /// * `pool` - Database connection pool
-/// * `flash` - Optional flash messages
+/// * `flash` - Optional flash messages from voting or other actions
 ///
 /// # Returns
-/// * `Ok(Template)` - Voters page with detailed voting information
-/// * `Err(Status::NotFound)` - If poll doesn't exist
-/// * `Err(Status::Forbidden)` - If user lacks permission
+/// * `Ok(Template)` - Poll detail template with voting interface
+/// * `Err(Status::NotFound)` - If poll doesn't exist, or the user may not see it
 /// * `Err(Status::InternalServerError)` - If database query fails
-#[get("/polls/<poll_id>/voters")]
-pub async fn poll_voters(
+#[get("/polls/<poll_id>")]
+pub async fn poll_detail(
     poll_id: i64,
     user: AuthenticatedUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
     pool: &State<SqlitePool>,
     flash: Option<rocket::request::FlashMessage<'_>>,
-) -> Result<Template, Status> {
-    // Get poll to check permissions
-    let poll = polls::get_poll_by_id(pool, poll_id)
+    if_none_match: IfNoneMatch,
+) -> Result<Conditional<Template>, Status> {
+    let poll = get_visible_poll(pool, poll_id, &user).await?;
+
+    // The page shows the viewer's own votes, so the tag is per user. Pages
+    // carrying a flash message are one-off and always rendered in full.
+    let etag = polls::get_poll_etag(pool, &poll, Some(user.id))
         .await
-        .map_err(|_| Status::NotFound)?;
+        .map_err(|_| Status::InternalServerError)?;
+    let etag = unread.tag(announcement.tag(etag));
 
-    // Only allow poll creator or admins to see who voted
-    if !user.is_admin && poll.creator_id != user.id {
-        return Err(Status::Forbidden);
+    if flash.is_none() && if_none_match.matches(&etag) {
+        return Ok(Conditional::NotModified((), Header::new("ETag", etag)));
     }
 
-    let voting_details = polls::get_poll_voting_details(pool, poll_id)
+    let options = polls::get_poll_options(pool.inner(), poll_id)
         .await
         .map_err(|_| Status::InternalServerError)?;
 
-    Ok(Template::render(
-        "poll_voters",
-        context! {
-            title: format!("Voters for {} - Platform Engineering Game Night", poll.title),
-            user: user.user,
-            voting_details: voting_details,
-            flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
-        },
+    let user_votes = polls::get_user_votes(pool, poll_id, user.id)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    let comments = polls::get_poll_comments(pool, poll_id)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    let mut poll_data = polls::format_poll_for_template(&poll, &options, &user_votes);
+    polls::attach_comments(&mut poll_data, &comments);
+
+    Ok(Conditional::Fresh(
+        Template::render(
+            "poll_detail",
+            context! {
+                title: format!("{} - Platform Engineering Game Night", poll.title),
+                user: user.user,
+                announcement: announcement.0,
+                unread_notifications: unread.0,
+                poll: poll_data,
+                flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
+            },
+        ),
+        Header::new("ETag", etag),
     ))
 }
 
-/// Displays the poll creation form page.
+/// Returns a poll's current results as JSON.
 ///
-/// This route renders the form for creating new polls, including
-/// fields for title, description, expiration date, and options.
+/// Responses carry a weak ETag that changes whenever a vote is cast or
+/// removed, so clients polling for live results can send `If-None-Match`
+/// and receive a bodyless `304 Not Modified` while nothing has changed.
 ///
 /// # Parameters
+/// * `poll_id` - Unique identifier of the poll
 /// * `user` - Authenticated user (enforced by request guard)
-/// * `flash` - Optional flash messages from previous creation attempts
+/// * `pool` - Database connection pool
+/// * `if_none_match` - The client's cached ETag, if any
 ///
 /// # Returns
-/// Poll creation form template
-#[get("/polls/create")]
-pub async fn create_poll_page(
+/// * `Ok(Conditional<Json>)` - Poll results, or 304 if unchanged
+/// * `Err(Status::NotFound)` - If poll doesn't exist, or the user may not see it
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/api/polls/<poll_id>/results")]
+pub async fn poll_results(
+    poll_id: i64,
     user: AuthenticatedUser,
-    flash: Option<rocket::request::FlashMessage<'_>>,
-) -> Template {
-    Template::render(
-        "create_poll",
-        context! {
-            title: "Create Poll - Platform Engineering Game Night",
-            user: user.user,
-            flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
-        },
-    )
+    pool: &State<SqlitePool>,
+    if_none_match: IfNoneMatch,
+) -> Result<Conditional<Json<serde_json::Value>>, Status> {
+    let poll = get_visible_poll(pool, poll_id, &user).await?;
+
+    let etag = polls::get_poll_etag(pool, &poll, None)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    if if_none_match.matches(&etag) {
+        return Ok(Conditional::NotModified((), Header::new("ETag", etag)));
+    }
+
+    let options = polls::get_poll_options(pool.inner(), poll_id)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    Ok(Conditional::Fresh(
+        Json(polls::format_poll_for_template(&poll, &options, &[])),
+        Header::new("ETag", etag),
+    ))
 }
 
-/// Handles poll creation form submission.
+/// Returns the number of votes for each of a poll's options as JSON.
 ///
-/// This route processes the new poll form data, creates the poll
-/// and its options in the database, and redirects to the new poll's
-/// detail page on success.
+/// A lighter alternative to [`poll_results`] for scripts that refresh
+/// counts frequently: the body is just `{"counts": {option_id: count},
+/// "total": n}` plus the same two fields weighted by vote weight
+/// (`weighted`, `weighted_total`), and each option's open slots
+/// (`remaining`) and whether it is `full`, both null for uncapped options.
+/// The weak ETag changes only when the counts do, so unchanged counts come
+/// back as a bodyless `304 Not Modified`.
 ///
 /// # Parameters
+/// * `poll_id` - Unique identifier of the poll
 /// * `user` - Authenticated user (enforced by request guard)
-/// * `form` - New poll form data
 /// * `pool` - Database connection pool
+/// * `if_none_match` - The client's cached ETag, if any
 ///
 /// # Returns
-/// * `Ok(Redirect)` - Redirects to new poll detail page on success
-/// * `Err(Flash<Redirect>)` - Redirects to creation page with error
-#[post("/polls/create", data = "<form>")]
-pub async fn create_poll_post(
+/// * `Ok(Conditional<Json>)` - Vote counts, or 304 if unchanged
+/// * `Err(Status::NotFound)` - If poll doesn't exist, or the user may not see it
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/polls/<poll_id>/counts.json")]
+pub async fn poll_counts_json(
+    poll_id: i64,
     user: AuthenticatedUser,
-    form: Form<NewPollForm>,
     pool: &State<SqlitePool>,
-) -> Result<Redirect, Flash<Redirect>> {
-    match polls::create_poll(pool, &form, user.id).await {
-        Ok(poll_id) => Ok(Redirect::to(uri!(poll_detail(poll_id)))),
-        Err(err) => Err(Flash::error(
-            Redirect::to(uri!(create_poll_page)),
-            format!("Failed to create poll: {}", err),
-        )),
+    if_none_match: IfNoneMatch,
+) -> Result<Conditional<Json<VoteCounts>>, Status> {
+    get_visible_poll(pool, poll_id, &user).await?;
+
+    let vote_counts = polls::get_vote_counts(pool, poll_id)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    let etag = polls::get_vote_counts_etag(poll_id, &vote_counts);
+    if if_none_match.matches(&etag) {
+        return Ok(Conditional::NotModified((), Header::new("ETag", etag)));
     }
+
+    Ok(Conditional::Fresh(Json(vote_counts), Header::new("ETag", etag)))
 }
 
-/// Handles voting on poll options (toggle functionality).
+/// Returns a poll's cumulative vote count over time as JSON, for charts.
 ///
-/// This route processes vote submissions with the following logic:
-/// - If user already voted for the option: remove their vote
-/// - If user hasn't voted for the option: add their vote
-/// - Prevents voting on expired polls
+/// The body is `{poll_id, bucket, buckets: [{timestamp, cumulative_votes}]}`
+/// (see [`polls::get_poll_timeline`]). `bucket` is `hourly` (the default)
+/// or `daily`. Only the poll's creator and admins may see it, since it
+/// shows how voting unfolded.
 ///
 /// # Parameters
 /// * `poll_id` - Unique identifier of the poll
+/// * `bucket` - Bucket size: `hourly` or `daily`
 /// * `user` - Authenticated user (enforced by request guard)
-/// * `form` - Vote form data containing option ID
 /// * `pool` - Database connection pool
 ///
 /// # Returns
-/// * `Ok(Redirect)` - Redirects back to poll detail page
-/// * `Err(Flash<Redirect>)` - Redirects with error message
-#[post("/polls/<poll_id>/vote", data = "<form>")]
-pub async fn vote_on_poll(
+/// * `Ok(Json<PollTimeline>)` - The timeline, with no buckets if nobody has voted
+/// * `Err(Status::BadRequest)` - If the bucket size is unknown
+/// * `Err(Status::NotFound)` - If poll doesn't exist, or the user may not see it
+/// * `Err(Status::Forbidden)` - If the user isn't the creator or an admin
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/polls/<poll_id>/timeline.json?<bucket>")]
+pub async fn poll_timeline_json(
     poll_id: i64,
+    bucket: Option<&str>,
     user: AuthenticatedUser,
-    form: Form<VoteForm>,
     pool: &State<SqlitePool>,
-) -> Result<Redirect, Flash<Redirect>> {
-    // Check if poll is active
-    let poll = match polls::get_poll_by_id(pool, poll_id).await {
-        Ok(poll) => poll,
-        Err(_) => {
-            return Err(Flash::error(
-                Redirect::to(uri!(poll_detail(poll_id))),
-                "Poll not found.",
-            ));
-        }
+) -> Result<Json<PollTimeline>, Status> {
+    let bucket = match bucket {
+        Some(value) => TimelineBucket::from_param(value).ok_or(Status::BadRequest)?,
+        None => TimelineBucket::Hourly,
     };
 
-    if poll.expires_at <= chrono::Utc::now() {
-        return Err(Flash::error(
-            Redirect::to(uri!(poll_detail(poll_id))),
-            "Cannot vote on expired poll.",
-        ));
+    let poll = get_visible_poll(pool, poll_id, &user).await?;
+    if !user.is_admin && poll.creator_id != user.id {
+        return Err(Status::Forbidden);
     }
 
-    match polls::vote_on_poll(pool, form.option_id, user.id).await {
-        Ok(_) => Ok(Redirect::to(uri!(poll_detail(poll_id)))),
-        Err(err) => Err(Flash::error(
-            Redirect::to(uri!(poll_detail(poll_id))),
-            format!("Failed to cast vote: {}", err),
-        )),
-    }
+    let buckets = polls::get_poll_timeline(pool, poll_id, bucket)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    Ok(Json(PollTimeline {
+        poll_id,
+        bucket,
+        buckets,
+    }))
 }
 
-/// Handles adding additional options to an existing poll
+/// Returns a poll's basic information as JSON, for listings and link previews.
+///
+/// The body is `{id, title, description, creator_username, created_at,
+/// expires_at, is_expired}`. It comes from the poll lookup alone, without
+/// the option and vote aggregation done by [`poll_results`].
 ///
 /// # Parameters
 /// * `poll_id` - Unique identifier of the poll
 /// * `user` - Authenticated user (enforced by request guard)
-/// * `form` - New options form data containing comma-separated options
 /// * `pool` - Database connection pool
+/// * `if_none_match` - The client's cached ETag, if any
 ///
 /// # Returns
-/// * `Ok(Redirect)` - Redirects back to poll detail page
-/// * `Err(Flash<Redirect>)` - Redirects with error message
-#[post("/polls/<poll_id>/add_options", data = "<form>")]
-pub async fn add_options_to_poll(
+/// * `Ok(Conditional<Json>)` - Poll information, or 304 if unchanged
+/// * `Err(Status::NotFound)` - If poll doesn't exist, or the user may not see it
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/polls/<poll_id>/meta.json")]
+pub async fn poll_meta_json(
     poll_id: i64,
     user: AuthenticatedUser,
-    form: Form<NewOptionsForm>,
     pool: &State<SqlitePool>,
-) -> Result<Redirect, Flash<Redirect>> {
-    // Check if poll is active
-    let poll = match polls::get_poll_by_id(pool, poll_id).await {
-        Ok(poll) => poll,
-        Err(_) => {
-            return Err(Flash::error(
-                Redirect::to(uri!(poll_detail(poll_id))),
-                "Poll not found.",
-            ));
-        }
-    };
-
-    if poll.expires_at <= chrono::Utc::now() {
-        return Err(Flash::error(
-            Redirect::to(uri!(poll_detail(poll_id))),
-            "Cannot modify an expired poll.",
-        ));
-    }
+    if_none_match: IfNoneMatch,
+) -> Result<Conditional<Json<PollMeta>>, Status> {
+    let poll = get_visible_poll(pool, poll_id, &user).await?;
 
-    // Check if user has permission to add options (creator or admin)
-    if !user.is_admin && poll.creator_id != user.id {
-        return Err(Flash::error(
-            Redirect::to(uri!(poll_detail(poll_id))),
-            "You don't have permission to modify this poll.",
-        ));
+    let meta = polls::get_poll_meta(&poll);
+    let etag = polls::get_poll_meta_etag(&meta);
+    if if_none_match.matches(&etag) {
+        return Ok(Conditional::NotModified((), Header::new("ETag", etag)));
     }
 
-    match polls::add_poll_options(pool, poll_id, &form).await {
-        Ok(_) => Ok(Redirect::to(uri!(poll_detail(poll_id)))),
-        Err(err) => Err(Flash::error(
-            Redirect::to(uri!(poll_detail(poll_id))),
-            format!("Failed to add options: {}", err),
-        )),
-    }
+    Ok(Conditional::Fresh(Json(meta), Header::new("ETag", etag)))
 }
 
-/// Handles removing a specific option from a poll (creator/admin only).
-///
-/// This route removes a poll option and all associated votes.
-/// Access is restricted to the poll creator and admin users.
+/// Returns a poll's options as JSON, optionally limited to a date range.
 ///
-/// # Access Control
-/// - Poll creators can remove options from their own polls
-/// - Admin users can remove options from any poll
-/// - Regular users cannot remove options from others' polls
-/// - Cannot remove options from expired polls
+/// With `from` and/or `to` (YYYY-MM-DDTHH:MM or RFC 3339, both inclusive),
+/// only date options in the range are returned, plus text options when
+/// `include_text=true`. Without a range every option is returned.
 ///
 /// # Parameters
 /// * `poll_id` - Unique identifier of the poll
-/// * `option_id` - Unique identifier of the option to remove
+/// * `from` - Optional start of the date range
+/// * `to` - Optional end of the date range
+/// * `include_text` - Whether to include options without a date in a range
 /// * `user` - Authenticated user (enforced by request guard)
 /// * `pool` - Database connection pool
 ///
 /// # Returns
-/// * `Ok(Flash<Redirect>)` - Success redirect to poll detail page
-/// * `Err(Flash<Redirect>)` - Error redirect with message
-#[post("/polls/<poll_id>/remove_option/<option_id>")]
-pub async fn remove_poll_option(
+/// * `Ok(Json<Vec<PollOption>>)` - Matching options with vote counts
+/// * `Err(Status::BadRequest)` - If a bound is malformed or `from` is after `to`
+/// * `Err(Status::NotFound)` - If poll doesn't exist, or the user may not see it
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/polls/<poll_id>/options?<from>&<to>&<include_text>")]
+pub async fn poll_options_json(
     poll_id: i64,
-    option_id: i64,
+    from: Option<&str>,
+    to: Option<&str>,
+    include_text: Option<bool>,
     user: AuthenticatedUser,
     pool: &State<SqlitePool>,
-) -> Result<Flash<Redirect>, Flash<Redirect>> {
-    match polls::remove_poll_option(pool, poll_id, option_id, user.id, user.is_admin).await {
-        Ok(_) => Ok(Flash::success(
-            Redirect::to(uri!(poll_detail(poll_id))),
-            "Option removed successfully.",
-        )),
-        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
-            Redirect::to(uri!(poll_detail(poll_id))),
-            "You don't have permission to remove this option, or the option doesn't exist.",
-        )),
-        Err(sqlx::Error::ColumnDecode { index, .. }) if index == "expired" => Err(Flash::error(
-            Redirect::to(uri!(poll_detail(poll_id))),
-            "Cannot modify options in an expired poll.",
-        )),
-        Err(err) => Err(Flash::error(
-            Redirect::to(uri!(poll_detail(poll_id))),
-            format!("Failed to remove option: {}", err),
-        )),
-    }
+) -> Result<Json<Vec<PollOption>>, Status> {
+    get_visible_poll(pool, poll_id, &user).await?;
+
+    polls::get_options_in_range(pool, poll_id, from, to, include_text.unwrap_or(false))
+        .await
+        .map(Json)
+        .map_err(|err| match err {
+            sqlx::Error::ColumnDecode { .. } => Status::BadRequest,
+            _ => Status::InternalServerError,
+        })
 }
 
-/// Handles poll deletion (creator/admin only).
+/// Displays detailed voter information for a poll.
 ///
-/// This route deletes a poll and all associated data including
-/// options and votes. Access is restricted to the poll creator
-/// and admin users.
+/// This route shows who voted for each option in a poll. Access is restricted
+/// for privacy reasons, unless the poll opts in to sharing with its voters.
 ///
 /// # Access Control
-/// - Poll creators can delete their own polls
-/// - Admin users can delete any poll
-/// - Regular users cannot delete others' polls
+/// - Poll creators can view voters for their own polls
+/// - Admin users can view voters for any poll
+/// - On polls with `open_voters` set, users who have voted can view voters
+/// - Other users cannot access this information
 ///
 /// # Parameters
-/// * `poll_id` - Unique identifier of the poll to delete
+/// * `poll_id` - Unique identifier of the poll
 /// * `user` - Authenticated user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
 /// * `pool` - Database connection pool
+/// * `flash` - Optional flash messages
 ///
 /// # Returns
-/// * `Ok(Flash<Redirect>)` - Success redirect to dashboard
-/// * `Err(Flash<Redirect>)` - Error redirect with message
-#[post("/polls/<poll_id>/delete")]
-pub async fn delete_poll(
+/// * `Ok(Template)` - Voters page with detailed voting information
+/// * `Err(Status::NotFound)` - If poll doesn't exist, or the user may not see it
+/// * `Err(Status::Forbidden)` - If user lacks permission
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/polls/<poll_id>/voters")]
+pub async fn poll_voters(
     poll_id: i64,
     user: AuthenticatedUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
     pool: &State<SqlitePool>,
-) -> Result<Flash<Redirect>, Flash<Redirect>> {
-    match polls::delete_poll(pool, poll_id, user.id, user.is_admin).await {
-        Ok(_) => Ok(Flash::success(
-            Redirect::to(uri!(dashboard)),
-            "Poll deleted successfully.",
-        )),
-        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
-            Redirect::to(uri!(poll_detail(poll_id))),
-            "You don't have permission to delete this poll.",
-        )),
-        Err(err) => Err(Flash::error(
-            Redirect::to(uri!(poll_detail(poll_id))),
-            format!("Failed to delete poll: {}", err),
-        )),
+    flash: Option<rocket::request::FlashMessage<'_>>,
+) -> Result<Template, Status> {
+    // Get poll to check permissions
+    let poll = get_visible_poll(pool, poll_id, &user).await?;
+
+    // Only allow poll creator, admins, or (for open polls) fellow voters to see who voted
+    let allowed = polls::can_view_voters(pool, &poll, &user)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    if !allowed {
+        return Err(Status::Forbidden);
     }
+
+    let voting_details = polls::get_poll_voters_view(pool, poll_id)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    Ok(Template::render(
+        "poll_voters",
+        context! {
+            title: format!("Voters for {} - Platform Engineering Game Night", poll.title),
+            user: user.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            voting_details: voting_details,
+            flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
+        },
+    ))
 }
 
-// ============================================================================
-// User Profile routes
-// ============================================================================
+/// Returns one page of an option's voters as JSON.
+///
+/// Large polls list only the first voters of each option on the voters
+/// page; this pages through the rest. Access mirrors the voters page.
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the poll
+/// * `option_id` - Unique identifier of the option
+/// * `page` - Page number, starting at 1 (default 1)
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Json<OptionVotersPage>)` - The page of voters and the option's total
+/// * `Err(Status::NotFound)` - If the poll doesn't exist, the user may not see it, or the option isn't part of it
+/// * `Err(Status::Forbidden)` - If user lacks permission
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/polls/<poll_id>/options/<option_id>/voters?<page>")]
+pub async fn option_voters_json(
+    poll_id: i64,
+    option_id: i64,
+    page: Option<i64>,
+    user: AuthenticatedUser,
+    pool: &State<SqlitePool>,
+) -> Result<Json<OptionVotersPage>, Status> {
+    let poll = get_visible_poll(pool, poll_id, &user).await?;
+
+    let allowed = polls::can_view_voters(pool, &poll, &user)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    if !allowed {
+        return Err(Status::Forbidden);
+    }
+
+    polls::get_option_voters_page(pool, poll_id, option_id, page.unwrap_or(1))
+        .await
+        .map(Json)
+        .map_err(poll_lookup_status)
+}
+
+/// Returns every vote on a poll as JSON for analysis (creator/admin only).
+///
+/// Each entry is a single vote with the voter and option details, ordered
+/// by when it was cast.
+///
+/// # Access Control
+/// - Poll creators can fetch ballots for their own polls
+/// - Admin users can fetch ballots for any poll
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the poll
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Json<Vec<Ballot>>)` - Ballots ordered by time
+/// * `Err(Status::NotFound)` - If poll doesn't exist, or the user may not see it
+/// * `Err(Status::Forbidden)` - If user lacks permission
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/polls/<poll_id>/ballots.json")]
+pub async fn poll_ballots(
+    poll_id: i64,
+    user: AuthenticatedUser,
+    pool: &State<SqlitePool>,
+) -> Result<Json<Vec<Ballot>>, Status> {
+    let poll = get_visible_poll(pool, poll_id, &user).await?;
+
+    if !user.is_admin && poll.creator_id != user.id {
+        return Err(Status::Forbidden);
+    }
+
+    polls::get_poll_ballots(pool, poll_id)
+        .await
+        .map(Json)
+        .map_err(|_| Status::InternalServerError)
+}
+
+/// Exports every vote on a poll as a CSV download.
+///
+/// Access mirrors the voters page. Rows are streamed to the client as they
+/// are read from the database, so large polls don't need to fit in memory.
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the poll
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(CsvDownload)` - `poll-<id>-voters.csv` attachment
+/// * `Err(Status::NotFound)` - If poll doesn't exist, or the user may not see it
+/// * `Err(Status::Forbidden)` - If user lacks permission
+#[get("/polls/<poll_id>/voters.csv")]
+pub async fn export_poll_voters_csv(
+    poll_id: i64,
+    user: AuthenticatedUser,
+    pool: &State<SqlitePool>,
+) -> Result<CsvDownload, Status> {
+    let poll = get_visible_poll(pool, poll_id, &user).await?;
+
+    let allowed = polls::can_view_voters(pool, &poll, &user)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    if !allowed {
+        return Err(Status::Forbidden);
+    }
+
+    Ok(CsvDownload::new(
+        &format!("poll-{}-voters.csv", poll_id),
+        polls::stream_poll_votes_csv(pool.inner().clone(), poll_id),
+    ))
+}
+
+/// Displays the poll creation form page.
+///
+/// This route renders the form for creating new polls, including
+/// fields for title, description, expiration date, and options. Past
+/// polls are listed so their options can be copied into the new one.
+///
+/// # Parameters
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
+/// * `flash` - Optional flash messages from previous creation attempts
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Template)` - Poll creation form template
+/// * `Err(Status)` - Internal server error if past polls can't be loaded
+#[get("/polls/create")]
+pub async fn create_poll_page(
+    user: AuthenticatedUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
+    flash: Option<rocket::request::FlashMessage<'_>>,
+    pool: &State<SqlitePool>,
+) -> Result<Template, Status> {
+    let past_polls = polls::get_expired_polls(pool)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    Ok(Template::render(
+        "create_poll",
+        context! {
+            title: "Create Poll - Platform Engineering Game Night",
+            user: user.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            past_polls: past_polls,
+            flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
+        },
+    ))
+}
+
+/// Handles poll creation form submission.
+///
+/// This route processes the new poll form data, creates the poll
+/// and its options in the database, and redirects to the new poll's
+/// detail page on success.
+///
+/// If the title repeats one of the creator's open polls, nothing is created;
+/// instead a confirmation page re-posts the same form with `confirm_duplicate`
+/// set, so an intentional duplicate is one click away.
+///
+/// # Parameters
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `form` - New poll form data
+/// * `pool` - Database connection pool
+/// * `announcement` - Active site-wide announcement, if any
+/// * `unread` - Number of unread notifications for the user
+///
+/// # Returns
+/// * `Ok(Either::Left(Redirect))` - Redirects to new poll detail page on success
+/// * `Ok(Either::Right(Template))` - Asks the creator to confirm a duplicate title
+/// * `Err(Flash<Redirect>)` - Redirects to creation page with error
+#[post("/polls/create", data = "<form>")]
+pub async fn create_poll_post(
+    user: AuthenticatedUser,
+    form: Form<NewPollForm>,
+    pool: &State<SqlitePool>,
+    announcement: Announcement,
+    unread: UnreadNotifications,
+) -> Result<Either<Redirect, Template>, Flash<Redirect>> {
+    match polls::create_poll(pool, &form, user.id).await {
+        Ok(poll_id) => Ok(Either::Left(Redirect::to(uri!(poll_detail(poll_id))))),
+        Err(sqlx::Error::ColumnDecode { index, source }) if index == "duplicate" => {
+            let existing = polls::find_duplicate_poll(pool, user.id, &form.title)
+                .await
+                .ok()
+                .flatten()
+                .map(|(id, _)| id);
+
+            Ok(Either::Right(Template::render(
+                "confirm_duplicate_poll",
+                context! {
+                    title: "Create Poll - Platform Engineering Game Night",
+                    user: user.user,
+                    announcement: announcement.0,
+                    unread_notifications: unread.0,
+                    warning: source.to_string(),
+                    existing_poll_id: existing,
+                    form: form.into_inner(),
+                },
+            )))
+        }
+        Err(sqlx::Error::ColumnDecode { source, .. }) => Err(Flash::error(
+            Redirect::to(uri!(create_poll_page)),
+            format!("Failed to create poll: {}", source),
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(create_poll_page)),
+            format!("Failed to create poll: {}", err),
+        )),
+    }
+}
+
+/// Returns a poll's structure as a shareable JSON template.
+///
+/// The template has the poll's title, description, options, and voting
+/// settings, but no votes, comments, creator, or times (see
+/// [`polls::get_poll_template`]). Another instance can recreate the poll
+/// from it through [`import_poll`].
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the poll
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Json<PollTemplate>)` - The poll's template
+/// * `Err(Status::NotFound)` - If poll doesn't exist, or the user may not see it
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/polls/<poll_id>/template.json")]
+pub async fn poll_template_json(
+    poll_id: i64,
+    user: AuthenticatedUser,
+    pool: &State<SqlitePool>,
+) -> Result<Json<PollTemplate>, Status> {
+    get_visible_poll(pool, poll_id, &user).await?;
+
+    polls::get_poll_template(pool, poll_id)
+        .await
+        .map(Json)
+        .map_err(poll_lookup_status)
+}
+
+/// Creates a poll from an uploaded poll template.
+///
+/// The new poll belongs to the importing user and expires at the time
+/// given with the upload. See [`polls::import_poll_template`] for the
+/// checks applied to the template.
+///
+/// # Parameters
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `form` - Multipart form with the template file and the new expiry
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Redirects to the new poll's page
+/// * `Err(Flash<Redirect>)` - Redirects to the creation page with the problem
+#[post("/polls/import", data = "<form>")]
+pub async fn import_poll(
+    user: AuthenticatedUser,
+    form: Form<ImportPollForm<'_>>,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    let mut contents = String::new();
+    let read_result = match form.file.open().await {
+        Ok(file) => Box::pin(file).read_to_string(&mut contents).await,
+        Err(err) => Err(err),
+    };
+
+    if let Err(err) = read_result {
+        log::error!("Error reading uploaded poll template: {}", err);
+        return Err(Flash::error(
+            Redirect::to(uri!(create_poll_page)),
+            "Could not read the uploaded file. Please upload a UTF-8 encoded JSON template.",
+        ));
+    }
+
+    match polls::import_poll_template(pool, &contents, &form.expires_at, user.id).await {
+        Ok(poll_id) => Ok(Flash::success(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "Poll imported.",
+        )),
+        Err(sqlx::Error::ColumnDecode { source, .. }) => Err(Flash::error(
+            Redirect::to(uri!(create_poll_page)),
+            format!("Failed to import poll: {}", source),
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(create_poll_page)),
+            format!("Failed to import poll: {}", err),
+        )),
+    }
+}
+
+/// Handles voting on poll options (toggle functionality).
+///
+/// This route processes vote submissions with the following logic:
+/// - If user already voted for the option: remove their vote
+/// - If user hasn't voted for the option: add their vote
+/// - Prevents voting on expired polls
+/// - Prevents voting or unvoting once the poll's votes are locked
+///
+/// The success message distinguishes a first vote, an additional vote,
+/// and a removed vote.
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the poll
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `form` - Vote form data containing option ID and optional comment
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Redirects back to poll detail page with feedback
+/// * `Err(Flash<Redirect>)` - Redirects with error message
+#[post("/polls/<poll_id>/vote", data = "<form>")]
+pub async fn vote_on_poll(
+    poll_id: i64,
+    user: AuthenticatedUser,
+    form: Form<VoteForm>,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    // Check the poll exists and the user can see it; expiry and locking
+    // are enforced when voting
+    get_visible_poll_or_flash(pool, poll_id, &user, "Failed to cast vote").await?;
+
+    let comment = form.comment.as_deref();
+    match polls::vote_on_poll(pool, poll_id, form.option_id, user.id, comment).await {
+        Ok(outcome) => {
+            let message = match outcome {
+                VoteOutcome::FirstVote => "Your vote has been recorded.".to_string(),
+                VoteOutcome::Added => "Your vote has been added to your other choices.".to_string(),
+                VoteOutcome::Changed { from, to } => {
+                    format!("Your vote has been changed from \"{}\" to \"{}\".", from, to)
+                }
+                VoteOutcome::Removed => "Your vote has been removed.".to_string(),
+                VoteOutcome::ClosedAtQuorum => {
+                    "Your vote has been recorded. The poll reached its quorum and is now closed."
+                        .to_string()
+                }
+            };
+            Ok(Flash::success(Redirect::to(uri!(poll_detail(poll_id))), message))
+        }
+        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "That option is not part of this poll.",
+        )),
+        Err(sqlx::Error::ColumnDecode { source, .. }) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            source.to_string(),
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            format!("Failed to cast vote: {}", err),
+        )),
+    }
+}
+
+/// Suggests a new option as a write-in and votes for it.
+///
+/// Only available on polls that allow write-ins.
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the poll
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `form` - Write-in form data containing the suggested option
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Redirects back to poll detail page
+/// * `Err(Flash<Redirect>)` - Redirects with error message
+#[post("/polls/<poll_id>/write-in", data = "<form>")]
+pub async fn add_write_in(
+    poll_id: i64,
+    user: AuthenticatedUser,
+    form: Form<WriteInForm>,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    get_visible_poll_or_flash(pool, poll_id, &user, "Failed to add write-in").await?;
+
+    match polls::add_write_in(pool, poll_id, &form.text, user.id).await {
+        Ok(VoteOutcome::ClosedAtQuorum) => Ok(Flash::success(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "Your suggestion was added with your vote. The poll reached its quorum and is now closed.",
+        )),
+        Ok(VoteOutcome::Changed { from, .. }) => Ok(Flash::success(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            format!("Your suggestion was added, and your vote moved to it from \"{}\".", from),
+        )),
+        Ok(_) => Ok(Flash::success(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "Your suggestion was added with your vote.",
+        )),
+        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
+            Redirect::to(uri!(dashboard)),
+            "Poll not found.",
+        )),
+        Err(sqlx::Error::ColumnDecode { source, .. }) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            source.to_string(),
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            format!("Failed to add write-in: {}", err),
+        )),
+    }
+}
+
+/// Posts a comment about one of a poll's options.
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the poll
+/// * `option_id` - Unique identifier of the option being discussed
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `form` - Comment form data
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Redirects back to poll detail page
+/// * `Err(Flash<Redirect>)` - Redirects with error message
+#[post("/polls/<poll_id>/options/<option_id>/comments", data = "<form>")]
+pub async fn add_option_comment(
+    poll_id: i64,
+    option_id: i64,
+    user: AuthenticatedUser,
+    form: Form<CommentForm>,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    // Check the poll exists and is visible so a bad option can be reported separately
+    get_visible_poll_or_flash(pool, poll_id, &user, "Failed to post comment").await?;
+
+    match polls::add_comment(pool, poll_id, Some(option_id), user.id, &form.body).await {
+        Ok(_) => Ok(Flash::success(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "Your comment has been posted.",
+        )),
+        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "That option is not part of this poll.",
+        )),
+        Err(sqlx::Error::ColumnDecode { source, .. }) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            source.to_string(),
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            format!("Failed to post comment: {}", err),
+        )),
+    }
+}
+
+/// Handles adding additional options to an existing poll
+///
+/// Options can be added to an expired poll. Setting `also_extend` moves the
+/// poll's expiration to the submitted time as part of the same change,
+/// reopening it for votes.
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the poll
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `form` - New options form data containing comma-separated options, and
+///   optionally a new expiration
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Redirect)` - Redirects back to poll detail page
+/// * `Err(Flash<Redirect>)` - Redirects with error message
+#[post("/polls/<poll_id>/add_options", data = "<form>")]
+pub async fn add_options_to_poll(
+    poll_id: i64,
+    user: AuthenticatedUser,
+    form: Form<NewOptionsForm>,
+    pool: &State<SqlitePool>,
+) -> Result<Redirect, Flash<Redirect>> {
+    let poll = get_visible_poll_or_flash(pool, poll_id, &user, "Failed to add options").await?;
+
+    // Check if user has permission to add options (creator or admin)
+    if !user.is_admin && poll.creator_id != user.id {
+        return Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "You don't have permission to modify this poll.",
+        ));
+    }
+
+    match polls::add_poll_options(pool, poll_id, &form, user.id).await {
+        Ok(_) => Ok(Redirect::to(uri!(poll_detail(poll_id)))),
+        Err(sqlx::Error::ColumnDecode { source, .. }) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            format!("Failed to add options: {}", source),
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            format!("Failed to add options: {}", err),
+        )),
+    }
+}
+
+/// Handles removing a specific option from a poll (creator/admin only).
+///
+/// This route removes a poll option and all associated votes.
+/// Access is restricted to the poll creator and admin users.
+///
+/// # Access Control
+/// - Poll creators can remove options from their own polls
+/// - Admin users can remove options from any poll
+/// - Regular users cannot remove options from others' polls
+/// - Cannot remove options from expired polls
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the poll
+/// * `option_id` - Unique identifier of the option to remove
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to poll detail page
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/polls/<poll_id>/remove_option/<option_id>")]
+pub async fn remove_poll_option(
+    poll_id: i64,
+    option_id: i64,
+    user: AuthenticatedUser,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    match polls::remove_poll_option(pool, poll_id, option_id, user.id, user.is_admin).await {
+        Ok(_) => Ok(Flash::success(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "Option removed successfully.",
+        )),
+        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "That option doesn't exist.",
+        )),
+        Err(sqlx::Error::ColumnDecode { index, .. }) if index == "forbidden" => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "You don't have permission to remove this option.",
+        )),
+        Err(sqlx::Error::ColumnDecode { index, .. }) if index == "expired" => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "Cannot modify options in an expired poll.",
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            format!("Failed to remove option: {}", err),
+        )),
+    }
+}
+
+/// Handles poll deletion (creator/admin only).
+///
+/// This route deletes a poll and all associated data including
+/// options and votes. Access is restricted to the poll creator
+/// and admin users. The deletion can be undone from the dashboard
+/// for a short time with [`undo_delete_poll`].
+///
+/// # Access Control
+/// - Poll creators can delete their own polls
+/// - Admin users can delete any poll
+/// - Regular users cannot delete others' polls
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the poll to delete
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to dashboard
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/polls/<poll_id>/delete")]
+pub async fn delete_poll(
+    poll_id: i64,
+    user: AuthenticatedUser,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    match polls::delete_poll(pool, poll_id, user.id, user.is_admin).await {
+        Ok(_) => Ok(Flash::success(
+            Redirect::to(uri!(dashboard)),
+            "Poll deleted successfully.",
+        )),
+        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
+            Redirect::to(uri!(dashboard)),
+            "Poll not found.",
+        )),
+        Err(sqlx::Error::ColumnDecode { index, .. }) if index == "forbidden" => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "You don't have permission to delete this poll.",
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            format!("Failed to delete poll: {}", err),
+        )),
+    }
+}
+
+/// Restores a recently deleted poll (deleter/admin only).
+///
+/// Only possible for a short time after the deletion, see
+/// [`tombstone::UNDO_WINDOW_SECONDS`].
+///
+/// # Parameters
+/// * `poll_id` - ID of the deleted poll
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to the restored poll
+/// * `Err(Flash<Redirect>)` - Error redirect to dashboard with message
+#[post("/polls/<poll_id>/undo-delete")]
+pub async fn undo_delete_poll(
+    poll_id: i64,
+    user: AuthenticatedUser,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    match polls::undo_delete_poll(pool, poll_id, user.id, user.is_admin).await {
+        Ok(_) => Ok(Flash::success(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "Poll restored.",
+        )),
+        Err(sqlx::Error::ColumnDecode { index, .. }) if index == "forbidden" => Err(Flash::error(
+            Redirect::to(uri!(dashboard)),
+            "You can only restore polls you deleted.",
+        )),
+        Err(sqlx::Error::ColumnDecode { source, .. }) => Err(Flash::error(
+            Redirect::to(uri!(dashboard)),
+            source.to_string(),
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(dashboard)),
+            format!("Failed to restore poll: {}", err),
+        )),
+    }
+}
+
+/// Reopens an expired poll with a new expiration date (creator/admin only).
+///
+/// Polls that are still active are rejected rather than extended.
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the poll to reopen
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `form` - Reopen form data containing the new expiration date
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to poll detail page
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/polls/<poll_id>/reopen", data = "<form>")]
+pub async fn reopen_poll(
+    poll_id: i64,
+    user: AuthenticatedUser,
+    form: Form<ReopenPollForm>,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    match polls::reopen_poll(pool, poll_id, &form.expires_at, user.id, user.is_admin).await {
+        Ok(_) => Ok(Flash::success(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "Poll reopened successfully.",
+        )),
+        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
+            Redirect::to(uri!(dashboard)),
+            "Poll not found.",
+        )),
+        Err(sqlx::Error::ColumnDecode { index, .. }) if index == "forbidden" => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "You don't have permission to reopen this poll.",
+        )),
+        Err(sqlx::Error::ColumnDecode { source, .. }) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            source.to_string(),
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            format!("Failed to reopen poll: {}", err),
+        )),
+    }
+}
+
+/// Archives or restores a poll (creator/admin only).
+///
+/// Archived polls are hidden from the poll listings but not deleted.
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the poll
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `form` - Archive form data containing the desired state
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to poll detail page
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/polls/<poll_id>/archive", data = "<form>")]
+pub async fn archive_poll(
+    poll_id: i64,
+    user: AuthenticatedUser,
+    form: Form<ArchivePollForm>,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    match polls::set_poll_archived(pool, poll_id, form.archived, user.id, user.is_admin).await {
+        Ok(_) => Ok(Flash::success(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            if form.archived {
+                "Poll archived. It no longer appears in the poll listings."
+            } else {
+                "Poll restored to the poll listings."
+            },
+        )),
+        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
+            Redirect::to(uri!(dashboard)),
+            "Poll not found.",
+        )),
+        Err(sqlx::Error::ColumnDecode { index, .. }) if index == "forbidden" => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "You don't have permission to archive this poll.",
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            format!("Failed to archive poll: {}", err),
+        )),
+    }
+}
+
+/// Finalizes a poll, freezing its results (creator/admin only).
+///
+/// A snapshot of the results is stored and the poll stops accepting votes.
+/// The snapshot is then served by [`poll_snapshot`].
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the poll
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to poll detail page
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/polls/<poll_id>/finalize")]
+pub async fn finalize_poll(
+    poll_id: i64,
+    user: AuthenticatedUser,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    match polls::finalize_poll(pool, poll_id, user.id, user.is_admin).await {
+        Ok(_) => Ok(Flash::success(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "Poll finalized. Its results have been recorded and voting is closed.",
+        )),
+        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
+            Redirect::to(uri!(dashboard)),
+            "Poll not found.",
+        )),
+        Err(sqlx::Error::ColumnDecode { index, .. }) if index == "forbidden" => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "You don't have permission to finalize this poll.",
+        )),
+        Err(sqlx::Error::ColumnDecode { source, .. }) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            source.to_string(),
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            format!("Failed to finalize poll: {}", err),
+        )),
+    }
+}
+
+/// Returns the results recorded when a poll was finalized, as JSON.
+///
+/// The snapshot is served as stored, even if votes have changed since.
+/// It names voters, so access mirrors the voters page.
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the poll
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Json)` - `poll_id`, `finalized_by`, `finalized_at`, and the `results`
+/// * `Err(Status::NotFound)` - If the poll doesn't exist, the user may not see it, or it hasn't been finalized
+/// * `Err(Status::Forbidden)` - If user lacks permission
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/polls/<poll_id>/snapshot")]
+pub async fn poll_snapshot(
+    poll_id: i64,
+    user: AuthenticatedUser,
+    pool: &State<SqlitePool>,
+) -> Result<Json<serde_json::Value>, Status> {
+    let poll = get_visible_poll(pool, poll_id, &user).await?;
+
+    let allowed = polls::can_view_voters(pool, &poll, &user)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    if !allowed {
+        return Err(Status::Forbidden);
+    }
+
+    let snapshot = polls::get_results_snapshot(pool, poll_id)
+        .await
+        .map_err(poll_lookup_status)?;
+    let results: serde_json::Value =
+        serde_json::from_str(&snapshot.results).map_err(|_| Status::InternalServerError)?;
+
+    Ok(Json(serde_json::json!({
+        "poll_id": snapshot.poll_id,
+        "finalized_by": snapshot.finalized_by,
+        "finalized_at": snapshot.created_at.to_rfc3339(),
+        "results": results,
+    })))
+}
+
+/// Closes a poll now and announces the results to its voters (creator/admin only).
+///
+/// The notifications go out in the background, so the redirect doesn't
+/// wait for them.
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the poll
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Redirects to poll detail page with how many will be notified
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/polls/<poll_id>/close-and-announce")]
+pub async fn close_and_announce(
+    poll_id: i64,
+    user: AuthenticatedUser,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    match polls::close_and_announce(pool, poll_id, user.id, user.is_admin).await {
+        Ok(0) => Ok(Flash::success(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "Poll closed. Nobody had voted, so there was nobody to tell.",
+        )),
+        Ok(count) => Ok(Flash::success(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            format!(
+                "Poll closed. The results are being sent to {} voter{}.",
+                count,
+                if count == 1 { "" } else { "s" }
+            ),
+        )),
+        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
+            Redirect::to(uri!(dashboard)),
+            "Poll not found.",
+        )),
+        Err(sqlx::Error::ColumnDecode { index, .. }) if index == "forbidden" => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "You don't have permission to close this poll.",
+        )),
+        Err(sqlx::Error::ColumnDecode { source, .. }) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            source.to_string(),
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            format!("Failed to close poll: {}", err),
+        )),
+    }
+}
+
+/// Sends an in-app reminder to everyone who hasn't voted yet (creator/admin only).
+///
+/// Nudges for the same poll are throttled to one per `NUDGE_INTERVAL_HOURS`.
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the poll
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Redirects to poll detail page with how many were nudged
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/polls/<poll_id>/nudge")]
+pub async fn nudge_non_voters(
+    poll_id: i64,
+    user: AuthenticatedUser,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    match polls::nudge_non_voters(pool, poll_id, user.id, user.is_admin).await {
+        Ok(0) => Ok(Flash::success(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "Everyone has already voted, so nobody was nudged.",
+        )),
+        Ok(count) => Ok(Flash::success(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            format!(
+                "Nudged {} user{} who haven't voted yet.",
+                count,
+                if count == 1 { "" } else { "s" }
+            ),
+        )),
+        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
+            Redirect::to(uri!(dashboard)),
+            "Poll not found.",
+        )),
+        Err(sqlx::Error::ColumnDecode { index, .. }) if index == "forbidden" => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "You don't have permission to nudge voters for this poll.",
+        )),
+        Err(sqlx::Error::ColumnDecode { source, .. }) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            source.to_string(),
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            format!("Failed to nudge voters: {}", err),
+        )),
+    }
+}
+
+/// Creates a runoff poll from a poll's highest-voted options (creator/admin only).
+///
+/// # Parameters
+/// * `poll_id` - Unique identifier of the source poll
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `form` - Runoff form data containing the option count and expiration
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Redirect)` - Redirects to the new runoff poll
+/// * `Err(Flash<Redirect>)` - Redirects back to the source poll with an error message
+#[post("/polls/<poll_id>/runoff", data = "<form>")]
+pub async fn create_runoff(
+    poll_id: i64,
+    user: AuthenticatedUser,
+    form: Form<RunoffForm>,
+    pool: &State<SqlitePool>,
+) -> Result<Redirect, Flash<Redirect>> {
+    match polls::create_runoff(pool, poll_id, form.top_n, &form.expires_at, user.id, user.is_admin)
+        .await
+    {
+        Ok(runoff_id) => Ok(Redirect::to(uri!(poll_detail(runoff_id)))),
+        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
+            Redirect::to(uri!(dashboard)),
+            "Poll not found.",
+        )),
+        Err(sqlx::Error::ColumnDecode { index, .. }) if index == "forbidden" => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            "You don't have permission to start a runoff for this poll.",
+        )),
+        Err(sqlx::Error::ColumnDecode { source, .. }) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            source.to_string(),
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(poll_detail(poll_id))),
+            format!("Failed to create runoff: {}", err),
+        )),
+    }
+}
+
+/// Displays the user's upcoming game nights.
+///
+/// Lists the date options the user has voted for on polls that are still
+/// open, in chronological order.
+///
+/// # Parameters
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Template)` - Calendar page template
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/calendar")]
+pub async fn calendar(
+    user: AuthenticatedUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
+    pool: &State<SqlitePool>,
+) -> Result<Template, Status> {
+    let entries = polls::get_user_calendar(pool, user.id)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    Ok(Template::render(
+        "calendar",
+        context! {
+            title: "My Game Nights - Platform Engineering Game Night",
+            user: user.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            entries: entries,
+        },
+    ))
+}
+
+// ============================================================================
+// User Profile routes
+// ============================================================================
+
+/// Displays the user profile page with statistics.
+///
+/// This route shows the user's profile information including
+/// statistics about polls created and votes cast.
+///
+/// # Parameters
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
+/// * `pool` - Database connection pool
+/// * `flash` - Optional flash messages from profile updates
+///
+/// # Returns
+/// * `Ok(Template)` - Profile page template with user statistics
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/profile")]
+pub async fn profile(
+    user: AuthenticatedUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
+    pool: &State<SqlitePool>,
+    flash: Option<rocket::request::FlashMessage<'_>>,
+) -> Result<Template, Status> {
+    // Get user statistics
+    let stats = users::get_user_stats_detailed(pool, user.id)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    Ok(Template::render(
+        "profile",
+        context! {
+            title: "User Profile - Platform Engineering Game Night",
+            user: user.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            stats: stats,
+            flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
+        },
+    ))
+}
+
+/// Returns another user's public profile as JSON.
+///
+/// Only the fields in [`PublicUserInfo`] are exposed; password hashes and
+/// session details never are. Admins also see the user's last login time.
+///
+/// # Parameters
+/// * `user_id` - Unique identifier of the user to look up
+/// * `viewer` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Json<PublicUserInfo>)` - The user's public profile
+/// * `Err(Status::NotFound)` - If the user doesn't exist
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/users/<user_id>")]
+pub async fn public_profile(
+    user_id: i64,
+    viewer: AuthenticatedUser,
+    pool: &State<SqlitePool>,
+) -> Result<Json<PublicUserInfo>, Status> {
+    users::get_public_user_info(pool, user_id, viewer.is_admin)
+        .await
+        .map_err(|_| Status::InternalServerError)?
+        .map(Json)
+        .ok_or(Status::NotFound)
+}
+
+/// Returns the logged-in user's own profile as JSON.
+///
+/// For scripts and front ends sharing the browser session. The body holds
+/// the user's account details (never the password hash or session details)
+/// plus `polls_created` and `votes_cast`.
+///
+/// # Parameters
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Json<CurrentUserInfo>)` - The user's profile
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/api/me")]
+pub async fn current_user(
+    user: AuthenticatedUser,
+    pool: &State<SqlitePool>,
+) -> Result<Json<CurrentUserInfo>, Status> {
+    let (polls_created, votes_cast) = users::get_user_stats(pool, user.id)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    Ok(Json(CurrentUserInfo {
+        user: user.user,
+        polls_created,
+        votes_cast,
+    }))
+}
+
+/// Downloads everything associated with the logged-in user as JSON.
+///
+/// The export holds the user's account details (never the password hash
+/// or session details), the polls they created with their options, and
+/// the votes they cast with the poll and option of each. It is streamed to
+/// the client as it is read from the database.
+///
+/// # Parameters
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// `game-night-export.json` attachment
+#[get("/profile/export.json")]
+pub async fn export_my_data(user: AuthenticatedUser, pool: &State<SqlitePool>) -> JsonDownload {
+    JsonDownload::new(
+        "game-night-export.json",
+        users::stream_user_export(pool.inner().clone(), user.user),
+    )
+}
+
+/// Handles password change requests.
+///
+/// This route processes password change forms, validates the current
+/// password, and updates the user's password hash in the database.
+///
+/// # Parameters
+/// * `user` - Authenticated user (enforced by request guard)
+/// * `form` - Password change form data
+/// * `cookies` - Cookie jar for refreshing the re-authentication marker
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to profile page
+/// * `Err(Flash<Redirect>)` - Error redirect to profile page
+#[post("/profile/password", data = "<form>")]
+pub async fn change_password(
+    user: AuthenticatedUser,
+    form: Form<ChangePasswordForm>,
+    cookies: &CookieJar<'_>,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    let result = users::change_password(pool, user.id, &form).await;
+
+    // The current password was just checked, which counts as re-authenticating
+    if result.is_ok() {
+        auth::set_reauth_cookie(cookies, &user);
+    }
+
+    result
+}
+
+// ============================================================================
+// Admin routes (require admin privileges)
+// ============================================================================
+
+/// Displays the admin user management page.
+///
+/// This route shows all users in the system and provides admin
+/// controls for managing user roles and accounts.
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+///
+/// # Parameters
+/// * `admin` - Admin user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
+/// * `pool` - Database connection pool
+/// * `flash` - Optional flash messages from admin actions
+///
+/// # Returns
+/// * `Ok(Template)` - Admin users page template
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/admin/users")]
+pub async fn admin_users(
+    admin: AdminUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
+    pool: &State<SqlitePool>,
+    flash: Option<rocket::request::FlashMessage<'_>>,
+) -> Result<Template, Status> {
+    let users = users::get_all_users(pool)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    Ok(Template::render(
+        "admin_users",
+        context! {
+            title: "Manage Users - Platform Engineering Game Night",
+            user: admin.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            users: users,
+            delete_policy: users::user_delete_policy().as_str(),
+            inactive_account_days: users::inactive_account_days(),
+            flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
+        },
+    ))
+}
+
+/// Streaming CSV file download response with an attachment filename.
+pub struct CsvDownload {
+    body: TextStream<CsvStream>,
+    disposition: Header<'static>,
+}
+
+impl<'r> Responder<'r, 'r> for CsvDownload {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'r> {
+        Response::build_from(self.body.respond_to(request)?)
+            .header(ContentType::CSV)
+            .header(self.disposition)
+            .ok()
+    }
+}
+
+impl CsvDownload {
+    /// Wraps a CSV stream as a download with the given filename.
+    fn new(filename: &str, body: CsvStream) -> Self {
+        CsvDownload {
+            body: TextStream(body),
+            disposition: Header::new(
+                "Content-Disposition",
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        }
+    }
+}
+
+/// Streaming JSON file download response with an attachment filename.
+pub struct JsonDownload {
+    body: TextStream<CsvStream>,
+    disposition: Header<'static>,
+}
+
+impl<'r> Responder<'r, 'r> for JsonDownload {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'r> {
+        Response::build_from(self.body.respond_to(request)?)
+            .header(ContentType::JSON)
+            .header(self.disposition)
+            .ok()
+    }
+}
+
+impl JsonDownload {
+    /// Wraps a stream of JSON fragments as a download with the given filename.
+    fn new(filename: &str, body: CsvStream) -> Self {
+        JsonDownload {
+            body: TextStream(body),
+            disposition: Header::new(
+                "Content-Disposition",
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        }
+    }
+}
+
+/// Exports the user list as a CSV download (admin only).
+///
+/// The export includes each user's ID, username, admin flag, creation
+/// time, and last login time. Password hashes are never included. Rows
+/// are streamed to the client as they are read from the database.
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+///
+/// # Parameters
+/// * `_admin` - Admin user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// `users.csv` attachment
+#[get("/admin/users.csv")]
+pub async fn export_users_csv(_admin: AdminUser, pool: &State<SqlitePool>) -> CsvDownload {
+    CsvDownload::new("users.csv", users::stream_users_csv(pool.inner().clone()))
+}
+
+/// Handles user role changes (promote/demote admin status).
+///
+/// This route allows admins to change user roles between regular
+/// user and admin status. Includes safety checks to prevent
+/// admins from demoting themselves.
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+/// and a recent password confirmation (see [`crate::auth`])
+///
+/// # Parameters
+/// * `admin` - Admin user performing the action
+/// * `form` - Role toggle form data
+/// * `cookies` - Cookie jar holding the re-authentication marker
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to admin users page
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/admin/users/role", data = "<form>")]
+pub async fn toggle_user_role(
+    admin: AdminUser,
+    form: Form<ToggleRoleForm>,
+    cookies: &CookieJar<'_>,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    if let Some(prompt) = reauth_prompt(cookies, &admin, "/admin/users") {
+        return Err(prompt);
+    }
+    users::toggle_user_role(pool, form.user_id, form.set_admin, admin.id).await
+}
+
+/// Handles setting how much a user's votes count (admin only).
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+///
+/// # Parameters
+/// * `user_id` - ID of the user whose vote weight should be changed
+/// * `admin` - Admin user performing the action
+/// * `form` - New vote weight
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to admin users page
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/admin/users/<user_id>/vote-weight", data = "<form>")]
+pub async fn set_vote_weight(
+    user_id: i64,
+    admin: AdminUser,
+    form: Form<VoteWeightForm>,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    users::set_vote_weight(pool, user_id, form.vote_weight, admin.id).await
+}
+
+/// Handles changing several users' roles at once (admin only).
+///
+/// The single-user toggle remains the usual path; this applies every row
+/// of the user management table in one transaction. The admin's own
+/// account is skipped.
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+/// and a recent password confirmation (see [`crate::auth`])
+///
+/// # Parameters
+/// * `admin` - Admin user performing the action
+/// * `form` - Requested role for each listed user
+/// * `cookies` - Cookie jar holding the re-authentication marker
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Summary redirect to admin users page
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/admin/users/roles/bulk", data = "<form>")]
+pub async fn bulk_toggle_roles(
+    admin: AdminUser,
+    form: Form<BulkRoleForm>,
+    cookies: &CookieJar<'_>,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    if let Some(prompt) = reauth_prompt(cookies, &admin, "/admin/users") {
+        return Err(prompt);
+    }
+    users::bulk_toggle_roles(pool, &form.changes, admin.id).await
+}
+
+/// Displays the add user form page (admin only).
+///
+/// This route renders the form for creating new user accounts,
+/// including options for setting admin privileges.
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+///
+/// # Parameters
+/// * `admin` - Admin user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
+/// * `flash` - Optional flash messages from previous creation attempts
+///
+/// # Returns
+/// Add user form template
+#[get("/admin/users/add")]
+pub async fn add_user_page(
+    admin: AdminUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
+    flash: Option<rocket::request::FlashMessage<'_>>,
+) -> Template {
+    Template::render(
+        "add_user",
+        context! {
+            title: "Add User - Platform Engineering Game Night",
+            user: admin.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
+        },
+    )
+}
+
+/// Handles new user creation form submission (admin only).
+///
+/// This route processes new user forms, validates the data,
+/// and creates new user accounts in the database.
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+///
+/// # Parameters
+/// * `_admin` - Admin user (authentication only, not used in logic)
+/// * `form` - New user form data
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to admin users page
+/// * `Err(Flash<Redirect>)` - Error redirect to add user page
+#[post("/admin/users/add", data = "<form>")]
+pub async fn add_user_post(
+    _admin: AdminUser,
+    form: Form<NewUserForm>,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    users::add_user_controller(pool, &form).await
+}
+
+/// Handles bulk user import from an uploaded CSV file (admin only).
+///
+/// The CSV must have the columns `username,password,is_admin`. Valid rows
+/// are created and invalid rows are reported back in a summary message.
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+///
+/// # Parameters
+/// * `admin` - Admin user performing the import
+/// * `form` - Multipart form containing the uploaded CSV file
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Summary redirect to admin users page
+/// * `Err(Flash<Redirect>)` - Error redirect to add user page
+#[post("/admin/users/import", data = "<form>")]
+pub async fn import_users(
+    admin: AdminUser,
+    form: Form<ImportUsersForm<'_>>,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    let mut contents = String::new();
+    let read_result = match form.file.open().await {
+        Ok(file) => Box::pin(file).read_to_string(&mut contents).await,
+        Err(err) => Err(err),
+    };
+
+    if let Err(err) = read_result {
+        log::error!("Error reading uploaded user CSV: {}", err);
+        return Err(Flash::error(
+            Redirect::to(uri!(add_user_page)),
+            "Could not read the uploaded file. Please upload a UTF-8 encoded CSV.",
+        ));
+    }
+
+    users::import_users_controller(pool, &contents, admin.id).await
+}
+
+/// Revokes all active sessions for a user (admin only).
+///
+/// This route invalidates every session cookie previously issued to the
+/// target user, forcing them to log in again. Useful when an account may
+/// have been compromised.
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+///
+/// # Parameters
+/// * `user_id` - ID of the user whose sessions should be revoked
+/// * `admin` - Admin user performing the action
+/// * `form` - Revocation form data (self-revocation confirmation)
+/// * `cookies` - Cookie jar for re-issuing the admin's own session
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to admin users page
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/admin/users/<user_id>/revoke-sessions", data = "<form>")]
+pub async fn revoke_user_sessions(
+    user_id: i64,
+    admin: AdminUser,
+    form: Form<RevokeSessionsForm>,
+    cookies: &CookieJar<'_>,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    users::revoke_sessions(pool, cookies, user_id, admin.id, form.confirm_self).await
+}
+
+/// Re-enables an account disabled for inactivity (admin only).
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+///
+/// # Parameters
+/// * `user_id` - ID of the user to re-enable
+/// * `admin` - Admin user performing the action
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to admin users page
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/admin/users/<user_id>/enable")]
+pub async fn enable_user(
+    user_id: i64,
+    admin: AdminUser,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    users::enable_user(pool, user_id, admin.id).await
+}
+
+/// Deletes a user account (admin only).
+///
+/// The user's polls, votes, and comments are reassigned or deleted
+/// according to `USER_DELETE_POLICY`, see [`users::delete_user`].
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+/// and a recent password confirmation (see [`crate::auth`])
+///
+/// # Parameters
+/// * `user_id` - ID of the user to delete
+/// * `admin` - Admin user performing the action
+/// * `cookies` - Cookie jar holding the re-authentication marker
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to admin users page
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/admin/users/<user_id>/delete")]
+pub async fn delete_user(
+    user_id: i64,
+    admin: AdminUser,
+    cookies: &CookieJar<'_>,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    if let Some(prompt) = reauth_prompt(cookies, &admin, "/admin/users") {
+        return Err(prompt);
+    }
+    users::delete_user(pool, user_id, admin.id).await
+}
+
+/// Merges a duplicate user account into another (admin only).
+///
+/// The source account's polls, votes, and comments move to the target
+/// account and the source is deleted, see [`users::merge_users`].
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+/// and a recent password confirmation (see [`crate::auth`])
+///
+/// # Parameters
+/// * `admin` - Admin user performing the action
+/// * `form` - Source and target account IDs
+/// * `cookies` - Cookie jar holding the re-authentication marker
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Success redirect to admin users page
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/admin/users/merge", data = "<form>")]
+pub async fn merge_users(
+    admin: AdminUser,
+    form: Form<MergeUsersForm>,
+    cookies: &CookieJar<'_>,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    if let Some(prompt) = reauth_prompt(cookies, &admin, "/admin/users") {
+        return Err(prompt);
+    }
+    users::merge_users(pool, form.source_id, form.target_id, admin.id).await
+}
+
+/// Displays the most recent audit log entries (admin only).
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+///
+/// # Parameters
+/// * `admin` - Admin user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
+/// * `pool` - Database connection pool
+/// * `flash` - Optional flash messages
+///
+/// # Returns
+/// * `Ok(Template)` - Audit log page template
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/admin/audit")]
+pub async fn admin_audit(
+    admin: AdminUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
+    pool: &State<SqlitePool>,
+    flash: Option<rocket::request::FlashMessage<'_>>,
+) -> Result<Template, Status> {
+    let entries = audit::get_recent_entries(pool, 200)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    Ok(Template::render(
+        "admin_audit",
+        context! {
+            title: "Audit Log - Platform Engineering Game Night",
+            user: admin.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            entries: entries,
+            flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
+        },
+    ))
+}
+
+/// Displays site-wide statistics (admin only).
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+///
+/// # Parameters
+/// * `admin` - Admin user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Template)` - Statistics page template
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/admin/stats")]
+pub async fn admin_stats(
+    admin: AdminUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
+    pool: &State<SqlitePool>,
+) -> Result<Template, Status> {
+    let stats = crate::db::get_admin_stats(pool)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    Ok(Template::render(
+        "admin_stats",
+        context! {
+            title: "Statistics - Platform Engineering Game Night",
+            user: admin.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            stats: stats,
+        },
+    ))
+}
+
+/// Number of days the admin trends page covers when `days` isn't given.
+const DEFAULT_TREND_DAYS: i64 = 30;
+
+/// Displays daily counts of polls created and votes cast (admin only).
+///
+/// Covers the last `days` days including today (30 by default, at most
+/// [`crate::db::MAX_TREND_DAYS`]), with a bar for each day.
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+///
+/// # Parameters
+/// * `days` - Length of the window in days
+/// * `admin` - Admin user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Template)` - Trends page template
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/admin/trends?<days>")]
+pub async fn admin_trends(
+    days: Option<i64>,
+    admin: AdminUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
+    pool: &State<SqlitePool>,
+) -> Result<Template, Status> {
+    let trends = crate::db::get_trends(pool, days.unwrap_or(DEFAULT_TREND_DAYS))
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    Ok(Template::render(
+        "admin_trends",
+        context! {
+            title: "Trends - Platform Engineering Game Night",
+            user: admin.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            trends: trends,
+            max_days: crate::db::MAX_TREND_DAYS,
+        },
+    ))
+}
+
+/// Returns the daily counts shown on the trends page as JSON, for external
+/// charting (admin only).
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+///
+/// # Parameters
+/// * `days` - Length of the window in days (see [`admin_trends`])
+/// * `_admin` - Admin user (authentication only, not used in logic)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Json<Trends>)` - Daily counts, oldest first, with totals
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/admin/trends.json?<days>")]
+pub async fn admin_trends_json(
+    days: Option<i64>,
+    _admin: AdminUser,
+    pool: &State<SqlitePool>,
+) -> Result<Json<crate::db::Trends>, Status> {
+    crate::db::get_trends(pool, days.unwrap_or(DEFAULT_TREND_DAYS))
+        .await
+        .map(Json)
+        .map_err(|_| Status::InternalServerError)
+}
+
+/// Displays security warnings about the instance's configuration (admin only).
+///
+/// Flags admins still using the default `admin` password and whether a
+/// `ROCKET_SECRET_KEY` is configured. Both are checked on each visit.
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+///
+/// # Parameters
+/// * `admin` - Admin user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Template)` - Security status page template
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/admin/security")]
+pub async fn admin_security(
+    admin: AdminUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
+    pool: &State<SqlitePool>,
+) -> Result<Template, Status> {
+    let default_password_admins = crate::db::admins_with_default_password(pool)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let secret_key_configured = crate::auth::secret_key_configured(&rocket::Config::figment());
+
+    Ok(Template::render(
+        "admin_security",
+        context! {
+            title: "Security - Platform Engineering Game Night",
+            user: admin.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            default_password_admins: default_password_admins,
+            secret_key_configured: secret_key_configured,
+        },
+    ))
+}
+
+/// Displays the results of a database integrity check (admin only).
+///
+/// Counts orphaned votes, comments, and options, and polls without options.
+/// The check runs on each visit.
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+///
+/// # Parameters
+/// * `admin` - Admin user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
+/// * `pool` - Database connection pool
+/// * `flash` - Optional flash messages from a repair
+///
+/// # Returns
+/// * `Ok(Template)` - Integrity check page template
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/admin/integrity")]
+pub async fn admin_integrity(
+    admin: AdminUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
+    pool: &State<SqlitePool>,
+    flash: Option<rocket::request::FlashMessage<'_>>,
+) -> Result<Template, Status> {
+    let report = integrity::check(pool)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let orphans = report.orphaned_votes + report.orphaned_comments + report.orphaned_options;
+
+    Ok(Template::render(
+        "admin_integrity",
+        context! {
+            title: "Integrity - Platform Engineering Game Night",
+            user: admin.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            report: report,
+            orphans: orphans,
+            flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
+        },
+    ))
+}
+
+/// Deletes orphaned rows found by the integrity check (admin only).
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+///
+/// # Parameters
+/// * `admin` - Admin user (enforced by request guard)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Flash<Redirect>)` - Redirects to the integrity page with what was removed
+/// * `Err(Flash<Redirect>)` - Redirects with error message
+#[post("/admin/integrity/repair")]
+pub async fn repair_integrity(
+    admin: AdminUser,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    match integrity::repair(pool, admin.id).await {
+        Ok(removed) => Ok(Flash::success(
+            Redirect::to(uri!(admin_integrity)),
+            format!(
+                "Removed {} orphaned vote(s), {} orphaned comment(s), and {} orphaned option(s).",
+                removed.orphaned_votes, removed.orphaned_comments, removed.orphaned_options
+            ),
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(admin_integrity)),
+            format!("Failed to repair the database: {}", err),
+        )),
+    }
+}
 
-/// Displays the user profile page with statistics.
+/// Displays the moderation queue of polls waiting for approval (admin only).
 ///
-/// This route shows the user's profile information including
-/// statistics about polls created and votes cast.
+/// New polls wait here when `REQUIRE_POLL_APPROVAL` is set. Each can be
+/// approved, or opened and deleted like any other poll.
+///
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
 ///
 /// # Parameters
-/// * `user` - Authenticated user (enforced by request guard)
+/// * `admin` - Admin user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
 /// * `pool` - Database connection pool
-/// * `flash` - Optional flash messages from profile updates
+/// * `flash` - Optional flash messages from an approval
 ///
 /// # Returns
-/// * `Ok(Template)` - Profile page template with user statistics
+/// * `Ok(Template)` - Moderation queue template
 /// * `Err(Status::InternalServerError)` - If database query fails
-#[get("/profile")]
-pub async fn profile(
-    user: AuthenticatedUser,
+#[get("/admin/polls/pending")]
+pub async fn admin_pending_polls(
+    admin: AdminUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
     pool: &State<SqlitePool>,
     flash: Option<rocket::request::FlashMessage<'_>>,
 ) -> Result<Template, Status> {
-    // Get user statistics
-    let (polls_created, votes_cast) = users::get_user_stats(pool, user.id)
+    let pending_polls = polls::get_pending_polls(pool)
         .await
         .map_err(|_| Status::InternalServerError)?;
 
     Ok(Template::render(
-        "profile",
+        "admin_pending_polls",
         context! {
-            title: "User Profile - Platform Engineering Game Night",
-            user: user.user,
-            polls_created: polls_created,
-            votes_cast: votes_cast,
+            title: "Pending Polls - Platform Engineering Game Night",
+            user: admin.user,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            pending_polls: pending_polls,
+            approval_required: polls::poll_approval_required(),
             flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
         },
     ))
 }
 
-/// Handles password change requests.
+/// Approves a poll waiting for approval, publishing it (admin only).
 ///
-/// This route processes password change forms, validates the current
-/// password, and updates the user's password hash in the database.
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
 ///
 /// # Parameters
-/// * `user` - Authenticated user (enforced by request guard)
-/// * `form` - Password change form data
+/// * `poll_id` - Unique identifier of the poll
+/// * `admin` - Admin user (enforced by request guard)
 /// * `pool` - Database connection pool
 ///
 /// # Returns
-/// * `Ok(Flash<Redirect>)` - Success redirect to profile page
-/// * `Err(Flash<Redirect>)` - Error redirect to profile page
-#[post("/profile/password", data = "<form>")]
-pub async fn change_password(
-    user: AuthenticatedUser,
-    form: Form<ChangePasswordForm>,
+/// * `Ok(Flash<Redirect>)` - Redirects to the moderation queue
+/// * `Err(Flash<Redirect>)` - Redirects with error message
+#[post("/admin/polls/<poll_id>/approve")]
+pub async fn approve_poll(
+    poll_id: i64,
+    admin: AdminUser,
     pool: &State<SqlitePool>,
 ) -> Result<Flash<Redirect>, Flash<Redirect>> {
-    users::change_password(pool, user.id, &form).await
+    match polls::approve_poll(pool, poll_id, admin.id).await {
+        Ok(()) => Ok(Flash::success(
+            Redirect::to(uri!(admin_pending_polls)),
+            "Poll approved. It now appears in the poll listings.",
+        )),
+        Err(sqlx::Error::RowNotFound) => Err(Flash::error(
+            Redirect::to(uri!(admin_pending_polls)),
+            "Poll not found.",
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(admin_pending_polls)),
+            format!("Failed to approve poll: {}", err),
+        )),
+    }
 }
 
-// ============================================================================
-// Admin routes (require admin privileges)
-// ============================================================================
-
-/// Displays the admin user management page.
+/// Returns the admin statistics as JSON for tooling (admin only).
 ///
-/// This route shows all users in the system and provides admin
-/// controls for managing user roles and accounts.
+/// # Access Control
+/// Requires admin privileges (enforced by AdminUser request guard)
+///
+/// # Parameters
+/// * `_admin` - Admin user (authentication only, not used in logic)
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `Ok(Json<AdminStats>)` - Current statistics
+/// * `Err(Status::InternalServerError)` - If database query fails
+#[get("/admin/stats.json")]
+pub async fn admin_stats_json(
+    _admin: AdminUser,
+    pool: &State<SqlitePool>,
+) -> Result<Json<crate::db::AdminStats>, Status> {
+    crate::db::get_admin_stats(pool)
+        .await
+        .map(Json)
+        .map_err(|_| Status::InternalServerError)
+}
+
+/// Displays the runtime settings page (admin only).
 ///
 /// # Access Control
 /// Requires admin privileges (enforced by AdminUser request guard)
 ///
 /// # Parameters
 /// * `admin` - Admin user (enforced by request guard)
+/// * `announcement` - Active site announcement, shown as a banner
+/// * `unread` - The user's unread notification count, shown in the navigation
 /// * `pool` - Database connection pool
-/// * `flash` - Optional flash messages from admin actions
+/// * `flash` - Optional flash messages from settings changes
 ///
 /// # Returns
-/// * `Ok(Template)` - Admin users page template
+/// * `Ok(Template)` - Settings page template
 /// * `Err(Status::InternalServerError)` - If database query fails
-#[get("/admin/users")]
-pub async fn admin_users(
+#[get("/admin/settings")]
+pub async fn admin_settings(
     admin: AdminUser,
+    announcement: Announcement,
+    unread: UnreadNotifications,
     pool: &State<SqlitePool>,
     flash: Option<rocket::request::FlashMessage<'_>>,
 ) -> Result<Template, Status> {
-    let users = users::get_all_users(pool)
+    let poll_creation_enabled = settings::poll_creation_enabled(pool)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let site_announcement = settings::site_announcement(pool)
         .await
         .map_err(|_| Status::InternalServerError)?;
 
     Ok(Template::render(
-        "admin_users",
+        "admin_settings",
         context! {
-            title: "Manage Users - Platform Engineering Game Night",
+            title: "Settings - Platform Engineering Game Night",
             user: admin.user,
-            users: users,
+            announcement: announcement.0,
+            unread_notifications: unread.0,
+            poll_creation_enabled: poll_creation_enabled,
+            site_announcement: site_announcement,
             flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
         },
     ))
 }
 
-/// Handles user role changes (promote/demote admin status).
-///
-/// This route allows admins to change user roles between regular
-/// user and admin status. Includes safety checks to prevent
-/// admins from demoting themselves.
+/// Handles runtime settings updates (admin only).
 ///
 /// # Access Control
 /// Requires admin privileges (enforced by AdminUser request guard)
 ///
 /// # Parameters
-/// * `admin` - Admin user performing the action
-/// * `form` - Role toggle form data
+/// * `admin` - Admin user performing the change
+/// * `form` - Settings form data
 /// * `pool` - Database connection pool
 ///
 /// # Returns
-/// * `Ok(Flash<Redirect>)` - Success redirect to admin users page
+/// * `Ok(Flash<Redirect>)` - Success redirect to settings page
 /// * `Err(Flash<Redirect>)` - Error redirect with message
-#[post("/admin/users/role", data = "<form>")]
-pub async fn toggle_user_role(
+#[post("/admin/settings", data = "<form>")]
+pub async fn update_settings(
     admin: AdminUser,
-    form: Form<ToggleRoleForm>,
+    form: Form<SettingsForm>,
     pool: &State<SqlitePool>,
 ) -> Result<Flash<Redirect>, Flash<Redirect>> {
-    users::toggle_user_role(pool, form.user_id, form.set_admin, admin.id).await
+    match settings::set_poll_creation_enabled(pool, form.poll_creation_enabled, admin.id).await {
+        Ok(_) => Ok(Flash::success(
+            Redirect::to(uri!(admin_settings)),
+            "Settings updated successfully.",
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(admin_settings)),
+            format!("Failed to update settings: {}", err),
+        )),
+    }
 }
 
-/// Displays the add user form page (admin only).
+/// Sets or clears the site-wide announcement banner (admin only).
 ///
-/// This route renders the form for creating new user accounts,
-/// including options for setting admin privileges.
+/// Submitting an empty message clears the announcement.
 ///
 /// # Access Control
 /// Requires admin privileges (enforced by AdminUser request guard)
 ///
 /// # Parameters
-/// * `admin` - Admin user (enforced by request guard)
-/// * `flash` - Optional flash messages from previous creation attempts
+/// * `admin` - Admin user performing the change
+/// * `form` - Announcement form data
+/// * `pool` - Database connection pool
 ///
 /// # Returns
-/// Add user form template
-#[get("/admin/users/add")]
-pub async fn add_user_page(
+/// * `Ok(Flash<Redirect>)` - Success redirect to settings page
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/admin/announcement", data = "<form>")]
+pub async fn update_announcement(
     admin: AdminUser,
-    flash: Option<rocket::request::FlashMessage<'_>>,
-) -> Template {
-    Template::render(
-        "add_user",
-        context! {
-            title: "Add User - Platform Engineering Game Night",
-            user: admin.user,
-            flash: flash.map(|msg| (msg.kind().to_string(), msg.message().to_string())),
-        },
-    )
+    form: Form<AnnouncementForm>,
+    pool: &State<SqlitePool>,
+) -> Result<Flash<Redirect>, Flash<Redirect>> {
+    match settings::set_site_announcement(pool, &form.message, form.active, admin.id).await {
+        Ok(_) if form.message.trim().is_empty() => Ok(Flash::success(
+            Redirect::to(uri!(admin_settings)),
+            "Announcement cleared.",
+        )),
+        Ok(_) => Ok(Flash::success(
+            Redirect::to(uri!(admin_settings)),
+            "Announcement updated.",
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(admin_settings)),
+            format!("Failed to update announcement: {}", err),
+        )),
+    }
 }
 
-/// Handles new user creation form submission (admin only).
+/// Sends a notification to every user (admin only).
 ///
-/// This route processes new user forms, validates the data,
-/// and creates new user accounts in the database.
+/// Broadcasts show up in each recipient's notifications on the dashboard.
 ///
 /// # Access Control
 /// Requires admin privileges (enforced by AdminUser request guard)
 ///
 /// # Parameters
-/// * `_admin` - Admin user (authentication only, not used in logic)
-/// * `form` - New user form data
+/// * `admin` - Admin user sending the broadcast
+/// * `form` - Broadcast form data
 /// * `pool` - Database connection pool
 ///
 /// # Returns
-/// * `Ok(Flash<Redirect>)` - Success redirect to admin users page
-/// * `Err(Flash<Redirect>)` - Error redirect to add user page
-#[post("/admin/users/add", data = "<form>")]
-pub async fn add_user_post(
-    _admin: AdminUser,
-    form: Form<NewUserForm>,
+/// * `Ok(Flash<Redirect>)` - Success redirect to settings page with the number notified
+/// * `Err(Flash<Redirect>)` - Error redirect with message
+#[post("/admin/broadcast", data = "<form>")]
+pub async fn broadcast(
+    admin: AdminUser,
+    form: Form<BroadcastForm>,
     pool: &State<SqlitePool>,
 ) -> Result<Flash<Redirect>, Flash<Redirect>> {
-    users::add_user_controller(pool, &form).await
+    match notify::broadcast(pool, admin.id, &form.subject, &form.body).await {
+        Ok(sent) => Ok(Flash::success(
+            Redirect::to(uri!(admin_settings)),
+            format!("Broadcast sent to {} user{}.", sent, if sent == 1 { "" } else { "s" }),
+        )),
+        Err(sqlx::Error::ColumnDecode { source, .. }) => Err(Flash::error(
+            Redirect::to(uri!(admin_settings)),
+            source.to_string(),
+        )),
+        Err(err) => Err(Flash::error(
+            Redirect::to(uri!(admin_settings)),
+            format!("Failed to send broadcast: {}", err),
+        )),
+    }
 }
 
 // ============================================================================
@@ -742,10 +3226,708 @@ pub async fn add_user_post(
 ///
 /// # Parameters
 /// * `pool` - Database connection pool for updating metrics
+/// * `refresh` - Tracks when the metrics were last refreshed
 ///
 /// # Returns
 /// Plain text response in Prometheus exposition format
 #[get("/metrics")]
-pub async fn metrics_endpoint(pool: &State<SqlitePool>) -> String {
-    crate::db::get_metrics(pool).await
+pub async fn metrics_endpoint(
+    pool: &State<SqlitePool>,
+    refresh: &State<crate::db::MetricsRefresh>,
+) -> String {
+    crate::db::get_metrics(pool, refresh).await
+}
+
+/// Process start time, managed by Rocket for reporting uptime.
+pub struct StartTime(pub Instant);
+
+/// JSON snapshot of application health and statistics.
+///
+/// Provides the same counts as the Prometheus metrics in a form that is
+/// easier for simple dashboards to consume. When the database can't be
+/// queried, `healthy` and `db_ok` are false and the counts are omitted.
+///
+/// # Public Access
+/// This endpoint is intentionally public, like `/metrics`.
+///
+/// # Parameters
+/// * `pool` - Database connection pool
+/// * `start_time` - Process start time
+///
+/// # Returns
+/// JSON body with `healthy`, `db_ok`, the entity counts, `uptime_seconds`, and `version`
+#[get("/status.json")]
+pub async fn status(
+    pool: &State<SqlitePool>,
+    start_time: &State<StartTime>,
+) -> Json<serde_json::Value> {
+    let stats = match crate::db::collect_stats(pool).await {
+        Ok(stats) => Some(stats),
+        Err(err) => {
+            log::warn!("Status check failed to query database: {}", err);
+            None
+        }
+    };
+
+    Json(serde_json::json!({
+        "healthy": stats.is_some(),
+        "db_ok": stats.is_some(),
+        "active_polls": stats.map(|s| s.active_polls),
+        "total_polls": stats.map(|s| s.total_polls),
+        "total_votes": stats.map(|s| s.total_votes),
+        "total_users": stats.map(|s| s.total_users),
+        "uptime_seconds": start_time.0.elapsed().as_secs(),
+        "version": env!("CARGO_PKG_VERSION"),
+    }))
+}
+
+/// Liveness probe endpoint.
+///
+/// This route only confirms that the process is running and able to
+/// handle requests. It intentionally has no dependencies (such as the
+/// database) so that a temporary database outage does not cause the
+/// container to be restarted.
+///
+/// # Public Access
+/// This endpoint is intentionally public so orchestrators can probe it.
+///
+/// # Returns
+/// JSON body `{"status": "ok"}` with a 200 status
+#[get("/api/health")]
+pub async fn health() -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "status": "ok" }))
+}
+
+/// Readiness probe endpoint.
+///
+/// This route verifies that a database connection can be acquired and that
+/// all migrations have been applied before reporting the application as
+/// ready to receive traffic.
+///
+/// # Public Access
+/// This endpoint is intentionally public so orchestrators can probe it.
+///
+/// # Parameters
+/// * `pool` - Database connection pool
+///
+/// # Returns
+/// * `200 OK` with `{"status": "ready"}` when the database is usable
+/// * `503 Service Unavailable` with the reason otherwise
+#[get("/api/ready")]
+pub async fn ready(pool: &State<SqlitePool>) -> (Status, Json<serde_json::Value>) {
+    match crate::db::check_ready(pool).await {
+        Ok(true) => (
+            Status::Ok,
+            Json(serde_json::json!({ "status": "ready" })),
+        ),
+        Ok(false) => (
+            Status::ServiceUnavailable,
+            Json(serde_json::json!({ "status": "migrations pending" })),
+        ),
+        Err(err) => {
+            log::warn!("Readiness check failed: {}", err);
+            (
+                Status::ServiceUnavailable,
+                Json(serde_json::json!({ "status": "database unavailable" })),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use rocket::http::Cookie;
+    use rocket::local::asynchronous::Client;
+
+    /// Inserts a user and returns their ID.
+    async fn add_user(pool: &SqlitePool, username: &str, is_admin: bool) -> i64 {
+        sqlx::query("INSERT INTO users (username, password_hash, is_admin) VALUES (?, '!', ?)")
+            .bind(username)
+            .bind(is_admin)
+            .execute(pool)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    /// Inserts an open poll by `creator_id` and returns its ID.
+    async fn add_poll(pool: &SqlitePool, creator_id: i64) -> i64 {
+        sqlx::query("INSERT INTO polls (title, creator_id, expires_at) VALUES ('Game night', ?, ?)")
+            .bind(creator_id)
+            .bind(Utc::now() + Duration::days(1))
+            .execute(pool)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    /// Starts a client serving the poll page, the JSON poll, profile, and
+    /// dashboard routes, a sensitive admin action, and logging in.
+    async fn client(pool: &SqlitePool) -> Client {
+        let rocket = rocket::build()
+            .manage(pool.clone())
+            .attach(Template::fairing())
+            .mount(
+                "/",
+                rocket::routes![
+                    poll_detail,
+                    poll_ballots,
+                    poll_timeline_json,
+                    option_voters_json,
+                    export_poll_voters_csv,
+                    poll_snapshot,
+                    vote_on_poll,
+                    delete_user,
+                    login_post,
+                    complete_welcome,
+                    public_profile,
+                    poll_counts_json,
+                    poll_meta_json,
+                    current_user,
+                    dashboard_json,
+                    export_my_data,
+                ],
+            );
+        Client::untracked(rocket).await.unwrap()
+    }
+
+    /// Requests `uri`, signed in as `user_id` if given, and returns the status.
+    async fn status(client: &Client, uri: &str, user_id: Option<i64>) -> Status {
+        let mut request = client.get(uri);
+        if let Some(user_id) = user_id {
+            request = request.private_cookie(Cookie::new("user_id", format!("{}:0", user_id)));
+        }
+        request.dispatch().await.status()
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn poll_routes_separate_not_found_from_forbidden(pool: SqlitePool) {
+        let creator = add_user(&pool, "creator", false).await;
+        let other = add_user(&pool, "other", false).await;
+        let admin = add_user(&pool, "admin", true).await;
+        let poll_id = add_poll(&pool, creator).await;
+        let client = client(&pool).await;
+
+        for route in ["ballots.json", "timeline.json"] {
+            let uri = format!("/polls/{}/{}", poll_id, route);
+            assert_eq!(status(&client, &uri, None).await, Status::Unauthorized);
+            assert_eq!(status(&client, &uri, Some(creator)).await, Status::Ok);
+            assert_eq!(status(&client, &uri, Some(admin)).await, Status::Ok);
+            assert_eq!(status(&client, &uri, Some(other)).await, Status::Forbidden);
+
+            let missing = format!("/polls/{}/{}", poll_id + 1, route);
+            assert_eq!(status(&client, &missing, Some(creator)).await, Status::NotFound);
+        }
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn unpublished_poll_is_not_found_for_other_users(pool: SqlitePool) {
+        let creator = add_user(&pool, "creator", false).await;
+        let other = add_user(&pool, "other", false).await;
+        let admin = add_user(&pool, "admin", true).await;
+        let poll_id = add_poll(&pool, creator).await;
+        let option_id = sqlx::query("INSERT INTO options (poll_id, text) VALUES (?, 'Catan')")
+            .bind(poll_id)
+            .execute(&pool)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        sqlx::query("UPDATE polls SET published = 0, open_voters = 1 WHERE id = ?")
+            .bind(poll_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let client = client(&pool).await;
+
+        for route in [
+            "ballots.json".to_string(),
+            "timeline.json".to_string(),
+            "voters.csv".to_string(),
+            format!("options/{}/voters", option_id),
+            "snapshot".to_string(),
+        ] {
+            let uri = format!("/polls/{}/{}", poll_id, route);
+            assert_eq!(status(&client, &uri, Some(other)).await, Status::NotFound, "{}", uri);
+        }
+        for user_id in [creator, admin] {
+            let uri = format!("/polls/{}/ballots.json", poll_id);
+            assert_eq!(status(&client, &uri, Some(user_id)).await, Status::Ok);
+        }
+
+        // Votes from users who can't see the poll are turned away as if it didn't exist
+        let response = client
+            .post(format!("/polls/{}/vote", poll_id))
+            .header(ContentType::Form)
+            .body(format!("option_id={}", option_id))
+            .private_cookie(Cookie::new("user_id", format!("{}:0", other)))
+            .dispatch()
+            .await;
+        assert_eq!(response.headers().get_one("Location"), Some("/dashboard"));
+    }
+
+    #[test]
+    fn unread_count_and_announcement_change_the_etag() {
+        let etag = "W/\"v1\"".to_string();
+
+        assert_ne!(
+            UnreadNotifications(0).tag(etag.clone()),
+            UnreadNotifications(1).tag(etag.clone())
+        );
+        assert_eq!(Announcement(None).tag(etag.clone()), etag);
+        assert_ne!(
+            Announcement(Some("Pizza at 6".to_string())).tag(etag.clone()),
+            Announcement(Some("Pizza at 7".to_string())).tag(etag.clone())
+        );
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn poll_page_answers_not_modified_until_it_changes(pool: SqlitePool) {
+        let creator = add_user(&pool, "creator", false).await;
+        let voter = add_user(&pool, "voter", false).await;
+        let poll_id = add_poll(&pool, creator).await;
+        let option_id = sqlx::query("INSERT INTO options (poll_id, text) VALUES (?, 'Catan')")
+            .bind(poll_id)
+            .execute(&pool)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        let client = client(&pool).await;
+        let uri = format!("/polls/{}", poll_id);
+
+        // Requests the page with the given cached ETag, returning the
+        // status and the ETag of the response
+        let get = |etag: Option<String>| {
+            let mut request = client
+                .get(uri.clone())
+                .private_cookie(Cookie::new("user_id", format!("{}:0", voter)));
+            if let Some(etag) = etag {
+                request = request.header(Header::new("If-None-Match", etag));
+            }
+            async move {
+                let response = request.dispatch().await;
+                (response.status(), response.headers().get_one("ETag").map(String::from))
+            }
+        };
+
+        let (status, etag) = get(None).await;
+        assert_eq!(status, Status::Ok);
+        let etag = etag.unwrap();
+        assert_eq!(get(Some(etag.clone())).await, (Status::NotModified, Some(etag.clone())));
+
+        sqlx::query("INSERT INTO votes (user_id, option_id) VALUES (?, ?)")
+            .bind(voter)
+            .bind(option_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let (status, after_vote) = get(Some(etag.clone())).await;
+        assert_eq!(status, Status::Ok);
+        let after_vote = after_vote.unwrap();
+        assert_ne!(after_vote, etag);
+
+        notify::push(&pool, voter, "nudge", "Time to vote", None).await.unwrap();
+        let (status, after_notification) = get(Some(after_vote.clone())).await;
+        assert_eq!(status, Status::Ok);
+        assert_ne!(after_notification.unwrap(), after_vote);
+    }
+
+    #[test]
+    fn redirect_target_must_be_a_local_path() {
+        assert_eq!(local_redirect_target(Some("/admin/users")), Some("/admin/users"));
+        assert_eq!(local_redirect_target(Some("//evil.com")), None);
+        assert_eq!(local_redirect_target(Some("/\\evil.com")), None);
+        assert_eq!(local_redirect_target(Some("https://evil.com")), None);
+        assert_eq!(local_redirect_target(None), None);
+    }
+
+    /// Returns 1 if the user exists, 0 otherwise.
+    async fn user_count(pool: &SqlitePool, user_id: i64) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE id = ?")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn sensitive_action_asks_for_the_password_first(pool: SqlitePool) {
+        let admin = add_user(&pool, "admin", true).await;
+        let other = add_user(&pool, "other", false).await;
+        let client = client(&pool).await;
+        let uri = format!("/admin/users/{}/delete", other);
+        let session = Cookie::new("user_id", format!("{}:0", admin));
+
+        let response = client.post(uri.clone()).private_cookie(session.clone()).dispatch().await;
+        assert_eq!(response.headers().get_one("Location"), Some("/reauth?next=%2Fadmin%2Fusers"));
+        assert_eq!(user_count(&pool, other).await, 1);
+
+        let reauth = Cookie::new("reauth", format!("{}:0:{}", admin, Utc::now().timestamp()));
+        let response = client
+            .post(uri)
+            .private_cookie(session)
+            .private_cookie(reauth)
+            .dispatch()
+            .await;
+        assert_eq!(response.headers().get_one("Location"), Some("/admin/users"));
+        assert_eq!(user_count(&pool, other).await, 0);
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn welcome_page_is_shown_on_first_login_only(pool: SqlitePool) {
+        let user_id = sqlx::query("INSERT INTO users (username, password_hash, has_onboarded) VALUES ('alice', ?, 0)")
+            .bind(bcrypt::hash("hunter2", 4).unwrap())
+            .execute(&pool)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        let client = client(&pool).await;
+        let log_in = || {
+            client
+                .post("/login")
+                .header(ContentType::Form)
+                .body("username=alice&password=hunter2")
+                .dispatch()
+        };
+
+        let response = log_in().await;
+        assert_eq!(response.headers().get_one("Location"), Some("/welcome"));
+
+        let response = client
+            .post("/welcome/complete")
+            .header(ContentType::Form)
+            .body("")
+            .private_cookie(Cookie::new("user_id", format!("{}:0", user_id)))
+            .dispatch()
+            .await;
+        assert_eq!(response.headers().get_one("Location"), Some("/dashboard"));
+
+        let response = log_in().await;
+        assert_eq!(response.headers().get_one("Location"), Some("/dashboard"));
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn open_voters_polls_show_voters_to_those_who_voted(pool: SqlitePool) {
+        let creator = add_user(&pool, "creator", false).await;
+        let voter = add_user(&pool, "voter", false).await;
+        let poll_id = add_poll(&pool, creator).await;
+        let option_id = sqlx::query("INSERT INTO options (poll_id, text) VALUES (?, 'Catan')")
+            .bind(poll_id)
+            .execute(&pool)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        sqlx::query("INSERT INTO votes (user_id, option_id) VALUES (?, ?)")
+            .bind(voter)
+            .bind(option_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let client = client(&pool).await;
+        let uri = format!("/polls/{}/voters.csv", poll_id);
+
+        // Voting alone isn't enough until the poll opens its voters list
+        assert_eq!(status(&client, &uri, Some(creator)).await, Status::Ok);
+        assert_eq!(status(&client, &uri, Some(voter)).await, Status::Forbidden);
+
+        sqlx::query("UPDATE polls SET open_voters = 1 WHERE id = ?")
+            .bind(poll_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let other = add_user(&pool, "other", false).await;
+        assert_eq!(status(&client, &uri, Some(voter)).await, Status::Ok);
+        assert_eq!(status(&client, &uri, Some(other)).await, Status::Forbidden);
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn status_reports_counts_until_the_database_fails(pool: SqlitePool) {
+        let creator = add_user(&pool, "creator", false).await;
+        add_poll(&pool, creator).await;
+        let rocket = rocket::build()
+            .manage(pool.clone())
+            .manage(StartTime(Instant::now()))
+            .mount("/", rocket::routes![status]);
+        let client = Client::untracked(rocket).await.unwrap();
+
+        let body: serde_json::Value =
+            client.get("/status.json").dispatch().await.into_json().await.unwrap();
+        assert_eq!(body["healthy"], true);
+        assert_eq!(body["db_ok"], true);
+        assert_eq!((body["total_users"].as_i64(), body["total_polls"].as_i64()), (Some(1), Some(1)));
+        assert_eq!((body["active_polls"].as_i64(), body["total_votes"].as_i64()), (Some(1), Some(0)));
+        assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+
+        pool.close().await;
+        let body: serde_json::Value =
+            client.get("/status.json").dispatch().await.into_json().await.unwrap();
+        assert_eq!(body["healthy"], false);
+        assert!(body["total_polls"].is_null());
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn public_profile_needs_a_login_and_hides_private_fields(pool: SqlitePool) {
+        let alice = add_user(&pool, "alice", false).await;
+        let bob = add_user(&pool, "bob", false).await;
+        let admin = add_user(&pool, "admin", true).await;
+        add_poll(&pool, alice).await;
+        sqlx::query("UPDATE users SET last_login_at = CURRENT_TIMESTAMP WHERE id = ?")
+            .bind(alice)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let client = client(&pool).await;
+        let uri = format!("/users/{}", alice);
+
+        assert_eq!(status(&client, &uri, None).await, Status::Unauthorized);
+        let missing = format!("/users/{}", alice + 100);
+        assert_eq!(status(&client, &missing, Some(bob)).await, Status::NotFound);
+
+        let profile = |viewer: i64| {
+            let request = client
+                .get(uri.clone())
+                .private_cookie(Cookie::new("user_id", format!("{}:0", viewer)));
+            async move { request.dispatch().await.into_json::<serde_json::Value>().await.unwrap() }
+        };
+        let seen_by_bob = profile(bob).await;
+        assert_eq!(seen_by_bob["username"], "alice");
+        assert_eq!(seen_by_bob["poll_count"], 1);
+        assert!(seen_by_bob.get("last_login_at").is_none());
+        assert!(seen_by_bob.get("password_hash").is_none());
+        assert!(seen_by_bob.get("session_epoch").is_none());
+
+        assert!(profile(admin).await["last_login_at"].is_string());
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn vote_counts_json_reports_counts_until_unchanged(pool: SqlitePool) {
+        let creator = add_user(&pool, "creator", false).await;
+        let voter = add_user(&pool, "voter", false).await;
+        let poll_id = add_poll(&pool, creator).await;
+        let mut option_ids = Vec::new();
+        for text in ["Catan", "Azul"] {
+            let option_id = sqlx::query("INSERT INTO options (poll_id, text) VALUES (?, ?)")
+                .bind(poll_id)
+                .bind(text)
+                .execute(&pool)
+                .await
+                .unwrap()
+                .last_insert_rowid();
+            option_ids.push(option_id);
+        }
+        sqlx::query("INSERT INTO votes (user_id, option_id) VALUES (?, ?)")
+            .bind(voter)
+            .bind(option_ids[0])
+            .execute(&pool)
+            .await
+            .unwrap();
+        let client = client(&pool).await;
+        let uri = format!("/polls/{}/counts.json", poll_id);
+        assert_eq!(status(&client, &uri, None).await, Status::Unauthorized);
+
+        let response = client
+            .get(uri.clone())
+            .private_cookie(Cookie::new("user_id", format!("{}:0", voter)))
+            .dispatch()
+            .await;
+        let etag = response.headers().get_one("ETag").unwrap().to_string();
+        let body: serde_json::Value = response.into_json().await.unwrap();
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["counts"][option_ids[0].to_string()], 1);
+        assert_eq!(body["counts"][option_ids[1].to_string()], 0);
+
+        let cached = client
+            .get(uri.clone())
+            .private_cookie(Cookie::new("user_id", format!("{}:0", voter)))
+            .header(Header::new("If-None-Match", etag.clone()))
+            .dispatch()
+            .await;
+        assert_eq!(cached.status(), Status::NotModified);
+
+        sqlx::query("INSERT INTO votes (user_id, option_id) VALUES (?, ?)")
+            .bind(creator)
+            .bind(option_ids[1])
+            .execute(&pool)
+            .await
+            .unwrap();
+        let changed = client
+            .get(uri)
+            .private_cookie(Cookie::new("user_id", format!("{}:0", voter)))
+            .header(Header::new("If-None-Match", etag))
+            .dispatch()
+            .await;
+        assert_eq!(changed.status(), Status::Ok);
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn poll_meta_json_leaves_out_options_and_tracks_expiry(pool: SqlitePool) {
+        let creator = add_user(&pool, "creator", false).await;
+        let poll_id = add_poll(&pool, creator).await;
+        let client = client(&pool).await;
+        let uri = format!("/polls/{}/meta.json", poll_id);
+        assert_eq!(status(&client, &uri, None).await, Status::Unauthorized);
+
+        let get = |etag: Option<String>| {
+            let mut request = client
+                .get(uri.clone())
+                .private_cookie(Cookie::new("user_id", format!("{}:0", creator)));
+            if let Some(etag) = etag {
+                request = request.header(Header::new("If-None-Match", etag));
+            }
+            request.dispatch()
+        };
+
+        let response = get(None).await;
+        let etag = response.headers().get_one("ETag").unwrap().to_string();
+        let meta: serde_json::Value = response.into_json().await.unwrap();
+        assert_eq!(meta["title"], "Game night");
+        assert_eq!(meta["creator_username"], "creator");
+        assert_eq!(meta["is_expired"], false);
+        assert!(meta.get("options").is_none());
+
+        assert_eq!(get(Some(etag.clone())).await.status(), Status::NotModified);
+
+        // Closing the poll changes the tag
+        sqlx::query("UPDATE polls SET expires_at = ? WHERE id = ?")
+            .bind(Utc::now() - Duration::minutes(1))
+            .bind(poll_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let response = get(Some(etag)).await;
+        assert_eq!(response.status(), Status::Ok);
+        let meta: serde_json::Value = response.into_json().await.unwrap();
+        assert_eq!(meta["is_expired"], true);
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn own_profile_json_has_stats_but_no_secrets(pool: SqlitePool) {
+        let alice = add_user(&pool, "alice", false).await;
+        add_poll(&pool, alice).await;
+        let client = client(&pool).await;
+        assert_eq!(status(&client, "/api/me", None).await, Status::Unauthorized);
+
+        let me: serde_json::Value = client
+            .get("/api/me")
+            .private_cookie(Cookie::new("user_id", format!("{}:0", alice)))
+            .dispatch()
+            .await
+            .into_json()
+            .await
+            .unwrap();
+        assert_eq!(me["id"], alice);
+        assert_eq!(me["username"], "alice");
+        assert_eq!(me["polls_created"], 1);
+        assert_eq!(me["votes_cast"], 0);
+        assert!(me.get("password_hash").is_none());
+        assert!(me.get("session_epoch").is_none());
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn dashboard_json_totals_votes_and_skips_archived_polls(pool: SqlitePool) {
+        let alice = add_user(&pool, "alice", false).await;
+        let bob = add_user(&pool, "bob", false).await;
+        let open = add_poll(&pool, alice).await;
+        let expired = add_poll(&pool, alice).await;
+        let archived = add_poll(&pool, alice).await;
+        sqlx::query("UPDATE polls SET expires_at = ? WHERE id = ?")
+            .bind(Utc::now() - Duration::days(1))
+            .bind(expired)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE polls SET archived = 1 WHERE id = ?")
+            .bind(archived)
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (text, voters) in [("Catan", vec![alice, bob]), ("Azul", vec![bob])] {
+            let option_id = sqlx::query("INSERT INTO options (poll_id, text) VALUES (?, ?)")
+                .bind(open)
+                .bind(text)
+                .execute(&pool)
+                .await
+                .unwrap()
+                .last_insert_rowid();
+            for user_id in voters {
+                sqlx::query("INSERT INTO votes (user_id, option_id) VALUES (?, ?)")
+                    .bind(user_id)
+                    .bind(option_id)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+            }
+        }
+        let client = client(&pool).await;
+        assert_eq!(status(&client, "/api/dashboard", None).await, Status::Unauthorized);
+
+        let dashboard: serde_json::Value = client
+            .get("/api/dashboard")
+            .private_cookie(Cookie::new("user_id", format!("{}:0", alice)))
+            .dispatch()
+            .await
+            .into_json()
+            .await
+            .unwrap();
+        assert_eq!(dashboard["user"]["username"], "alice");
+        let active = dashboard["active_polls"].as_array().unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0]["id"], open);
+        assert_eq!(active[0]["total_votes"], 3);
+        assert_eq!(active[0]["voter_count"], 2);
+        let expired_polls = dashboard["expired_polls"].as_array().unwrap();
+        assert_eq!(expired_polls.len(), 1);
+        assert_eq!(expired_polls[0]["id"], expired);
+        assert_eq!(expired_polls[0]["total_votes"], 0);
+        assert_eq!(dashboard["stats"]["votes_cast"], 1);
+    }
+
+    #[sqlx::test(migrator = "crate::db::MIGRATOR")]
+    async fn data_export_holds_own_polls_and_votes_only(pool: SqlitePool) {
+        let alice = add_user(&pool, "alice", false).await;
+        let bob = add_user(&pool, "bob", false).await;
+        for (creator, voters) in [(alice, vec![alice]), (alice, vec![]), (bob, vec![alice, bob])] {
+            let poll_id = add_poll(&pool, creator).await;
+            let option_id = sqlx::query("INSERT INTO options (poll_id, text) VALUES (?, 'Catan')")
+                .bind(poll_id)
+                .execute(&pool)
+                .await
+                .unwrap()
+                .last_insert_rowid();
+            for user_id in voters {
+                sqlx::query("INSERT INTO votes (user_id, option_id) VALUES (?, ?)")
+                    .bind(user_id)
+                    .bind(option_id)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+            }
+        }
+        let client = client(&pool).await;
+        assert_eq!(status(&client, "/profile/export.json", None).await, Status::Unauthorized);
+
+        let response = client
+            .get("/profile/export.json")
+            .private_cookie(Cookie::new("user_id", format!("{}:0", alice)))
+            .dispatch()
+            .await;
+        assert_eq!(
+            response.headers().get_one("Content-Disposition"),
+            Some("attachment; filename=\"game-night-export.json\"")
+        );
+        let export: serde_json::Value = response.into_json().await.unwrap();
+        assert_eq!(export["user"]["username"], "alice");
+        assert!(export["user"].get("password_hash").is_none());
+        assert!(export["user"].get("session_epoch").is_none());
+
+        let polls = export["polls"].as_array().unwrap();
+        assert_eq!(polls.len(), 2);
+        assert!(polls.iter().all(|poll| poll["creator_id"] == alice));
+        assert!(polls.iter().all(|poll| poll["options"][0]["text"] == "Catan"));
+
+        let votes = export["votes"].as_array().unwrap();
+        assert_eq!(votes.len(), 2);
+        assert!(votes.iter().all(|vote| vote["option_text"] == "Catan"));
+    }
 }